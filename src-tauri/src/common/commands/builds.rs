@@ -1,4 +1,5 @@
 // Tauri 命令模块 - 集中管理所有的 Tauri 命令
+use crate::lcu::error::LcuError;
 use crate::{http_client, lcu};
 use reqwest::Client;
 use serde_json;
@@ -35,7 +36,7 @@ pub async fn get_all_runes() -> Result<lcu::types::AllRunesResponse, String> {
 pub async fn apply_champion_build(
     champion_alias: String,
     build_index: usize,
-) -> Result<String, String> {
+) -> Result<String, LcuError> {
     log::info!(
         "🚀 开始应用英雄详细: {} (详细索引: {})",
         champion_alias,
@@ -50,7 +51,7 @@ pub async fn apply_champion_build(
         Ok(data) => data,
         Err(e) => {
             log::error!("❌ 获取英雄详细数据失败: {}", e);
-            return Err(format!("获取英雄详细数据失败: {}", e));
+            return Err(LcuError::other(format!("获取英雄详细数据失败: {}", e)));
         }
     };
 
@@ -74,7 +75,7 @@ pub async fn apply_champion_build(
             runes_array.len()
         );
         log::error!("❌ {}", msg);
-        return Err(msg);
+        return Err(LcuError::other(msg));
     }
 
     let rune_build = &runes_array[build_index];
@@ -124,7 +125,7 @@ pub async fn apply_champion_build(
         }
         Err(e) => {
             log::error!("❌ 符文应用失败: {}", e);
-            Err(format!("符文配置应用失败: {}", e))
+            Err(e)
         }
     }
 }
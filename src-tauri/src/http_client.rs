@@ -1,6 +1,8 @@
 // HTTP客户端管理模块 - 统一管理HTTP客户端的创建和配置
 use once_cell::sync::Lazy;
 use reqwest::Client;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use std::sync::Arc;
 
 /// 全局的LCU HTTP客户端，单例模式
 static LCU_CLIENT: Lazy<Client> = Lazy::new(|| {
@@ -11,6 +13,36 @@ static LCU_CLIENT: Lazy<Client> = Lazy::new(|| {
         .expect("Failed to create LCU HTTP client")
 });
 
+/// 跳过证书校验，LCU 使用自签名证书（与 LCU_CLIENT 的 danger_accept_invalid_certs 相同）
+struct AcceptInvalidCerts;
+
+impl ServerCertVerifier for AcceptInvalidCerts {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// LCU 连接使用的 rustls 配置（WebSocket 等不经过 reqwest 的连接）
+pub fn get_lcu_tls_config() -> Arc<rustls::ClientConfig> {
+    static LCU_TLS_CONFIG: Lazy<Arc<rustls::ClientConfig>> = Lazy::new(|| {
+        Arc::new(
+            rustls::ClientConfig::builder()
+                .with_safe_defaults()
+                .with_custom_certificate_verifier(Arc::new(AcceptInvalidCerts))
+                .with_no_client_auth(),
+        )
+    });
+    LCU_TLS_CONFIG.clone()
+}
+
 /// 获取配置好的LCU HTTP客户端
 pub fn get_lcu_client() -> &'static Client {
     &LCU_CLIENT
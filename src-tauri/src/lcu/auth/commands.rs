@@ -1,8 +1,26 @@
+use crate::lcu::auth::discovery;
 use crate::lcu::auth::service::AUTH_INFO;
-use crate::lcu::types::LcuAuthInfo;
+use crate::lcu::types::{AuthDiscoveryConfig, AuthDiscoveryReport, LcuAuthInfo};
 
 #[tauri::command]
 pub fn get_auth_info() -> Option<LcuAuthInfo> {
     let auth = AUTH_INFO.read().unwrap();
     auth.as_ref().cloned()
 }
+
+#[tauri::command]
+pub fn get_auth_discovery_config() -> AuthDiscoveryConfig {
+    discovery::get_config()
+}
+
+#[tauri::command]
+pub fn save_auth_discovery_config(
+    config: AuthDiscoveryConfig,
+) -> Result<AuthDiscoveryConfig, String> {
+    discovery::save_config(config)
+}
+
+#[tauri::command]
+pub fn get_auth_discovery_report() -> Option<AuthDiscoveryReport> {
+    discovery::get_last_report()
+}
@@ -0,0 +1,77 @@
+//! 认证发现策略配置（持久化到 auth_config.json）与最近一次发现报告
+use crate::lcu::endpoint::LcuEndpoint;
+use crate::lcu::types::{AuthDiscoveryConfig, AuthDiscoveryReport};
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+static DISCOVERY_CONFIG: Lazy<RwLock<AuthDiscoveryConfig>> =
+    Lazy::new(|| RwLock::new(load_config()));
+static LAST_REPORT: Lazy<RwLock<Option<AuthDiscoveryReport>>> = Lazy::new(|| RwLock::new(None));
+
+fn config_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nidalee").join("auth_config.json"))
+}
+
+fn load_config() -> AuthDiscoveryConfig {
+    let Some(file) = config_file() else {
+        return AuthDiscoveryConfig::default();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return AuthDiscoveryConfig::default();
+    };
+    match serde_json::from_str::<AuthDiscoveryConfig>(&content) {
+        Ok(config) if !config.strategies.is_empty() => config,
+        Ok(_) => AuthDiscoveryConfig::default(),
+        Err(e) => {
+            log::warn!("[LCU] 解析 auth_config.json 失败，使用默认配置: {}", e);
+            AuthDiscoveryConfig::default()
+        }
+    }
+}
+
+/// 当前的策略配置
+pub fn get_config() -> AuthDiscoveryConfig {
+    DISCOVERY_CONFIG.read().unwrap().clone()
+}
+
+/// 保存策略配置（去重，不允许为空）
+pub fn save_config(mut config: AuthDiscoveryConfig) -> Result<AuthDiscoveryConfig, String> {
+    let mut seen = Vec::new();
+    config.strategies.retain(|s| {
+        if seen.contains(s) {
+            false
+        } else {
+            seen.push(*s);
+            true
+        }
+    });
+    if config.strategies.is_empty() {
+        return Err("至少需要启用一种认证发现策略".to_string());
+    }
+    if let Some(endpoint) = &config.endpoint {
+        LcuEndpoint::custom(&endpoint.base_url, &endpoint.token)?;
+    }
+
+    let file = config_file().ok_or("无法获取配置目录")?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&config).map_err(|e| format!("序列化配置失败: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("保存配置失败: {}", e))?;
+
+    *DISCOVERY_CONFIG.write().unwrap() = config.clone();
+    log::info!("[LCU] 认证发现策略已更新: {:?}", config.strategies);
+    Ok(config)
+}
+
+pub fn set_last_report(report: AuthDiscoveryReport) {
+    *LAST_REPORT.write().unwrap() = Some(report);
+}
+
+/// 最近一次认证发现的报告，尚未进行过发现时为 None
+pub fn get_last_report() -> Option<AuthDiscoveryReport> {
+    LAST_REPORT.read().unwrap().clone()
+}
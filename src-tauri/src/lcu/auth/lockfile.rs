@@ -0,0 +1,190 @@
+//! 通过客户端安装目录下的 lockfile 获取认证信息
+//!
+//! lockfile 内容格式: name:pid:port:password:protocol，例如
+//! LeagueClient:12345:54321:abcdef:https
+use crate::lcu::types::LcuAuthInfo;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 各平台的默认安装位置，保存/检测到的路径都找不到时兜底
+const DEFAULT_LOCKFILE_PATHS: &[&str] = &[
+    #[cfg(target_os = "windows")]
+    "C:\\Riot Games\\League of Legends\\lockfile",
+    #[cfg(target_os = "macos")]
+    "/Applications/League of Legends.app/Contents/LoL/lockfile",
+];
+
+/// 解析 lockfile 内容
+pub fn parse_lockfile(content: &str) -> Result<LcuAuthInfo, String> {
+    let parts: Vec<&str> = content.trim().split(':').collect();
+    if parts.len() != 5 {
+        return Err(format!("lockfile 格式错误，字段数: {}", parts.len()));
+    }
+    let app_port = parts[2]
+        .parse::<u16>()
+        .map_err(|_| format!("lockfile 端口无效: {}", parts[2]))?;
+    if parts[3].is_empty() {
+        return Err("lockfile 中密码为空".to_string());
+    }
+    // lockfile 只包含 LCU 的端口和密码，没有 Riot Client 的参数
+    Ok(LcuAuthInfo {
+        app_port,
+        remoting_auth_token: parts[3].to_string(),
+        riotclient_app_port: 0,
+        riotclient_auth_token: String::new(),
+    })
+}
+
+/// 根据游戏路径（LeagueClient.exe、WeGame 的 client.exe 或安装目录）推断 lockfile 可能的位置
+pub fn candidate_lockfile_paths(game_path: &Path) -> Vec<PathBuf> {
+    let base = if game_path.is_file() || game_path.extension().is_some() {
+        game_path.parent().unwrap_or(game_path)
+    } else {
+        game_path
+    };
+
+    let mut dirs = vec![base.to_path_buf()];
+    // WeGame: 英雄联盟\Launcher\client.exe -> 英雄联盟\LeagueClient\lockfile
+    if let Some(parent) = base.parent() {
+        dirs.push(parent.to_path_buf());
+    }
+
+    let mut paths = Vec::new();
+    for dir in dirs {
+        paths.push(dir.join("lockfile"));
+        paths.push(dir.join("LeagueClient").join("lockfile"));
+    }
+    paths
+}
+
+/// 依次尝试候选路径，读取第一个存在且有效的 lockfile（阻塞）
+pub fn read_auth_from_lockfile(game_paths: &[String]) -> Result<LcuAuthInfo, String> {
+    let mut candidates: Vec<PathBuf> = game_paths
+        .iter()
+        .filter(|p| !p.is_empty())
+        .flat_map(|p| candidate_lockfile_paths(Path::new(p)))
+        .collect();
+    candidates.extend(DEFAULT_LOCKFILE_PATHS.iter().map(PathBuf::from));
+
+    for path in &candidates {
+        if !path.exists() {
+            continue;
+        }
+        // 客户端运行时会独占写入 lockfile，但允许共享读取
+        match fs::read_to_string(path) {
+            Ok(content) => match parse_lockfile(&content) {
+                Ok(auth) => {
+                    log::info!("[LCU] 从 lockfile 获取认证信息: {}", path.display());
+                    return Ok(auth);
+                }
+                Err(e) => log::warn!("[LCU] lockfile 无效 {}: {}", path.display(), e),
+            },
+            Err(e) => log::warn!("[LCU] 读取 lockfile 失败 {}: {}", path.display(), e),
+        }
+    }
+    Err(format!(
+        "未找到可用的 lockfile（已检查 {} 个位置）",
+        candidates.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的临时安装目录，结束时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "nidalee-lockfile-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn parses_valid_lockfile() {
+        let auth = parse_lockfile("LeagueClient:12345:54321:abcdef:https\r\n").unwrap();
+        assert_eq!(auth.app_port, 54321);
+        assert_eq!(auth.remoting_auth_token, "abcdef");
+        assert_eq!(auth.riotclient_app_port, 0);
+        assert!(auth.riotclient_auth_token.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_lockfile() {
+        assert!(parse_lockfile("").is_err());
+        assert!(parse_lockfile("LeagueClient:12345:54321:https").is_err());
+        assert!(parse_lockfile("LeagueClient:12345:not-a-port:abcdef:https").is_err());
+        assert!(parse_lockfile("LeagueClient:12345:70000:abcdef:https").is_err());
+        assert!(parse_lockfile("LeagueClient:12345:54321::https").is_err());
+    }
+
+    #[test]
+    fn candidate_paths_for_league_client_exe() {
+        let game_path = Path::new("/games/League of Legends/LeagueClient.exe");
+        let paths = candidate_lockfile_paths(game_path);
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/games/League of Legends/lockfile"),
+                PathBuf::from("/games/League of Legends/LeagueClient/lockfile"),
+                PathBuf::from("/games/lockfile"),
+                PathBuf::from("/games/LeagueClient/lockfile"),
+            ]
+        );
+    }
+
+    #[test]
+    fn candidate_paths_for_wegame_client_exe() {
+        // WeGame: 英雄联盟\Launcher\client.exe，lockfile 在 英雄联盟\LeagueClient 下
+        let game_path = Path::new("/games/英雄联盟/Launcher/client.exe");
+        let paths = candidate_lockfile_paths(game_path);
+        assert!(paths.contains(&PathBuf::from("/games/英雄联盟/LeagueClient/lockfile")));
+        assert_eq!(paths[0], PathBuf::from("/games/英雄联盟/Launcher/lockfile"));
+    }
+
+    #[test]
+    fn reads_first_valid_lockfile() {
+        let install = TempDir::new("read");
+        let launcher = install.0.join("Launcher");
+        let client_dir = install.0.join("LeagueClient");
+        fs::create_dir_all(&launcher).unwrap();
+        fs::create_dir_all(&client_dir).unwrap();
+        // 启动器目录下的 lockfile 无效，应跳过并继续查找
+        fs::write(launcher.join("lockfile"), "broken").unwrap();
+        fs::write(
+            client_dir.join("lockfile"),
+            "LeagueClient:4242:61234:wegame-token:https",
+        )
+        .unwrap();
+
+        let client_exe = launcher.join("client.exe");
+        let auth =
+            read_auth_from_lockfile(&[String::new(), client_exe.to_string_lossy().into_owned()])
+                .unwrap();
+        assert_eq!(auth.app_port, 61234);
+        assert_eq!(auth.remoting_auth_token, "wegame-token");
+    }
+
+    // Windows/macOS 还会检查默认安装位置，本机装有客户端时结果不确定
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn missing_lockfile_is_an_error() {
+        let install = TempDir::new("missing");
+        let err = read_auth_from_lockfile(&[install.0.to_string_lossy().into_owned()]).unwrap_err();
+        assert!(err.contains("未找到可用的 lockfile"));
+    }
+}
@@ -1,2 +1,5 @@
 pub mod commands;
+pub mod discovery;
+pub mod lockfile;
+pub mod provider;
 pub mod service;
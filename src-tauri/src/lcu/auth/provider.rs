@@ -0,0 +1,108 @@
+//! 可插拔的 LCU 凭据来源
+//!
+//! 按注册顺序询问各个 CredentialProvider，第一个返回端点的生效；
+//! 都没有时才回退到进程/lockfile 发现（见 service::ensure_valid_auth_info）
+use crate::lcu::auth::discovery;
+use crate::lcu::endpoint::LcuEndpoint;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, RwLock};
+
+/// 环境变量：LCU 基础地址，如 https://127.0.0.1:2999
+pub const ENV_BASE_URL: &str = "NIDALEE_LCU_BASE_URL";
+/// 环境变量：LCU 认证 token（Basic 认证中 riot 用户的密码）
+pub const ENV_TOKEN: &str = "NIDALEE_LCU_TOKEN";
+
+pub trait CredentialProvider: Send + Sync {
+    /// 用于日志的来源名称
+    fn name(&self) -> &str;
+    /// 当前可用的端点，返回 None 表示该来源未配置
+    fn endpoint(&self) -> Option<LcuEndpoint>;
+}
+
+/// 从环境变量读取端点
+pub struct EnvCredentialProvider;
+
+impl CredentialProvider for EnvCredentialProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn endpoint(&self) -> Option<LcuEndpoint> {
+        let base_url = std::env::var(ENV_BASE_URL).ok()?;
+        let token = std::env::var(ENV_TOKEN).unwrap_or_default();
+        match LcuEndpoint::custom(&base_url, &token) {
+            Ok(endpoint) => Some(endpoint),
+            Err(e) => {
+                log::warn!("[LCU] 环境变量 {} 无效: {}", ENV_BASE_URL, e);
+                None
+            }
+        }
+    }
+}
+
+/// 从 auth_config.json 的 endpoint 字段读取端点
+pub struct ConfigCredentialProvider;
+
+impl CredentialProvider for ConfigCredentialProvider {
+    fn name(&self) -> &str {
+        "config"
+    }
+
+    fn endpoint(&self) -> Option<LcuEndpoint> {
+        let config = discovery::get_config().endpoint?;
+        match LcuEndpoint::custom(&config.base_url, &config.token) {
+            Ok(endpoint) => Some(endpoint),
+            Err(e) => {
+                log::warn!("[LCU] auth_config.json 中的 endpoint 无效: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// 固定端点，供测试或嵌入方直接指定
+#[allow(dead_code)]
+pub struct StaticCredentialProvider(pub LcuEndpoint);
+
+impl CredentialProvider for StaticCredentialProvider {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    fn endpoint(&self) -> Option<LcuEndpoint> {
+        Some(self.0.clone())
+    }
+}
+
+static PROVIDERS: Lazy<RwLock<Vec<Arc<dyn CredentialProvider>>>> =
+    Lazy::new(|| RwLock::new(default_providers()));
+
+fn default_providers() -> Vec<Arc<dyn CredentialProvider>> {
+    vec![
+        Arc::new(EnvCredentialProvider),
+        Arc::new(ConfigCredentialProvider),
+    ]
+}
+
+/// 注册一个凭据来源，优先级高于已有的来源
+#[allow(dead_code)]
+pub fn register_provider(provider: Arc<dyn CredentialProvider>) {
+    log::info!("[LCU] 注册凭据来源: {}", provider.name());
+    PROVIDERS.write().unwrap().insert(0, provider);
+}
+
+/// 恢复为默认的凭据来源（环境变量、配置文件）
+#[allow(dead_code)]
+pub fn reset_providers() {
+    *PROVIDERS.write().unwrap() = default_providers();
+}
+
+/// 依次询问各来源，返回第一个可用的端点
+pub fn provided_endpoint() -> Option<LcuEndpoint> {
+    let providers = PROVIDERS.read().unwrap().clone();
+    providers.iter().find_map(|p| {
+        let endpoint = p.endpoint()?;
+        log::debug!("[LCU] 使用凭据来源 {}: {}", p.name(), endpoint.base_url);
+        Some(endpoint)
+    })
+}
@@ -1,41 +1,71 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::future::Future;
 use std::sync::{Mutex, RwLock};
 use std::time::{Duration, Instant};
 use sysinfo::{ProcessRefreshKind, RefreshKind, System};
 
-use crate::lcu::types::LcuAuthInfo;
+use crate::common::commands::game::{detect_game_path, get_saved_game_path};
+use crate::lcu::auth::{discovery, lockfile, provider};
+use crate::lcu::endpoint::LcuEndpoint;
+use crate::lcu::types::{AuthDiscoveryReport, AuthStrategy, AuthStrategyAttempt, LcuAuthInfo};
 
 pub static AUTH_INFO: Lazy<RwLock<Option<LcuAuthInfo>>> = Lazy::new(|| RwLock::new(None));
 static SYSTEM: Lazy<Mutex<System>> = Lazy::new(|| Mutex::new(System::new()));
 static AUTH_TIMESTAMP: Lazy<RwLock<Option<Instant>>> = Lazy::new(|| RwLock::new(None));
+// 同一时刻只允许一个刷新任务扫描进程，其他调用方等待并复用其结果
+static REFRESH_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
 // 配置：token 最多允许缓存多久，超时自动刷新
 const AUTH_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+// 配置：这段时间内刚刷新过的结果直接复用，避免并发的强制刷新重复扫描进程
+const REFRESH_COALESCE_WINDOW: Duration = Duration::from_secs(1);
+
+/// 读取缓存的 AuthInfo 及其刷新时间
+fn cached_auth_info() -> Option<(LcuAuthInfo, Instant)> {
+    let auth = AUTH_INFO.read().unwrap();
+    let ts = AUTH_TIMESTAMP.read().unwrap();
+    match (auth.as_ref(), ts.as_ref()) {
+        (Some(a), Some(t)) => Some((a.clone(), *t)),
+        _ => None,
+    }
+}
+
+/// 获取当前可用的 LCU 端点：优先使用注册的凭据来源（环境变量、配置等），否则使用本机客户端
+pub async fn ensure_valid_endpoint() -> Option<LcuEndpoint> {
+    if let Some(endpoint) = provider::provided_endpoint() {
+        return Some(endpoint);
+    }
+    ensure_valid_auth_info().await.map(LcuEndpoint::local)
+}
 
 /// 获取（并自动刷新）最新有效的 LCU AuthInfo
-pub fn ensure_valid_auth_info() -> Option<LcuAuthInfo> {
+///
+/// 缓存未过期直接返回；缓存过期时先返回旧值，同时在后台刷新；
+/// 没有缓存时等待刷新完成（LOL 启动初期可能需要多次尝试）
+pub async fn ensure_valid_auth_info() -> Option<LcuAuthInfo> {
     // 1. 先检测缓存是否存在且未超时
-    {
-        let auth = AUTH_INFO.read().unwrap();
-        let ts = AUTH_TIMESTAMP.read().unwrap();
-        if let (Some(a), Some(t)) = (auth.as_ref(), ts.as_ref()) {
-            if t.elapsed() < AUTH_REFRESH_INTERVAL {
-                log::debug!(
-                    "[LCU] 使用缓存的 AuthInfo，距离上次刷新: {:?}秒",
-                    t.elapsed().as_secs()
-                );
-                return Some(a.clone());
-            } else {
-                log::info!("[LCU] AuthInfo 缓存已过期，准备刷新");
-            }
+    if let Some((auth, t)) = cached_auth_info() {
+        if t.elapsed() < AUTH_REFRESH_INTERVAL {
+            log::debug!(
+                "[LCU] 使用缓存的 AuthInfo，距离上次刷新: {:?}秒",
+                t.elapsed().as_secs()
+            );
         } else {
-            log::debug!("[LCU] 当前无有效缓存，准备刷新");
+            log::info!("[LCU] AuthInfo 缓存已过期，后台刷新");
+            spawn_background_refresh();
         }
+        return Some(auth);
     }
+    log::debug!("[LCU] 当前无有效缓存，准备刷新");
 
-    // 2. 带重试的自动刷新（LOL 启动初期可能需要多次尝试）
+    // 2. 带重试的自动刷新，并发调用方共享同一次刷新
+    let _guard = REFRESH_LOCK.lock().await;
+    if let Some((auth, _)) = cached_auth_info() {
+        log::debug!("[LCU] 其他任务已完成刷新，直接使用");
+        return Some(auth);
+    }
     for attempt in 1..=3 {
-        match refresh_auth_info() {
+        match scan_auth_info().await {
             Ok(auth) => {
                 log::info!("[LCU] 自动刷新 AuthInfo 成功 (尝试 {}/3)", attempt);
                 return Some(auth);
@@ -44,7 +74,7 @@ pub fn ensure_valid_auth_info() -> Option<LcuAuthInfo> {
                 log::warn!("[LCU] 自动刷新 AuthInfo 失败 (尝试 {}/3): {}", attempt, e);
                 if attempt < 3 {
                     // 短暂等待后重试
-                    std::thread::sleep(Duration::from_millis(500));
+                    tokio::time::sleep(Duration::from_millis(500)).await;
                 }
             }
         }
@@ -54,14 +84,147 @@ pub fn ensure_valid_auth_info() -> Option<LcuAuthInfo> {
     None
 }
 
-/// 手动强制刷新 AuthInfo（一般不直接用，内部自动调用）
-pub fn refresh_auth_info() -> Result<LcuAuthInfo, String> {
+/// 缓存过期时在后台刷新，已有刷新在进行时直接跳过
+fn spawn_background_refresh() {
+    tokio::spawn(async {
+        let Ok(_guard) = REFRESH_LOCK.try_lock() else {
+            return;
+        };
+        if let Err(e) = scan_auth_info().await {
+            log::warn!("[LCU] 后台刷新 AuthInfo 失败: {}", e);
+        }
+    });
+}
+
+/// 强制刷新 AuthInfo（认证失效时由请求管线调用）
+///
+/// 并发调用会排队，若前一个调用方刚刷新过则直接复用其结果
+pub async fn refresh_auth_info() -> Result<LcuAuthInfo, String> {
+    if provider::provided_endpoint().is_some() {
+        return Err("当前使用外部提供的 LCU 凭据，无法自动刷新".to_string());
+    }
+    let _guard = REFRESH_LOCK.lock().await;
+    if let Some((auth, t)) = cached_auth_info() {
+        if t.elapsed() < REFRESH_COALESCE_WINDOW {
+            log::debug!("[LCU] AuthInfo 刚刚刷新过，复用结果");
+            return Ok(auth);
+        }
+    }
+    scan_auth_info().await
+}
+
+/// 按配置的策略顺序获取认证信息并写入缓存，调用方需持有 REFRESH_LOCK
+async fn scan_auth_info() -> Result<LcuAuthInfo, String> {
     log::info!("[LCU] 开始强制刷新 AuthInfo");
+    let strategies = discovery::get_config().strategies;
+    let (result, report) = run_strategies(&strategies, run_strategy).await;
+    discovery::set_last_report(report);
+    match result {
+        Ok((auth, strategy)) => {
+            store_auth_info(&auth, strategy);
+            Ok(auth)
+        }
+        Err(message) => {
+            log::error!("[LCU] 所有认证发现策略均失败，清空缓存");
+            invalidate_auth_info();
+            Err(message)
+        }
+    }
+}
+
+async fn run_strategy(strategy: AuthStrategy) -> Result<LcuAuthInfo, String> {
+    match strategy {
+        AuthStrategy::CommandLine => tokio::task::spawn_blocking(auth_from_cmdline)
+            .await
+            .map_err(|e| format!("刷新 AuthInfo 任务异常: {}", e))
+            .and_then(|r| r),
+        AuthStrategy::Lockfile => auth_from_lockfile().await,
+    }
+}
+
+/// 依次尝试各策略，第一个成功的生效；全部失败时返回各策略错误信息的汇总
+async fn run_strategies<F, Fut>(
+    strategies: &[AuthStrategy],
+    mut run: F,
+) -> (
+    Result<(LcuAuthInfo, AuthStrategy), String>,
+    AuthDiscoveryReport,
+)
+where
+    F: FnMut(AuthStrategy) -> Fut,
+    Fut: Future<Output = Result<LcuAuthInfo, String>>,
+{
+    let mut report = AuthDiscoveryReport::default();
+    for &strategy in strategies {
+        match run(strategy).await {
+            Ok(auth) => {
+                report.attempts.push(AuthStrategyAttempt {
+                    strategy,
+                    success: true,
+                    message: None,
+                });
+                report.strategy = Some(strategy);
+                return (Ok((auth, strategy)), report);
+            }
+            Err(e) => {
+                log::warn!("[LCU] 认证发现策略 {:?} 失败: {}", strategy, e);
+                report.attempts.push(AuthStrategyAttempt {
+                    strategy,
+                    success: false,
+                    message: Some(e),
+                });
+            }
+        }
+    }
+
+    let message = report
+        .attempts
+        .iter()
+        .filter_map(|a| a.message.as_deref())
+        .collect::<Vec<_>>()
+        .join("; ");
+    (Err(message), report)
+}
+
+fn store_auth_info(auth: &LcuAuthInfo, strategy: AuthStrategy) {
+    {
+        let mut info = AUTH_INFO.write().unwrap();
+        *info = Some(auth.clone());
+    }
+    {
+        let mut ts = AUTH_TIMESTAMP.write().unwrap();
+        *ts = Some(Instant::now());
+    }
+    log::info!(
+        "[LCU] AuthInfo 刷新成功 ({:?})，端口: {}, token: {}... (已隐藏)",
+        strategy,
+        auth.app_port,
+        &auth.remoting_auth_token[..8.min(auth.remoting_auth_token.len())]
+    );
+}
+
+/// 使用保存的游戏路径（没有则自动检测）查找 lockfile
+async fn auth_from_lockfile() -> Result<LcuAuthInfo, String> {
+    let mut game_paths = Vec::new();
+    match get_saved_game_path().await {
+        Ok(path) if !path.is_empty() => game_paths.push(path),
+        _ => {
+            if let Ok(path) = detect_game_path().await {
+                game_paths.push(path);
+            }
+        }
+    }
+    tokio::task::spawn_blocking(move || lockfile::read_auth_from_lockfile(&game_paths))
+        .await
+        .map_err(|e| format!("读取 lockfile 任务异常: {}", e))?
+}
+
+/// 扫描 LeagueClientUx 进程并解析启动参数（阻塞）
+fn auth_from_cmdline() -> Result<LcuAuthInfo, String> {
     let cmdline = match get_lcu_cmdline() {
         Some(cmd) => cmd,
         None => {
-            log::error!("[LCU] LeagueClientUx.exe 进程未找到，无法刷新 AuthInfo");
-            invalidate_auth_info();
+            log::warn!("[LCU] LeagueClientUx.exe 进程未找到或无法读取启动参数");
             return Err("LeagueClientUx.exe not found".into());
         }
     };
@@ -92,29 +255,14 @@ pub fn refresh_auth_info() -> Result<LcuAuthInfo, String> {
         remoting_auth_token,
         app_port,
     ) {
-        let auth = LcuAuthInfo {
+        Ok(LcuAuthInfo {
             riotclient_auth_token: r_token,
             riotclient_app_port: r_port,
             remoting_auth_token: m_token,
             app_port: a_port,
-        };
-        {
-            let mut info = AUTH_INFO.write().unwrap();
-            *info = Some(auth.clone());
-        }
-        {
-            let mut ts = AUTH_TIMESTAMP.write().unwrap();
-            *ts = Some(Instant::now());
-        }
-        log::info!(
-            "[LCU] AuthInfo 刷新成功，端口: {}, token: {}... (已隐藏)",
-            auth.app_port,
-            &auth.remoting_auth_token[..8.min(auth.remoting_auth_token.len())]
-        );
-        Ok(auth)
+        })
     } else {
-        log::error!("[LCU] 解析 LeagueClientUx.exe 启动参数失败，清空缓存");
-        invalidate_auth_info();
+        log::warn!("[LCU] 解析 LeagueClientUx.exe 启动参数失败");
         Err("Failed to parse LeagueClientUx command line".into())
     }
 }
@@ -145,7 +293,7 @@ pub fn invalidate_auth_info() {
 }
 
 /// 验证 AuthInfo 是否真正可用（通过简单的 API 测试）
-pub async fn validate_auth_connection(auth: &LcuAuthInfo) -> bool {
+pub async fn validate_auth_connection(endpoint: &LcuEndpoint) -> bool {
     let client = reqwest::Client::builder()
         .danger_accept_invalid_certs(true)
         .timeout(Duration::from_secs(5))
@@ -156,13 +304,10 @@ pub async fn validate_auth_connection(auth: &LcuAuthInfo) -> bool {
         Err(_) => return false,
     };
 
-    let url = format!(
-        "https://127.0.0.1:{}/lol-summoner/v1/current-summoner",
-        auth.app_port
-    );
+    let url = endpoint.url("/lol-summoner/v1/current-summoner");
     let response = client
         .get(&url)
-        .basic_auth("riot", Some(&auth.remoting_auth_token))
+        .basic_auth("riot", Some(&endpoint.auth.remoting_auth_token))
         .send()
         .await;
 
@@ -313,3 +458,79 @@ fn get_lcu_cmdline_unix() -> Option<String> {
 
 //     None
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn auth(port: u16) -> LcuAuthInfo {
+        LcuAuthInfo {
+            app_port: port,
+            remoting_auth_token: "token".to_string(),
+            riotclient_app_port: 0,
+            riotclient_auth_token: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_strategy() {
+        let tried = RefCell::new(Vec::new());
+        let (result, report) = run_strategies(
+            &[AuthStrategy::CommandLine, AuthStrategy::Lockfile],
+            |strategy| {
+                tried.borrow_mut().push(strategy);
+                async move {
+                    match strategy {
+                        AuthStrategy::CommandLine => Err("进程未找到".to_string()),
+                        AuthStrategy::Lockfile => Ok(auth(61234)),
+                    }
+                }
+            },
+        )
+        .await;
+
+        let (auth, strategy) = result.unwrap();
+        assert_eq!(auth.app_port, 61234);
+        assert_eq!(strategy, AuthStrategy::Lockfile);
+        assert_eq!(
+            tried.into_inner(),
+            [AuthStrategy::CommandLine, AuthStrategy::Lockfile]
+        );
+        assert_eq!(report.strategy, Some(AuthStrategy::Lockfile));
+        assert_eq!(report.attempts.len(), 2);
+        assert!(!report.attempts[0].success);
+        assert_eq!(report.attempts[0].message.as_deref(), Some("进程未找到"));
+        assert!(report.attempts[1].success);
+    }
+
+    #[tokio::test]
+    async fn stops_at_first_successful_strategy_in_configured_order() {
+        let tried = RefCell::new(Vec::new());
+        let (result, report) = run_strategies(
+            &[AuthStrategy::Lockfile, AuthStrategy::CommandLine],
+            |strategy| {
+                tried.borrow_mut().push(strategy);
+                async move { Ok(auth(strategy as u16 + 1)) }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap().1, AuthStrategy::Lockfile);
+        assert_eq!(tried.into_inner(), [AuthStrategy::Lockfile]);
+        assert_eq!(report.attempts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reports_all_errors_when_every_strategy_fails() {
+        let (result, report) = run_strategies(
+            &[AuthStrategy::CommandLine, AuthStrategy::Lockfile],
+            |strategy| async move { Err(format!("{:?} 失败", strategy)) },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "CommandLine 失败; Lockfile 失败");
+        assert_eq!(report.strategy, None);
+        assert!(report.attempts.iter().all(|a| !a.success));
+    }
+}
@@ -0,0 +1,258 @@
+//! 自动接受对局
+//!
+//! 找到对局后先发送 Pending 事件并等待 delay_ms，期间前端可以调用 cancel_auto_accept；
+//! 之后确认准备确认仍未处理再接受。每次准备确认结束时记录玩家的最终响应，
+//! 上一次拒绝或挂机（没有响应）时按设置跳过下一次自动接受；取消自动接受不算挂机。
+use crate::lcu::matchmaking::service::{accept_match, get_ready_check};
+use crate::lcu::types::{AutoAcceptPhase, AutoAcceptSettings, AutoAcceptStatus};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// 准备确认结束后最多观察多久（客户端的准备确认为 10 秒左右）
+const WATCH_ATTEMPTS: u32 = 15;
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 同一时间只处理一个准备确认
+static RUNNING: AtomicBool = AtomicBool::new(false);
+/// 等待中的自动接受，取消时通知
+static PENDING: Lazy<Mutex<Option<Arc<Notify>>>> = Lazy::new(|| Mutex::new(None));
+/// 上一次准备确认的结果
+static LAST_OUTCOME: Lazy<Mutex<Option<Outcome>>> = Lazy::new(|| Mutex::new(None));
+
+/// 准备确认结束时玩家的最终响应
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Accepted,
+    Declined,
+    /// 没有响应（挂机）
+    NoResponse,
+    /// 取消了自动接受，之后也没有手动处理
+    Cancelled,
+}
+
+/// 取消等待中的自动接受
+pub fn cancel() -> bool {
+    match PENDING.lock().unwrap().take() {
+        Some(notify) => {
+            log::info!("[自动接受] 已取消");
+            // notify_one 会保留许可，即使任务还没开始等待也能收到
+            notify.notify_one();
+            true
+        }
+        None => false,
+    }
+}
+
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        PENDING.lock().unwrap().take();
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// 处理一次准备确认，emit 用于把进度发送给前端
+pub async fn run<F>(client: &Client, settings: AutoAcceptSettings, emit: F)
+where
+    F: Fn(AutoAcceptStatus),
+{
+    if !settings.enabled {
+        return;
+    }
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        log::debug!("[自动接受] 已在处理准备确认，跳过");
+        return;
+    }
+    let _guard = RunningGuard;
+    let status = |phase, reason: Option<String>| AutoAcceptStatus {
+        phase,
+        delay_ms: settings.delay_ms,
+        reason,
+    };
+
+    let last_outcome = *LAST_OUTCOME.lock().unwrap();
+    let skip_reason = match last_outcome {
+        _ if !settings.skip_after_decline => None,
+        Some(Outcome::Declined) => Some("上一次准备确认被拒绝"),
+        Some(Outcome::NoResponse) => Some("上一次准备确认没有响应"),
+        _ => None,
+    };
+    if let Some(reason) = skip_reason {
+        log::info!("[自动接受] 跳过: {}", reason);
+        emit(status(AutoAcceptPhase::Skipped, Some(reason.to_string())));
+        watch_outcome(client, Outcome::NoResponse).await;
+        return;
+    }
+
+    let notify = Arc::new(Notify::new());
+    *PENDING.lock().unwrap() = Some(notify.clone());
+    log::info!("[自动接受] {}ms 后接受对局", settings.delay_ms);
+    emit(status(AutoAcceptPhase::Pending, None));
+
+    tokio::select! {
+        _ = tokio::time::sleep(Duration::from_millis(settings.delay_ms)) => {}
+        _ = notify.notified() => {
+            emit(status(AutoAcceptPhase::Cancelled, None));
+            watch_outcome(client, Outcome::Cancelled).await;
+            return;
+        }
+    }
+    PENDING.lock().unwrap().take();
+
+    // 等待期间玩家可能已经手动处理，或者准备确认已经结束
+    match get_ready_check(client).await {
+        Ok(ready_check)
+            if ready_check.state == "InProgress" && ready_check.player_response == "None" => {}
+        Ok(ready_check) => {
+            log::info!(
+                "[自动接受] 准备确认已处理 ({}/{})，跳过",
+                ready_check.state,
+                ready_check.player_response
+            );
+            emit(status(
+                AutoAcceptPhase::Skipped,
+                Some("准备确认已处理".to_string()),
+            ));
+            watch_outcome(client, Outcome::NoResponse).await;
+            return;
+        }
+        Err(e) => {
+            log::info!("[自动接受] 准备确认已结束: {}", e);
+            emit(status(
+                AutoAcceptPhase::Skipped,
+                Some("准备确认已结束".to_string()),
+            ));
+            return;
+        }
+    }
+
+    match accept_match(client).await {
+        Ok(()) => {
+            log::info!("[自动接受] 已接受对局");
+            emit(status(AutoAcceptPhase::Accepted, None));
+            watch_outcome(client, Outcome::Accepted).await;
+        }
+        Err(e) => {
+            log::warn!("[自动接受] 接受对局失败: {}", e);
+            emit(status(AutoAcceptPhase::Failed, Some(e.to_string())));
+            watch_outcome(client, Outcome::NoResponse).await;
+        }
+    }
+}
+
+/// 观察准备确认直到结束，记录玩家的最终响应，玩家没有手动处理时记录 outcome
+async fn watch_outcome(client: &Client, mut outcome: Outcome) {
+    for _ in 0..WATCH_ATTEMPTS {
+        match get_ready_check(client).await {
+            Ok(ready_check) => {
+                match ready_check.player_response.as_str() {
+                    "Accepted" => outcome = Outcome::Accepted,
+                    "Declined" => outcome = Outcome::Declined,
+                    _ => {}
+                }
+                if ready_check.state != "InProgress" {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+        tokio::time::sleep(WATCH_INTERVAL).await;
+    }
+    log::debug!("[自动接受] 本次准备确认的结果: {:?}", outcome);
+    *LAST_OUTCOME.lock().unwrap() = Some(outcome);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcu;
+    use reqwest::Method;
+    use tokio::sync::mpsc;
+
+    fn settings(delay_ms: u64) -> AutoAcceptSettings {
+        AutoAcceptSettings {
+            enabled: true,
+            delay_ms,
+            skip_after_decline: true,
+        }
+    }
+
+    fn collect() -> (
+        mpsc::UnboundedReceiver<AutoAcceptPhase>,
+        impl Fn(AutoAcceptStatus),
+    ) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (rx, move |s: AutoAcceptStatus| {
+            tx.send(s.phase).unwrap();
+        })
+    }
+
+    #[tokio::test]
+    async fn accepts_after_delay_and_skips_only_after_no_response() {
+        let mock = MockLcu::start().await;
+        let client = mock.client();
+        *LAST_OUTCOME.lock().unwrap() = None;
+
+        // 阶段通过 channel 逐个等待，不依赖固定的 sleep
+        // 第一次：等待期间取消，之后没有手动处理，准备确认结束
+        mock.transition("ReadyCheck");
+        let (mut phases, emit) = collect();
+        let task = {
+            let client = client.clone();
+            tokio::spawn(async move { run(&client, settings(5_000), emit).await })
+        };
+        assert_eq!(phases.recv().await, Some(AutoAcceptPhase::Pending));
+        assert!(cancel());
+        mock.transition("ChampSelect");
+        assert_eq!(phases.recv().await, Some(AutoAcceptPhase::Cancelled));
+        task.await.unwrap();
+        assert!(phases.try_recv().is_err());
+        assert!(mock
+            .requests_to(Method::POST, "/lol-matchmaking/v1/ready-check/accept")
+            .is_empty());
+        assert_eq!(*LAST_OUTCOME.lock().unwrap(), Some(Outcome::Cancelled));
+
+        // 第二次：取消不算挂机，不跳过，delay_ms 之后接受
+        mock.transition("ReadyCheck");
+        let (mut phases, emit) = collect();
+        let started = std::time::Instant::now();
+        let task = {
+            let client = client.clone();
+            tokio::spawn(async move { run(&client, settings(50), emit).await })
+        };
+        assert_eq!(phases.recv().await, Some(AutoAcceptPhase::Pending));
+        assert_eq!(phases.recv().await, Some(AutoAcceptPhase::Accepted));
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        mock.transition("ChampSelect");
+        task.await.unwrap();
+        assert_eq!(
+            mock.requests_to(Method::POST, "/lol-matchmaking/v1/ready-check/accept")
+                .len(),
+            1
+        );
+        assert_eq!(*LAST_OUTCOME.lock().unwrap(), Some(Outcome::Accepted));
+
+        // 第三次：上一次挂机，跳过
+        *LAST_OUTCOME.lock().unwrap() = Some(Outcome::NoResponse);
+        mock.transition("ReadyCheck");
+        let (mut phases, emit) = collect();
+        let task = {
+            let client = client.clone();
+            tokio::spawn(async move { run(&client, settings(0), emit).await })
+        };
+        assert_eq!(phases.recv().await, Some(AutoAcceptPhase::Skipped));
+        mock.transition("ChampSelect");
+        task.await.unwrap();
+        assert!(phases.try_recv().is_err());
+        assert_eq!(
+            mock.requests_to(Method::POST, "/lol-matchmaking/v1/ready-check/accept")
+                .len(),
+            1
+        );
+    }
+}
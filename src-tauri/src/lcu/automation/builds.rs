@@ -0,0 +1,205 @@
+//! 自动化功能使用的出装数据：根据选人会话确定英雄、分路和模式，再从设置的数据源获取
+use crate::lcu::automation::session::local_player;
+use crate::lcu::ddragon;
+use crate::lcu::gameflow::service::get_gameflow_session;
+use crate::lcu::opgg::service as opgg_service;
+use crate::lcu::opgg::types::OpggChampionBuild;
+use crate::lcu::request::lcu_get;
+use crate::lcu::types::{BuildSource, BuildSourceSettings, ChampSelectSession};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// 获取出装数据所需的对局信息
+#[derive(Debug, Clone)]
+pub struct BuildContext {
+    pub champion_id: i32,
+    /// 英文别名，ChampR 按别名查询
+    pub alias: String,
+    pub name: String,
+    /// OP.GG 格式的分路（TOP/JUNGLE/MID/ADC/SUPPORT），没有分路时为 None
+    pub position: Option<String>,
+    /// OP.GG 格式的模式（ranked/aram/arena/urf）
+    pub mode: String,
+    /// LCU 的 gameMode（CLASSIC/ARAM/CHERRY 等），获取失败时为空
+    pub game_mode: String,
+    /// 队列 id（如 420 单双排、450 大乱斗），获取失败时为 None
+    pub queue_id: Option<i32>,
+}
+
+/// 一套符文选择
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuneBuild {
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    pub selected_perk_ids: Vec<i32>,
+    /// 数据源中其余符文选择的符文，按常用程度排列，修复无效符文时使用
+    pub popular_perk_ids: Vec<Vec<i32>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChampionAsset {
+    alias: String,
+    name: String,
+}
+
+/// LCU 分路名转为 OP.GG 格式
+pub fn opgg_position(position: &str) -> Option<&'static str> {
+    match position.to_lowercase().as_str() {
+        "top" => Some("TOP"),
+        "jungle" => Some("JUNGLE"),
+        "middle" | "mid" => Some("MID"),
+        "bottom" | "adc" => Some("ADC"),
+        "utility" | "support" => Some("SUPPORT"),
+        _ => None,
+    }
+}
+
+/// LCU 的 gameMode 转为 OP.GG 的模式
+pub fn opgg_mode(game_mode: &str) -> &'static str {
+    match game_mode {
+        "ARAM" => "aram",
+        "CHERRY" => "arena",
+        "URF" => "urf",
+        _ => "ranked",
+    }
+}
+
+/// 英雄的别名和名称，获取失败时用 id 代替
+pub async fn champion_names(client: &Client, champion_id: i32) -> (String, String) {
+    let path = format!("/lol-game-data/assets/v1/champions/{}.json", champion_id);
+    match lcu_get::<ChampionAsset>(client, &path).await {
+        Ok(asset) => (asset.alias, asset.name),
+        Err(e) => {
+            log::warn!("[自动化] 获取英雄 {} 信息失败: {}", champion_id, e);
+            (champion_id.to_string(), champion_id.to_string())
+        }
+    }
+}
+
+pub async fn build_context(
+    client: &Client,
+    session: &ChampSelectSession,
+    champion_id: i32,
+) -> BuildContext {
+    let (alias, name) = champion_names(client, champion_id).await;
+    let position = local_player(session)
+        .and_then(|p| p.assigned_position.as_deref())
+        .and_then(opgg_position)
+        .map(String::from);
+    let (game_mode, queue_id) = match get_gameflow_session(client).await {
+        Ok(gameflow) => {
+            let queue = &gameflow["gameData"]["queue"];
+            (
+                queue["gameMode"].as_str().unwrap_or_default().to_string(),
+                queue["id"].as_i64().map(|id| id as i32),
+            )
+        }
+        Err(e) => {
+            log::debug!("[自动化] 获取游戏流程会话失败，按排位处理: {}", e);
+            (String::new(), None)
+        }
+    };
+    BuildContext {
+        champion_id,
+        alias,
+        name,
+        position,
+        mode: opgg_mode(&game_mode).to_string(),
+        game_mode,
+        queue_id,
+    }
+}
+
+/// 从 OP.GG 获取当前英雄的出装
+pub async fn fetch_opgg_build(
+    context: &BuildContext,
+    source: &BuildSourceSettings,
+) -> Result<OpggChampionBuild, String> {
+    opgg_service::get_champion_build(
+        &source.region,
+        &context.mode,
+        context.champion_id,
+        context.position.clone(),
+        &source.tier,
+    )
+    .await
+}
+
+/// 从 ChampR 获取当前英雄的出装，优先选择分路一致的一套
+pub async fn fetch_champr_build(
+    client: &Client,
+    context: &BuildContext,
+    source: &BuildSourceSettings,
+) -> Result<Value, String> {
+    let data = ddragon::get_builds_by_alias(client, &source.champr_source, &context.alias).await?;
+    let content = data
+        .get("content")
+        .and_then(|c| c.as_array())
+        .ok_or("无法获取详细数据")?;
+    let position = context.position.as_deref().map(str::to_lowercase);
+    content
+        .iter()
+        .find(|build| {
+            position.is_some()
+                && build
+                    .get("position")
+                    .and_then(|p| p.as_str())
+                    .map(str::to_lowercase)
+                    == position
+        })
+        .or_else(|| content.first())
+        .cloned()
+        .ok_or_else(|| "无法获取详细数据".to_string())
+}
+
+/// 从设置的数据源获取推荐的第一套符文
+pub async fn fetch_rune_build(
+    client: &Client,
+    context: &BuildContext,
+    source: &BuildSourceSettings,
+) -> Result<RuneBuild, String> {
+    match source.source {
+        BuildSource::Opgg => {
+            let build = fetch_opgg_build(context, source).await?;
+            let perk = build.perks.first().ok_or("OP.GG 没有符文数据")?;
+            Ok(RuneBuild {
+                primary_style_id: perk.primary_id,
+                sub_style_id: perk.secondary_id,
+                selected_perk_ids: perk.perks.clone(),
+                popular_perk_ids: build
+                    .perks
+                    .iter()
+                    .skip(1)
+                    .map(|p| p.perks.clone())
+                    .collect(),
+            })
+        }
+        BuildSource::Champr => {
+            let build = fetch_champr_build(client, context, source).await?;
+            let runes = build
+                .get("runes")
+                .and_then(|r| r.as_array())
+                .filter(|r| !r.is_empty())
+                .ok_or("无法获取符文配置数组")?;
+            let perk_ids = |rune: &Value| -> Vec<i32> {
+                rune.get("selectedPerkIds")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_i64().map(|i| i as i32))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let rune = &runes[0];
+            let id = |key: &str| rune.get(key).and_then(|v| v.as_i64()).map(|v| v as i32);
+            Ok(RuneBuild {
+                primary_style_id: id("primaryStyleId").ok_or("无法获取主系符文ID")?,
+                sub_style_id: id("subStyleId").ok_or("无法获取副系符文ID")?,
+                selected_perk_ids: perk_ids(rune),
+                popular_perk_ids: runes.iter().skip(1).map(perk_ids).collect(),
+            })
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::http_client;
+use crate::lcu::automation::{auto_accept, settings, spells};
+use crate::lcu::champ_select::service::get_champ_select_session;
+use crate::lcu::error::LcuError;
+use crate::lcu::perks::service::get_current_rune_page;
+use crate::lcu::types::{AutomationSettings, PinnedRunePage, SummonerSpellResult};
+
+#[tauri::command]
+pub fn get_automation_settings() -> AutomationSettings {
+    settings::get_settings()
+}
+
+#[tauri::command]
+pub fn save_automation_settings(
+    settings: AutomationSettings,
+) -> Result<AutomationSettings, LcuError> {
+    settings::save_settings(settings).map_err(LcuError::from)
+}
+
+/// 取消等待中的自动接受，没有等待中的自动接受时返回 false
+#[tauri::command]
+pub fn cancel_auto_accept() -> bool {
+    auto_accept::cancel()
+}
+
+/// 把客户端当前的符文页固定为该英雄的自动符文，指定 queue_id 时只在该队列使用
+#[tauri::command]
+pub async fn pin_current_rune_page(
+    champion_id: i32,
+    queue_id: Option<i32>,
+) -> Result<PinnedRunePage, LcuError> {
+    let client = http_client::get_lcu_client();
+    let page = get_current_rune_page(client)
+        .await?
+        .ok_or_else(|| LcuError::other("当前没有正在使用的符文页"))?;
+    let pinned = PinnedRunePage {
+        name: page.name,
+        primary_style_id: page.primary_style_id,
+        sub_style_id: page.sub_style_id,
+        selected_perk_ids: page.selected_perk_ids,
+    };
+
+    let mut current = settings::get_settings();
+    let pins = match queue_id {
+        Some(queue_id) => current.auto_runes.queue_pinned.entry(queue_id).or_default(),
+        None => &mut current.auto_runes.pinned,
+    };
+    pins.insert(champion_id, pinned.clone());
+    settings::save_settings(current)?;
+    Ok(pinned)
+}
+
+/// 取消固定，指定 queue_id 时只取消该队列的固定
+#[tauri::command]
+pub fn unpin_rune_page(
+    champion_id: i32,
+    queue_id: Option<i32>,
+) -> Result<AutomationSettings, LcuError> {
+    let mut current = settings::get_settings();
+    let auto_runes = &mut current.auto_runes;
+    match queue_id {
+        Some(queue_id) => {
+            if let Some(pins) = auto_runes.queue_pinned.get_mut(&queue_id) {
+                pins.remove(&champion_id);
+                if pins.is_empty() {
+                    auto_runes.queue_pinned.remove(&queue_id);
+                }
+            }
+        }
+        None => {
+            auto_runes.pinned.remove(&champion_id);
+        }
+    }
+    settings::save_settings(current).map_err(LcuError::from)
+}
+
+/// 按出装数据为该英雄设置召唤师技能（需要在选人阶段）
+#[tauri::command]
+pub async fn apply_summoner_spells(champion_id: i32) -> Result<SummonerSpellResult, LcuError> {
+    let client = http_client::get_lcu_client();
+    let session = get_champ_select_session(client).await?;
+    let result =
+        spells::apply_for_champion(client, &session, champion_id, &settings::get_settings()).await;
+    if result.success {
+        Ok(result)
+    } else {
+        Err(LcuError::other(result.message))
+    }
+}
@@ -0,0 +1,8 @@
+pub mod auto_accept;
+pub mod builds;
+pub mod commands;
+pub mod pick_ban;
+pub mod runes;
+pub mod session;
+pub mod settings;
+pub mod spells;
@@ -0,0 +1,338 @@
+//! 自动选择/禁用英雄
+//!
+//! 根据选人会话找出本地玩家正在进行的 action，从对应分路的优先级列表中选第一个可用英雄：
+//! 跳过双方已禁用、已选择的英雄以及队友正在预选的英雄。先预选，lock_delay_ms 后按最新会话
+//! 重新检查再锁定。每个决策都会记录日志并通过 emit 发送给前端。
+use crate::lcu::automation::session::{latest_session, local_player};
+use crate::lcu::champ_select::lookup::valid_champion_id;
+use crate::lcu::champ_select::service::champion_action;
+use crate::lcu::types::{
+    AutoPickBanDecision, AutoPickBanSettings, ChampSelectAction, ChampSelectSession,
+};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 已处理过的 action，避免会话每次变化都重复预选
+static HANDLED_ACTIONS: Lazy<Mutex<HashSet<i32>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// 一次 action 的候选英雄
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickBanPlan {
+    pub action_id: i32,
+    pub action_type: String,
+    pub position: String,
+    /// 按优先级排列的可用英雄
+    pub candidates: Vec<i32>,
+    /// 被跳过的英雄及原因
+    pub skipped: Vec<(i32, &'static str)>,
+}
+
+impl PickBanPlan {
+    fn reason(&self, champion_id: i32) -> String {
+        let rank = self.candidates.iter().position(|&c| c == champion_id);
+        let mut reason = format!(
+            "按 {} 优先级列表选择 (可用英雄中第 {} 个)",
+            self.position,
+            rank.map(|r| r + 1).unwrap_or_default()
+        );
+        if !self.skipped.is_empty() {
+            let skipped: Vec<String> = self
+                .skipped
+                .iter()
+                .map(|(id, why)| format!("{}({})", id, why))
+                .collect();
+            reason.push_str(&format!("，跳过: {}", skipped.join(", ")));
+        }
+        reason
+    }
+}
+
+/// 选人结束时清空已处理的 action
+pub fn reset() {
+    HANDLED_ACTIONS.lock().unwrap().clear();
+}
+
+/// 本地玩家正在进行的 pick/ban action
+fn local_action(session: &ChampSelectSession) -> Option<&ChampSelectAction> {
+    session.actions.iter().flatten().find(|action| {
+        action.actor_cell_id == Some(session.local_player_cell_id)
+            && action.is_in_progress == Some(true)
+            && !action.completed
+            && matches!(action.action_type.as_str(), "pick" | "ban")
+    })
+}
+
+/// 当前不可选的英雄及原因
+fn unavailable_champions(session: &ChampSelectSession) -> HashMap<i32, &'static str> {
+    let mut unavailable = HashMap::new();
+
+    for id in session
+        .bans
+        .my_team_bans
+        .iter()
+        .chain(&session.bans.their_team_bans)
+        .flatten()
+    {
+        if valid_champion_id(*id) {
+            unavailable.insert(*id as i32, "已被禁用");
+        }
+    }
+    for action in session.actions.iter().flatten() {
+        let Some(id) = action.champion_id.filter(|&id| id > 0) else {
+            continue;
+        };
+        if action.completed {
+            let why = if action.action_type == "ban" {
+                "已被禁用"
+            } else {
+                "已被选择"
+            };
+            unavailable.insert(id, why);
+        }
+    }
+    for player in session.my_team.iter().chain(&session.their_team) {
+        if player.cell_id == session.local_player_cell_id {
+            continue;
+        }
+        if let Some(id) = player.champion_id.filter(|&id| valid_champion_id(id)) {
+            unavailable.entry(id as i32).or_insert("已被选择");
+        }
+    }
+    // 只避开队友的预选，对方的预选不影响选择
+    for player in &session.my_team {
+        if player.cell_id == session.local_player_cell_id {
+            continue;
+        }
+        if let Some(id) = player
+            .champion_pick_intent
+            .filter(|&id| valid_champion_id(id))
+        {
+            unavailable.entry(id as i32).or_insert("队友预选");
+        }
+    }
+    unavailable
+}
+
+/// 根据设置为本地玩家当前的 action 生成候选英雄，没有需要处理的 action 时返回 None
+pub fn plan_action(
+    session: &ChampSelectSession,
+    settings: &AutoPickBanSettings,
+) -> Option<PickBanPlan> {
+    let action = local_action(session)?;
+    let lists = match action.action_type.as_str() {
+        "pick" if settings.pick_enabled => &settings.picks,
+        "ban" if settings.ban_enabled => &settings.bans,
+        _ => return None,
+    };
+
+    let position = local_player(session)
+        .and_then(|p| p.assigned_position.as_deref())
+        .map(str::to_lowercase)
+        .filter(|p| !p.is_empty() && lists.contains_key(p))
+        .unwrap_or_else(|| "default".to_string());
+    let priorities = lists.get(&position).filter(|l| !l.is_empty())?;
+
+    let unavailable = unavailable_champions(session);
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+    for &champion_id in priorities {
+        match unavailable.get(&champion_id) {
+            Some(why) => skipped.push((champion_id, *why)),
+            None if !candidates.contains(&champion_id) => candidates.push(champion_id),
+            None => {}
+        }
+    }
+
+    Some(PickBanPlan {
+        action_id: action.id,
+        action_type: action.action_type.clone(),
+        position,
+        candidates,
+        skipped,
+    })
+}
+
+/// 处理最新会话中本地玩家的 action：预选 -> 等待 -> 重新检查 -> 锁定
+pub async fn run<F>(client: &Client, settings: AutoPickBanSettings, emit: F)
+where
+    F: Fn(AutoPickBanDecision),
+{
+    let Some(plan) = latest_session().and_then(|s| plan_action(&s, &settings)) else {
+        return;
+    };
+    if !HANDLED_ACTIONS.lock().unwrap().insert(plan.action_id) {
+        return;
+    }
+
+    let decide = |champion_id: Option<i32>, locked: bool, success: bool, reason: String| {
+        if success {
+            log::info!(
+                "[自动选人] action {} {} {:?} ({}): {}",
+                plan.action_id,
+                plan.action_type,
+                champion_id,
+                if locked { "锁定" } else { "预选" },
+                reason
+            );
+        } else {
+            log::warn!(
+                "[自动选人] action {} {} {:?} 失败: {}",
+                plan.action_id,
+                plan.action_type,
+                champion_id,
+                reason
+            );
+        }
+        emit(AutoPickBanDecision {
+            action_id: plan.action_id,
+            action_type: plan.action_type.clone(),
+            champion_id,
+            locked,
+            success,
+            reason,
+        });
+    };
+
+    // 预选：未拥有等原因失败时尝试下一个
+    let mut hovered = None;
+    for &champion_id in &plan.candidates {
+        match champion_action(client, plan.action_id as u64, champion_id as u64, false).await {
+            Ok(()) => {
+                decide(Some(champion_id), false, true, plan.reason(champion_id));
+                hovered = Some(champion_id);
+                break;
+            }
+            Err(e) => decide(Some(champion_id), false, false, e.to_string()),
+        }
+    }
+    let Some(hovered) = hovered else {
+        decide(
+            None,
+            false,
+            false,
+            format!("{} 优先级列表中没有可用英雄", plan.position),
+        );
+        return;
+    };
+
+    tokio::time::sleep(Duration::from_millis(settings.lock_delay_ms)).await;
+
+    // 等待期间可能已经轮到别人、有人选走了该英雄或玩家手动换了英雄
+    let Some(session) = latest_session() else {
+        log::info!("[自动选人] 选人已结束，取消锁定");
+        return;
+    };
+    let Some(action) = local_action(&session).filter(|a| a.id == plan.action_id) else {
+        log::info!("[自动选人] action {} 已结束，取消锁定", plan.action_id);
+        return;
+    };
+    if let Some(current) = action.champion_id.filter(|&id| id > 0 && id != hovered) {
+        decide(
+            Some(current),
+            true,
+            false,
+            format!("玩家已手动改为 {}，不再锁定", current),
+        );
+        return;
+    }
+    let Some(latest) = plan_action(&session, &settings) else {
+        return;
+    };
+    let target = if latest.candidates.contains(&hovered) {
+        hovered
+    } else if let Some(&next) = latest.candidates.first() {
+        next
+    } else {
+        decide(
+            None,
+            true,
+            false,
+            format!("{} 优先级列表中没有可用英雄", latest.position),
+        );
+        return;
+    };
+
+    match champion_action(client, plan.action_id as u64, target as u64, true).await {
+        Ok(()) => decide(Some(target), true, true, latest.reason(target)),
+        Err(e) => decide(Some(target), true, false, e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::automation::session::observe_session;
+    use crate::lcu::champ_select::service::get_champ_select_session;
+    use crate::lcu::mock::MockLcu;
+    use reqwest::Method;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn ban_settings(bans: Vec<i32>) -> AutoPickBanSettings {
+        AutoPickBanSettings {
+            ban_enabled: true,
+            bans: HashMap::from([("jungle".to_string(), bans)]),
+            lock_delay_ms: 10,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn plan_skips_banned_and_teammate_hovered_champions() {
+        let mock = MockLcu::start().await;
+        mock.transition("ChampSelect");
+        let mut session = get_champ_select_session(&mock.client()).await.unwrap();
+        session.bans.their_team_bans = vec![Some(64.0)];
+        session.my_team[1].champion_pick_intent = Some(238.0);
+        session.their_team[0].champion_pick_intent = Some(11.0);
+
+        let plan = plan_action(&session, &ban_settings(vec![64, 238, 76, 11])).unwrap();
+
+        assert_eq!(plan.action_id, 1);
+        assert_eq!(plan.action_type, "ban");
+        assert_eq!(plan.position, "jungle");
+        // 76 是自己的预选，11 是对方的预选，都不算被占用
+        assert_eq!(plan.candidates, vec![76, 11]);
+        assert_eq!(plan.skipped, vec![(64, "已被禁用"), (238, "队友预选")]);
+
+        let mut pick_only = ban_settings(vec![76]);
+        pick_only.ban_enabled = false;
+        assert!(plan_action(&session, &pick_only).is_none());
+    }
+
+    #[tokio::test]
+    async fn run_hovers_then_locks_once() {
+        let mock = MockLcu::start().await;
+        mock.transition("ChampSelect");
+        let session = get_champ_select_session(&mock.client()).await.unwrap();
+        observe_session(None);
+        observe_session(Some(&session));
+
+        let decisions: Arc<Mutex<Vec<AutoPickBanDecision>>> = Default::default();
+        for _ in 0..2 {
+            let recorder = decisions.clone();
+            run(&mock.client(), ban_settings(vec![76]), move |d| {
+                recorder.lock().unwrap().push(d)
+            })
+            .await;
+        }
+        observe_session(None);
+
+        let patches = mock.requests_to(Method::PATCH, "/lol-champ-select/v1/session/actions/1");
+        let bodies: Vec<_> = patches.into_iter().map(|r| r.body.unwrap()).collect();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({ "championId": 76, "completed": false }),
+                json!({ "championId": 76, "completed": true }),
+            ]
+        );
+        let decisions = decisions.lock().unwrap();
+        assert_eq!(decisions.len(), 2);
+        assert!(decisions.iter().all(|d| d.success));
+        assert!(decisions[1].locked);
+    }
+}
@@ -0,0 +1,265 @@
+//! 锁定英雄后自动应用符文
+//!
+//! 本地玩家锁定英雄（大乱斗为分配到英雄）后，优先使用该英雄的固定符文页（按队列 id 单独固定的优先），
+//! 否则按英雄、分路和模式从设置的数据源获取推荐符文，再通过 apply_rune_build 应用。
+//! 同一英雄在一次选人中只应用一次，大乱斗重随到新英雄时会重新应用。
+use crate::lcu::automation::builds::{self, RuneBuild};
+use crate::lcu::automation::session::{latest_session, locked_champion};
+use crate::lcu::perks::service::{apply_rune_build, NIDALEE_PAGE_PREFIX};
+use crate::lcu::perks::validator;
+use crate::lcu::types::{
+    AutoRuneResult, AutomationSettings, BuildSource, ChampSelectSession, RuneSelection,
+};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use std::sync::Mutex;
+
+/// 本次选人中已应用过符文的英雄
+static APPLIED_CHAMPION: Lazy<Mutex<Option<i32>>> = Lazy::new(|| Mutex::new(None));
+
+/// 选人结束时清空
+pub fn reset() {
+    APPLIED_CHAMPION.lock().unwrap().take();
+}
+
+/// 检查最新会话，锁定了新英雄时应用符文
+pub async fn run<F>(client: &Client, settings: AutomationSettings, emit: F)
+where
+    F: Fn(AutoRuneResult),
+{
+    if !settings.auto_runes.enabled {
+        return;
+    }
+    let Some(session) = latest_session() else {
+        return;
+    };
+    let Some(champion_id) = locked_champion(&session) else {
+        return;
+    };
+    {
+        let mut applied = APPLIED_CHAMPION.lock().unwrap();
+        if *applied == Some(champion_id) {
+            return;
+        }
+        *applied = Some(champion_id);
+    }
+
+    let (source, result) = apply_for_champion(client, &session, champion_id, &settings).await;
+    let result = match result {
+        Ok(message) => {
+            log::info!("[自动符文] 英雄 {} ({}) {}", champion_id, source, message);
+            AutoRuneResult {
+                champion_id,
+                source,
+                success: true,
+                message,
+            }
+        }
+        Err(message) => {
+            log::warn!(
+                "[自动符文] 英雄 {} ({}) 应用失败: {}",
+                champion_id,
+                source,
+                message
+            );
+            AutoRuneResult {
+                champion_id,
+                source,
+                success: false,
+                message,
+            }
+        }
+    };
+    emit(result);
+}
+
+/// 返回使用的来源和应用结果
+async fn apply_for_champion(
+    client: &Client,
+    session: &ChampSelectSession,
+    champion_id: i32,
+    settings: &AutomationSettings,
+) -> (String, Result<String, String>) {
+    let context = builds::build_context(client, session, champion_id).await;
+    if let Some(pinned) = settings
+        .auto_runes
+        .pinned_page(champion_id, context.queue_id)
+    {
+        // 固定的页面可能本身就是 Nidalee 创建的，去掉前缀避免重复
+        let name = pinned.name.trim_start_matches(NIDALEE_PAGE_PREFIX).trim();
+        let name = if name.is_empty() { &context.name } else { name };
+        let result = apply_rune_build(
+            client,
+            name,
+            pinned.primary_style_id,
+            pinned.sub_style_id,
+            pinned.selected_perk_ids.clone(),
+        )
+        .await
+        .map_err(String::from);
+        return ("pinned".to_string(), result);
+    }
+
+    let source = match settings.build_source.source {
+        BuildSource::Opgg => "opgg",
+        BuildSource::Champr => "champr",
+    }
+    .to_string();
+    let RuneBuild {
+        primary_style_id,
+        sub_style_id,
+        selected_perk_ids,
+        popular_perk_ids,
+    } = match builds::fetch_rune_build(client, &context, &settings.build_source).await {
+        Ok(build) => build,
+        Err(e) => return (source, Err(format!("获取符文数据失败: {}", e))),
+    };
+    let mut selection = RuneSelection {
+        primary_style_id,
+        sub_style_id,
+        selected_perk_ids,
+    };
+    // 数据源的符文可能已过时，用最常用的有效符文替换
+    if settings.auto_runes.repair_invalid {
+        match validator::check_rune_selection(client, &selection, true, &popular_perk_ids).await {
+            Ok(validation) => {
+                if let Some(repaired) = validation.repaired {
+                    log::warn!(
+                        "[自动符文] 英雄 {} 的符文无效，已修复: {}",
+                        champion_id,
+                        validator::describe(&validation.problems)
+                    );
+                    selection = repaired;
+                }
+            }
+            Err(e) => log::warn!("[自动符文] 获取符文数据失败，跳过修复: {}", e),
+        }
+    }
+    let result = apply_rune_build(
+        client,
+        &context.name,
+        selection.primary_style_id,
+        selection.sub_style_id,
+        selection.selected_perk_ids,
+    )
+    .await
+    .map_err(String::from);
+    (source, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::automation::session::observe_session;
+    use crate::lcu::champ_select::service::get_champ_select_session;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+    use crate::lcu::types::PinnedRunePage;
+    use reqwest::Method;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn pinned_page_is_applied_once_after_lock_in() {
+        let mock = MockLcu::start().await;
+        mock.transition("ChampSelect");
+        mock.respond(
+            Method::GET,
+            "/lol-game-data/assets/v1/champions/103.json",
+            MockResponse::json(json!({ "id": 103, "alias": "Ahri", "name": "阿狸" })),
+        );
+        mock.respond(
+            Method::GET,
+            "/lol-gameflow/v1/session",
+            MockResponse::json(
+                json!({ "gameData": { "queue": { "id": 450, "gameMode": "ARAM" } } }),
+            ),
+        );
+        let mut session = get_champ_select_session(&mock.client()).await.unwrap();
+
+        let mut settings = AutomationSettings::default();
+        settings.auto_runes.enabled = true;
+        settings.auto_runes.pinned.insert(
+            103,
+            PinnedRunePage {
+                name: "电刑阿狸".to_string(),
+                primary_style_id: 8100,
+                sub_style_id: 8200,
+                selected_perk_ids: vec![8112, 8139, 8138, 8135, 8226, 8210, 5008, 5008, 5002],
+            },
+        );
+        // 当前是大乱斗（450），使用该队列单独固定的页面；页面名已带前缀时不重复添加
+        settings
+            .auto_runes
+            .queue_pinned
+            .entry(450)
+            .or_default()
+            .insert(
+                103,
+                PinnedRunePage {
+                    name: "Nidalee : 大乱斗阿狸".to_string(),
+                    primary_style_id: 8200,
+                    sub_style_id: 8100,
+                    selected_perk_ids: vec![8214, 8226, 8210, 8237, 8139, 8135, 5008, 5008, 5002],
+                },
+            );
+        let results: Arc<Mutex<Vec<AutoRuneResult>>> = Default::default();
+        let run_once = |settings: AutomationSettings| {
+            let results = results.clone();
+            let client = mock.client();
+            async move {
+                run(&client, settings, move |r| results.lock().unwrap().push(r)).await;
+            }
+        };
+
+        // 还没锁定：不应用
+        observe_session(None);
+        observe_session(Some(&session));
+        run_once(settings.clone()).await;
+        assert!(results.lock().unwrap().is_empty());
+
+        // 锁定阿狸后应用一次，会话再变化也不重复应用
+        let pick = session.actions[1]
+            .iter_mut()
+            .find(|a| a.actor_cell_id == Some(0))
+            .unwrap();
+        pick.champion_id = Some(103);
+        pick.completed = true;
+        observe_session(Some(&session));
+        run_once(settings.clone()).await;
+        run_once(settings.clone()).await;
+        observe_session(None);
+
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source, "pinned");
+        assert!(results[0].success);
+        let creates = mock.requests_to(Method::POST, "/lol-perks/v1/pages");
+        assert_eq!(creates.len(), 1);
+        let body = creates[0].body.as_ref().unwrap();
+        assert_eq!(body["name"], "Nidalee : 大乱斗阿狸");
+        assert_eq!(body["primaryStyleId"], 8200);
+    }
+
+    #[test]
+    fn queue_pin_overrides_champion_pin() {
+        let page = |name: &str| PinnedRunePage {
+            name: name.to_string(),
+            primary_style_id: 8100,
+            sub_style_id: 8200,
+            selected_perk_ids: vec![],
+        };
+        let mut settings = AutomationSettings::default().auto_runes;
+        settings.pinned.insert(103, page("通用"));
+        settings
+            .queue_pinned
+            .entry(450)
+            .or_default()
+            .insert(103, page("大乱斗"));
+
+        let name = |queue_id| settings.pinned_page(103, queue_id).map(|p| p.name.as_str());
+        assert_eq!(name(Some(450)), Some("大乱斗"));
+        assert_eq!(name(Some(420)), Some("通用"));
+        assert_eq!(name(None), Some("通用"));
+        assert_eq!(settings.pinned_page(1, Some(450)), None);
+    }
+}
@@ -0,0 +1,52 @@
+//! 自动化功能共享的选人会话状态
+use crate::lcu::automation::{pick_ban, runes, spells};
+use crate::lcu::types::{ChampSelectPlayer, ChampSelectSession};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+/// 最新的选人会话（由轮询/WebSocket 更新）
+static LATEST_SESSION: Lazy<Mutex<Option<ChampSelectSession>>> = Lazy::new(|| Mutex::new(None));
+
+/// 记录最新会话，选人结束（None）时重置各自动化功能的状态
+pub fn observe_session(session: Option<&ChampSelectSession>) {
+    if session.is_none() {
+        pick_ban::reset();
+        runes::reset();
+        spells::reset();
+    }
+    *LATEST_SESSION.lock().unwrap() = session.cloned();
+}
+
+pub fn latest_session() -> Option<ChampSelectSession> {
+    LATEST_SESSION.lock().unwrap().clone()
+}
+
+pub fn local_player(session: &ChampSelectSession) -> Option<&ChampSelectPlayer> {
+    session
+        .my_team
+        .iter()
+        .find(|p| p.cell_id == session.local_player_cell_id)
+}
+
+/// 本地玩家已锁定的英雄：有 pick action 时取已完成的 pick，
+/// 没有 pick action（如大乱斗直接分配英雄）时取当前英雄
+pub fn locked_champion(session: &ChampSelectSession) -> Option<i32> {
+    let mut picks = session
+        .actions
+        .iter()
+        .flatten()
+        .filter(|a| {
+            a.actor_cell_id == Some(session.local_player_cell_id) && a.action_type == "pick"
+        })
+        .peekable();
+    if picks.peek().is_some() {
+        return picks
+            .filter(|a| a.completed)
+            .filter_map(|a| a.champion_id)
+            .find(|&id| id > 0);
+    }
+    local_player(session)
+        .and_then(|p| p.champion_id)
+        .filter(|&id| id > 0.0 && id < 100_000.0)
+        .map(|id| id as i32)
+}
@@ -0,0 +1,47 @@
+//! 后台自动化设置（持久化到 automation_settings.json），窗口隐藏时同样生效
+use crate::lcu::types::AutomationSettings;
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+static AUTOMATION_SETTINGS: Lazy<RwLock<AutomationSettings>> =
+    Lazy::new(|| RwLock::new(load_settings()));
+
+fn settings_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nidalee").join("automation_settings.json"))
+}
+
+fn load_settings() -> AutomationSettings {
+    let Some(file) = settings_file() else {
+        return AutomationSettings::default();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return AutomationSettings::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!(
+            "[自动化] 解析 automation_settings.json 失败，使用默认设置: {}",
+            e
+        );
+        AutomationSettings::default()
+    })
+}
+
+pub fn get_settings() -> AutomationSettings {
+    AUTOMATION_SETTINGS.read().unwrap().clone()
+}
+
+pub fn save_settings(settings: AutomationSettings) -> Result<AutomationSettings, String> {
+    let file = settings_file().ok_or("无法获取配置目录")?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&settings).map_err(|e| format!("序列化设置失败: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("保存设置失败: {}", e))?;
+
+    *AUTOMATION_SETTINGS.write().unwrap() = settings.clone();
+    log::info!("[自动化] 设置已更新: {:?}", settings);
+    Ok(settings)
+}
@@ -0,0 +1,250 @@
+//! 锁定英雄后自动设置召唤师技能
+//!
+//! 从 OP.GG 出装数据中按顺序取第一组当前对局可用的召唤师技能：跳过不属于当前模式的技能
+//! （如大乱斗专属的雪球），已分配分路且不是打野时跳过惩戒。闪现按设置放在 D 或 F，
+//! 默认沿用玩家当前选择中的习惯。召唤师技能只有 OP.GG 提供，不受 build_source.source 影响。
+use crate::lcu::automation::builds::{self, BuildContext};
+use crate::lcu::automation::session::{latest_session, local_player, locked_champion};
+use crate::lcu::champ_select::service::set_summoner_spells;
+use crate::lcu::opgg::types::OpggSummonerSpell;
+use crate::lcu::request::lcu_get;
+use crate::lcu::types::{AutomationSettings, ChampSelectSession, FlashSlot, SummonerSpellResult};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const FLASH: i32 = 4;
+const SMITE: i32 = 11;
+
+/// 本次选人中已设置过召唤师技能的英雄
+static APPLIED_CHAMPION: Lazy<Mutex<Option<i32>>> = Lazy::new(|| Mutex::new(None));
+
+/// 选人结束时清空
+pub fn reset() {
+    APPLIED_CHAMPION.lock().unwrap().take();
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpellAsset {
+    id: i32,
+    #[serde(default)]
+    game_modes: Vec<String>,
+}
+
+/// 当前对局中召唤师技能是否可用
+#[derive(Debug, Clone, Default)]
+pub struct SpellRules {
+    /// LCU 的 gameMode，为空时不按模式过滤
+    pub game_mode: String,
+    /// OP.GG 格式的分路
+    pub position: Option<String>,
+    /// 技能 id -> 可用的 gameMode（来自 summoner-spells.json）
+    pub game_modes: HashMap<i32, Vec<String>>,
+}
+
+impl SpellRules {
+    pub fn allows(&self, spell_id: i32) -> bool {
+        if spell_id == SMITE && self.position.as_deref().is_some_and(|p| p != "JUNGLE") {
+            return false;
+        }
+        if self.game_mode.is_empty() {
+            return true;
+        }
+        match self.game_modes.get(&spell_id) {
+            Some(modes) => modes.contains(&self.game_mode),
+            // 获取不到客户端数据时只处理常见的模式专属技能
+            None => match spell_id {
+                13 | 32 => self.game_mode == "ARAM",
+                11 | 12 => self.game_mode != "ARAM",
+                _ => true,
+            },
+        }
+    }
+}
+
+async fn spell_rules(client: &Client, context: &BuildContext) -> SpellRules {
+    let path = "/lol-game-data/assets/v1/summoner-spells.json";
+    let game_modes = match lcu_get::<Vec<SpellAsset>>(client, path).await {
+        Ok(assets) => assets.into_iter().map(|a| (a.id, a.game_modes)).collect(),
+        Err(e) => {
+            log::warn!("[召唤师技能] 获取召唤师技能数据失败: {}", e);
+            HashMap::new()
+        }
+    };
+    SpellRules {
+        game_mode: context.game_mode.clone(),
+        position: context.position.clone(),
+        game_modes,
+    }
+}
+
+/// 选出第一组可用的召唤师技能并确定 D/F 栏位，返回 (spell1, spell2)
+pub fn choose_spells(
+    spells: &[OpggSummonerSpell],
+    rules: &SpellRules,
+    current: (Option<i32>, Option<i32>),
+    flash_slot: FlashSlot,
+) -> Option<(i32, i32)> {
+    let (first, second) = spells
+        .iter()
+        .filter_map(|spell| match spell.ids[..] {
+            [first, second] if first != second => Some((first, second)),
+            _ => None,
+        })
+        .find(|&(first, second)| rules.allows(first) && rules.allows(second))?;
+
+    let flash_on_d = match flash_slot {
+        FlashSlot::D => Some(true),
+        FlashSlot::F => Some(false),
+        FlashSlot::Keep if current.0 == Some(FLASH) => Some(true),
+        FlashSlot::Keep if current.1 == Some(FLASH) => Some(false),
+        FlashSlot::Keep => None,
+    };
+    let swap = if first == FLASH || second == FLASH {
+        flash_on_d.is_some_and(|on_d| (first == FLASH) != on_d)
+    } else {
+        // 没有闪现时尽量保留已选技能所在的栏位
+        current.0 == Some(second) || current.1 == Some(first)
+    };
+    Some(if swap {
+        (second, first)
+    } else {
+        (first, second)
+    })
+}
+
+/// 为英雄设置 OP.GG 推荐的召唤师技能
+pub async fn apply_for_champion(
+    client: &Client,
+    session: &ChampSelectSession,
+    champion_id: i32,
+    settings: &AutomationSettings,
+) -> SummonerSpellResult {
+    let result = |spells: Option<(i32, i32)>, success: bool, message: String| {
+        if success {
+            log::info!("[召唤师技能] 英雄 {} {}", champion_id, message);
+        } else {
+            log::warn!("[召唤师技能] 英雄 {} 设置失败: {}", champion_id, message);
+        }
+        SummonerSpellResult {
+            champion_id,
+            spell1_id: spells.map(|s| s.0),
+            spell2_id: spells.map(|s| s.1),
+            success,
+            message,
+        }
+    };
+
+    let context = builds::build_context(client, session, champion_id).await;
+    let build = match builds::fetch_opgg_build(&context, &settings.build_source).await {
+        Ok(build) => build,
+        Err(e) => return result(None, false, format!("获取召唤师技能数据失败: {}", e)),
+    };
+    let rules = spell_rules(client, &context).await;
+    let current = local_player(session)
+        .map(|p| {
+            (
+                p.spell1_id.map(|id| id as i32),
+                p.spell2_id.map(|id| id as i32),
+            )
+        })
+        .unwrap_or_default();
+    let Some(spells) = choose_spells(
+        &build.summoner_spells,
+        &rules,
+        current,
+        settings.auto_spells.flash_slot,
+    ) else {
+        return result(None, false, "没有当前模式可用的召唤师技能".to_string());
+    };
+
+    match set_summoner_spells(client, spells.0, spells.1).await {
+        Ok(()) => result(
+            Some(spells),
+            true,
+            format!("已设置召唤师技能 D={} F={}", spells.0, spells.1),
+        ),
+        Err(e) => result(Some(spells), false, e.to_string()),
+    }
+}
+
+/// 检查最新会话，锁定了新英雄时设置召唤师技能
+pub async fn run<F>(client: &Client, settings: AutomationSettings, emit: F)
+where
+    F: Fn(SummonerSpellResult),
+{
+    if !settings.auto_spells.enabled {
+        return;
+    }
+    let Some(session) = latest_session() else {
+        return;
+    };
+    let Some(champion_id) = locked_champion(&session) else {
+        return;
+    };
+    {
+        let mut applied = APPLIED_CHAMPION.lock().unwrap();
+        if *applied == Some(champion_id) {
+            return;
+        }
+        *applied = Some(champion_id);
+    }
+    emit(apply_for_champion(client, &session, champion_id, &settings).await);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(ids: [i32; 2]) -> OpggSummonerSpell {
+        OpggSummonerSpell {
+            spell_id: ids[0],
+            ids: ids.to_vec(),
+            win: 0,
+            play: 0,
+            pick_rate: 0.0,
+        }
+    }
+
+    #[test]
+    fn choose_spells_skips_disallowed_and_keeps_flash_slot() {
+        let spells = [pair([4, 11]), pair([4, 32]), pair([14, 4])];
+        let mid = SpellRules {
+            game_mode: "CLASSIC".to_string(),
+            position: Some("MID".to_string()),
+            game_modes: HashMap::from([(32, vec!["ARAM".to_string()])]),
+        };
+
+        // 中路跳过惩戒，非大乱斗跳过雪球；玩家习惯闪现放在 F
+        assert_eq!(
+            choose_spells(&spells, &mid, (Some(14), Some(4)), FlashSlot::Keep),
+            Some((14, 4))
+        );
+        assert_eq!(
+            choose_spells(&spells, &mid, (Some(14), Some(4)), FlashSlot::D),
+            Some((4, 14))
+        );
+
+        let jungle = SpellRules {
+            position: Some("JUNGLE".to_string()),
+            ..mid.clone()
+        };
+        assert_eq!(
+            choose_spells(&spells, &jungle, (Some(11), Some(4)), FlashSlot::Keep),
+            Some((11, 4))
+        );
+
+        let aram = SpellRules {
+            game_mode: "ARAM".to_string(),
+            position: None,
+            ..mid
+        };
+        assert_eq!(
+            choose_spells(&spells, &aram, (None, None), FlashSlot::Keep),
+            Some((4, 32))
+        );
+    }
+}
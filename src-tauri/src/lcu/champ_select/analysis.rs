@@ -0,0 +1,391 @@
+//! 选人阶段的队友分析
+//!
+//! 根据队友最近的对局（本地战绩数据库，由选人查询写入）给出标签：连败、首次使用当前英雄、
+//! 最近重开、多次提前投降、分配位置不是常玩位置以及与同队玩家多次一起游戏。
+//! 每个标签都带有依据的对局，前端可以展示给玩家自行判断。
+use crate::lcu::champ_select::lookup;
+use crate::lcu::champ_select::service::get_champ_select_session;
+use crate::lcu::error::LcuError;
+use crate::lcu::matches::analytics::position_of;
+use crate::lcu::matches::service::player_participant;
+use crate::lcu::types::{ChampSelectPlayerAnalysis, PlayerTag, PlayerTagEvidence, PlayerTagKind};
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 连败达到该场次时标记
+const LOSS_STREAK_MIN: usize = 3;
+/// 短于该时长（秒）的对局视为重开
+const REMAKE_SECONDS: i64 = 300;
+/// 短于该时长（秒）的投降视为提前投降
+const EARLY_SURRENDER_SECONDS: i64 = 20 * 60;
+const EARLY_SURRENDER_MIN: usize = 2;
+/// 有位置的对局达到该场次才判断位置
+const ROLE_MIN_GAMES: usize = 5;
+/// 分配位置在有位置的对局中占比低于该值时标记
+const ROLE_MAX_SHARE: f32 = 0.2;
+/// 同队次数达到该场次时标记
+const DUO_MIN_GAMES: usize = 2;
+
+/// 一名队友及其最近的对局（本地战绩数据库格式，从新到旧）
+#[derive(Debug, Clone, Default)]
+pub struct PlayerGames {
+    pub cell_id: i32,
+    pub puuid: String,
+    pub display_name: Option<String>,
+    pub champion_id: Option<i32>,
+    pub assigned_position: Option<String>,
+    pub games: Vec<Value>,
+}
+
+/// 玩家在一场对局中的数据
+#[derive(Debug, Clone)]
+struct GameRecord {
+    game_id: u64,
+    game_creation: i64,
+    champion_id: i32,
+    team_id: i64,
+    win: bool,
+    duration: i64,
+    position: String,
+    remake: bool,
+    surrender: bool,
+}
+
+impl GameRecord {
+    fn parse(game: &Value, puuid: &str) -> Option<Self> {
+        let participant = player_participant(game, puuid)?;
+        let stats = &participant["stats"];
+        let flag = |key: &str| stats[key].as_bool().unwrap_or(false);
+        let duration = game["gameDuration"].as_i64().unwrap_or(0);
+        Some(Self {
+            game_id: game["gameId"].as_u64()?,
+            game_creation: game["gameCreation"].as_i64().unwrap_or(0),
+            champion_id: participant["championId"].as_i64().unwrap_or(0) as i32,
+            team_id: participant["teamId"].as_i64().unwrap_or(0),
+            win: flag("win"),
+            duration,
+            position: position_of(participant),
+            remake: flag("gameEndedInEarlySurrender") || duration < REMAKE_SECONDS,
+            surrender: flag("gameEndedInSurrender"),
+        })
+    }
+
+    fn evidence(&self, detail: impl Into<String>) -> PlayerTagEvidence {
+        PlayerTagEvidence {
+            game_id: self.game_id,
+            game_creation: self.game_creation,
+            champion_id: self.champion_id,
+            win: self.win,
+            game_duration: self.duration,
+            detail: detail.into(),
+        }
+    }
+}
+
+fn position_name(position: &str) -> &str {
+    match position {
+        "TOP" => "上单",
+        "JUNGLE" => "打野",
+        "MIDDLE" => "中单",
+        "BOTTOM" => "下路",
+        "UTILITY" => "辅助",
+        other => other,
+    }
+}
+
+fn loss_streak(games: &[GameRecord]) -> Option<PlayerTag> {
+    let streak: Vec<&GameRecord> = games
+        .iter()
+        .filter(|g| !g.remake)
+        .take_while(|g| !g.win)
+        .collect();
+    (streak.len() >= LOSS_STREAK_MIN).then(|| PlayerTag {
+        kind: PlayerTagKind::LossStreak,
+        message: format!("最近 {} 连败", streak.len()),
+        evidence: streak.iter().map(|g| g.evidence("失败")).collect(),
+    })
+}
+
+fn first_time_champion(games: &[GameRecord], champion_id: Option<i32>) -> Option<PlayerTag> {
+    let champion_id = champion_id?;
+    (!games.is_empty() && games.iter().all(|g| g.champion_id != champion_id)).then(|| PlayerTag {
+        kind: PlayerTagKind::FirstTimeChampion,
+        message: format!("最近 {} 场没有使用过当前英雄", games.len()),
+        evidence: Vec::new(),
+    })
+}
+
+fn recent_remakes(games: &[GameRecord]) -> Option<PlayerTag> {
+    let remakes: Vec<PlayerTagEvidence> = games
+        .iter()
+        .filter(|g| g.remake)
+        .map(|g| g.evidence(format!("{} 秒结束", g.duration)))
+        .collect();
+    (!remakes.is_empty()).then(|| PlayerTag {
+        kind: PlayerTagKind::RecentRemake,
+        message: format!("最近 {} 场中有 {} 场重开", games.len(), remakes.len()),
+        evidence: remakes,
+    })
+}
+
+fn early_surrenders(games: &[GameRecord]) -> Option<PlayerTag> {
+    let surrenders: Vec<PlayerTagEvidence> = games
+        .iter()
+        .filter(|g| g.surrender && !g.remake && !g.win && g.duration < EARLY_SURRENDER_SECONDS)
+        .map(|g| g.evidence(format!("{} 分钟投降", g.duration / 60)))
+        .collect();
+    (surrenders.len() >= EARLY_SURRENDER_MIN).then(|| PlayerTag {
+        kind: PlayerTagKind::EarlySurrender,
+        message: format!(
+            "最近 {} 场中有 {} 场提前投降",
+            games.len(),
+            surrenders.len()
+        ),
+        evidence: surrenders,
+    })
+}
+
+fn role_mismatch(games: &[GameRecord], assigned: Option<&str>) -> Option<PlayerTag> {
+    let assigned = assigned.filter(|p| !p.is_empty())?.to_uppercase();
+    let known: Vec<&GameRecord> = games.iter().filter(|g| g.position != "NONE").collect();
+    if known.len() < ROLE_MIN_GAMES {
+        return None;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for game in &known {
+        *counts.entry(game.position.as_str()).or_default() += 1;
+    }
+    let played = counts.get(assigned.as_str()).copied().unwrap_or(0);
+    if played as f32 / known.len() as f32 >= ROLE_MAX_SHARE {
+        return None;
+    }
+    let (main, main_count) = counts
+        .iter()
+        .max_by_key(|(position, count)| (**count, std::cmp::Reverse(**position)))?;
+    Some(PlayerTag {
+        kind: PlayerTagKind::RoleMismatch,
+        message: format!(
+            "分配到{}，最近 {} 场中只打过 {} 场，常玩{}（{} 场）",
+            position_name(&assigned),
+            known.len(),
+            played,
+            position_name(main),
+            main_count
+        ),
+        evidence: known
+            .iter()
+            .map(|g| g.evidence(position_name(&g.position)))
+            .collect(),
+    })
+}
+
+/// 两名玩家同队的对局
+fn shared_games(a: &[GameRecord], b: &[GameRecord]) -> Vec<PlayerTagEvidence> {
+    a.iter()
+        .filter(|game| {
+            b.iter()
+                .any(|other| other.game_id == game.game_id && other.team_id == game.team_id)
+        })
+        .map(|game| game.evidence("同队"))
+        .collect()
+}
+
+/// 为每名队友生成标签
+pub fn tag_players(players: &[PlayerGames]) -> Vec<ChampSelectPlayerAnalysis> {
+    let records: Vec<Vec<GameRecord>> = players
+        .iter()
+        .map(|p| {
+            p.games
+                .iter()
+                .filter_map(|g| GameRecord::parse(g, &p.puuid))
+                .collect()
+        })
+        .collect();
+
+    let mut result: Vec<ChampSelectPlayerAnalysis> = players
+        .iter()
+        .zip(&records)
+        .map(|(player, games)| ChampSelectPlayerAnalysis {
+            cell_id: player.cell_id,
+            puuid: player.puuid.clone(),
+            display_name: player.display_name.clone(),
+            champion_id: player.champion_id,
+            assigned_position: player.assigned_position.clone(),
+            games_analyzed: games.len() as i32,
+            tags: [
+                loss_streak(games),
+                first_time_champion(games, player.champion_id),
+                recent_remakes(games),
+                early_surrenders(games),
+                role_mismatch(games, player.assigned_position.as_deref()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let shared = shared_games(&records[i], &records[j]);
+            if shared.len() < DUO_MIN_GAMES {
+                continue;
+            }
+            for (me, other) in [(i, j), (j, i)] {
+                let name = players[other].display_name.as_deref().unwrap_or("队友");
+                result[me].tags.push(PlayerTag {
+                    kind: PlayerTagKind::RepeatedDuo,
+                    message: format!("最近与 {} 同队 {} 场", name, shared.len()),
+                    evidence: shared.clone(),
+                });
+            }
+        }
+    }
+    result
+}
+
+/// 分析当前选人中的队友（机器人和隐藏身份的玩家除外）
+pub async fn analyze_my_team(client: &Client) -> Result<Vec<ChampSelectPlayerAnalysis>, LcuError> {
+    let session = get_champ_select_session(client).await?;
+
+    let puuids = lookup::puuids_by_cell(client, &session.my_team).await;
+    let mut players: Vec<PlayerGames> = session
+        .my_team
+        .iter()
+        .filter_map(|p| {
+            Some(PlayerGames {
+                cell_id: p.cell_id,
+                puuid: puuids.get(&p.cell_id)?.clone(),
+                display_name: p.display_name.clone(),
+                champion_id: lookup::player_champion_id(p),
+                assigned_position: p.assigned_position.clone(),
+                games: Vec::new(),
+            })
+        })
+        .collect();
+
+    // 查询最近战绩（已缓存的不会重复请求）
+    let puuids: Vec<String> = players.iter().map(|p| p.puuid.clone()).collect();
+    let mut games = lookup::recent_games_by_puuids(client, &puuids).await;
+    for player in &mut players {
+        player.games = games.remove(&player.puuid).unwrap_or_default();
+    }
+    Ok(tag_players(&players))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct G {
+        id: u64,
+        champion: i32,
+        team: i64,
+        win: bool,
+        minutes: i64,
+        lane: &'static str,
+        surrender: bool,
+    }
+
+    fn games(puuid: &str, list: &[G]) -> Vec<Value> {
+        list.iter()
+            .map(|g| {
+                json!({
+                    "gameId": g.id,
+                    "gameCreation": 1_730_000_000_000u64 - g.id * 1000,
+                    "gameDuration": g.minutes * 60,
+                    "participantIdentities": [{ "participantId": 1, "player": { "puuid": puuid } }],
+                    "participants": [{
+                        "participantId": 1,
+                        "championId": g.champion,
+                        "teamId": g.team,
+                        "timeline": { "lane": g.lane, "role": "SOLO" },
+                        "stats": { "win": g.win, "gameEndedInSurrender": g.surrender }
+                    }]
+                })
+            })
+            .collect()
+    }
+
+    fn g(id: u64, win: bool, minutes: i64, surrender: bool) -> G {
+        G {
+            id,
+            champion: 64,
+            team: 100,
+            win,
+            minutes,
+            lane: "JUNGLE",
+            surrender,
+        }
+    }
+
+    #[test]
+    fn tags_come_with_evidence() {
+        // 新到旧：重开、两次提前投降后接着一场失败，之前一直打野
+        let tilted = PlayerGames {
+            cell_id: 0,
+            puuid: "tilted".to_string(),
+            display_name: Some("A#1".to_string()),
+            champion_id: Some(76),
+            assigned_position: Some("utility".to_string()),
+            games: games(
+                "tilted",
+                &[
+                    g(1, false, 3, true),
+                    g(2, false, 15, true),
+                    g(3, false, 18, true),
+                    g(4, false, 30, false),
+                    g(5, true, 30, false),
+                    g(6, true, 30, false),
+                ],
+            ),
+        };
+        let duo = PlayerGames {
+            cell_id: 1,
+            puuid: "duo".to_string(),
+            display_name: Some("B#1".to_string()),
+            champion_id: Some(64),
+            assigned_position: Some("jungle".to_string()),
+            // 与 tilted 在 2、5 同队，在 6 不同队
+            games: games(
+                "duo",
+                &[
+                    g(2, false, 15, true),
+                    g(5, true, 30, false),
+                    G {
+                        team: 200,
+                        ..g(6, false, 30, false)
+                    },
+                ],
+            ),
+        };
+
+        let result = tag_players(&[tilted, duo]);
+
+        let kinds: Vec<_> = result[0].tags.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                PlayerTagKind::LossStreak,
+                PlayerTagKind::FirstTimeChampion,
+                PlayerTagKind::RecentRemake,
+                PlayerTagKind::EarlySurrender,
+                PlayerTagKind::RoleMismatch,
+                PlayerTagKind::RepeatedDuo,
+            ]
+        );
+        let ids = |tag: &PlayerTag| tag.evidence.iter().map(|e| e.game_id).collect::<Vec<_>>();
+        // 重开不算在连败里
+        assert_eq!(ids(&result[0].tags[0]), [2, 3, 4]);
+        assert_eq!(ids(&result[0].tags[2]), [1]);
+        assert_eq!(ids(&result[0].tags[3]), [2, 3]);
+        assert_eq!(result[0].tags[5].message, "最近与 B#1 同队 2 场");
+        assert_eq!(ids(&result[0].tags[5]), [2, 5]);
+
+        assert_eq!(result[1].games_analyzed, 3);
+        let kinds: Vec<_> = result[1].tags.iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, [PlayerTagKind::RepeatedDuo]);
+    }
+}
@@ -1,21 +1,30 @@
+use crate::lcu::error::LcuError;
 use crate::{http_client, lcu};
 use std::collections::HashMap;
 
 #[tauri::command]
 pub async fn get_champselect_team_players_info(
-) -> Result<HashMap<String, lcu::types::MatchStatistics>, String> {
+) -> Result<HashMap<String, lcu::types::MatchStatistics>, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::champ_select::service::get_champselect_team_players_info(client).await
 }
 
+/// 分析选人中的队友，返回每名队友的标签及依据
 #[tauri::command]
-pub async fn get_champ_select_session() -> Result<serde_json::Value, String> {
+pub async fn analyze_champ_select_team(
+) -> Result<Vec<lcu::types::ChampSelectPlayerAnalysis>, LcuError> {
+    let client = http_client::get_lcu_client();
+    lcu::champ_select::analysis::analyze_my_team(client).await
+}
+
+#[tauri::command]
+pub async fn get_champ_select_session() -> Result<serde_json::Value, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::champ_select::service::get_champ_select_session_raw(client).await
 }
 
 #[tauri::command]
-pub async fn get_champ_select_session_typed() -> Result<lcu::types::ChampSelectSession, String> {
+pub async fn get_champ_select_session_typed() -> Result<lcu::types::ChampSelectSession, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::champ_select::service::get_champ_select_session(client).await
 }
@@ -25,7 +34,7 @@ pub async fn pick_champion(
     action_id: u64,
     champion_id: u64,
     completed: bool,
-) -> Result<String, String> {
+) -> Result<String, LcuError> {
     let client = http_client::get_lcu_client();
     match lcu::champ_select::service::pick_champion(client, action_id, champion_id, completed).await
     {
@@ -40,15 +49,14 @@ pub async fn pick_champion(
         }
         Err(e) => {
             let action_type = if completed { "锁定" } else { "预选" };
-            let error_msg = format!("{}英雄失败: {}", action_type, e);
-            log::error!("[Commands] {}", error_msg);
-            Err(error_msg)
+            log::error!("[Commands] {}英雄失败: {}", action_type, e);
+            Err(e)
         }
     }
 }
 
 #[tauri::command]
-pub async fn ban_champion(action_id: u64, champion_id: u64) -> Result<String, String> {
+pub async fn ban_champion(action_id: u64, champion_id: u64) -> Result<String, LcuError> {
     let client = http_client::get_lcu_client();
     match lcu::champ_select::service::ban_champion(client, action_id, champion_id).await {
         Ok(()) => {
@@ -60,9 +68,8 @@ pub async fn ban_champion(action_id: u64, champion_id: u64) -> Result<String, St
             Ok(message)
         }
         Err(e) => {
-            let error_msg = format!("禁用英雄失败: {}", e);
-            log::error!("[Commands] {}", error_msg);
-            Err(error_msg)
+            log::error!("[Commands] 禁用英雄失败: {}", e);
+            Err(e)
         }
     }
 }
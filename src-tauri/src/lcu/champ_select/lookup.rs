@@ -0,0 +1,252 @@
+//! 选人阶段的召唤师信息和战绩查询
+//!
+//! 选人会话每次变化（以及每次轮询）都需要双方玩家的信息，这里并发查询并按 summonerId / 名称 / puuid
+//! 缓存结果：同一次选人中重复的查询直接使用缓存，只有新出现的玩家才会请求 LCU。
+//! 查询失败的玩家不缓存，下次会重新查询。
+use crate::lcu::error::LcuError;
+use crate::lcu::matches::service::get_recent_matches_by_puuid;
+use crate::lcu::matches::store;
+use crate::lcu::summoner::service::{
+    fill_summoner_extra_info, get_summoner_by_id, get_summoner_by_name,
+};
+use crate::lcu::types::{ChampSelectPlayer, MatchStatistics, SummonerInfo};
+use futures_util::{stream, StreamExt};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 同时进行的 LCU 请求数量
+const LOOKUP_CONCURRENCY: usize = 4;
+/// 缓存有效期，覆盖一次完整的选人
+const CACHE_TTL: Duration = Duration::from_secs(300);
+/// 每个玩家查询的最近对局数量
+pub const RECENT_MATCH_COUNT: usize = 20;
+
+struct TtlCache<K, V> {
+    entries: HashMap<K, (V, Instant)>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> TtlCache<K, V> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries
+            .get(key)
+            .filter(|(_, at)| at.elapsed() < CACHE_TTL)
+            .map(|(value, _)| value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.entries.retain(|_, (_, at)| at.elapsed() < CACHE_TTL);
+        self.entries.insert(key, (value, Instant::now()));
+    }
+}
+
+static SUMMONERS: Lazy<Mutex<TtlCache<u64, SummonerInfo>>> =
+    Lazy::new(|| Mutex::new(TtlCache::new()));
+static RANKED_SUMMONERS: Lazy<Mutex<TtlCache<String, SummonerInfo>>> =
+    Lazy::new(|| Mutex::new(TtlCache::new()));
+static RECENT_MATCHES: Lazy<Mutex<TtlCache<String, MatchStatistics>>> =
+    Lazy::new(|| Mutex::new(TtlCache::new()));
+
+/// 清空缓存
+pub fn clear() {
+    SUMMONERS.lock().unwrap().entries.clear();
+    RANKED_SUMMONERS.lock().unwrap().entries.clear();
+    RECENT_MATCHES.lock().unwrap().entries.clear();
+}
+
+/// 以最多 LOOKUP_CONCURRENCY 个并发查询每个 key，结果顺序与 keys 无关
+pub async fn fetch_concurrently<K, V, F, Fut, E>(keys: Vec<K>, fetch: F) -> Vec<(K, Result<V, E>)>
+where
+    K: Clone,
+    F: Fn(K) -> Fut,
+    Fut: std::future::Future<Output = Result<V, E>>,
+{
+    stream::iter(keys)
+        .map(|key| {
+            let fut = fetch(key.clone());
+            async move { (key, fut.await) }
+        })
+        .buffer_unordered(LOOKUP_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// 先取缓存，未命中的 key 以最多 LOOKUP_CONCURRENCY 个并发查询，成功的结果写入缓存
+async fn cached_lookup<K, V, F, Fut, E>(
+    cache: &Mutex<TtlCache<K, V>>,
+    keys: &[K],
+    fetch: F,
+) -> HashMap<K, V>
+where
+    K: Eq + Hash + Clone + std::fmt::Display,
+    V: Clone,
+    F: Fn(K) -> Fut,
+    Fut: std::future::Future<Output = Result<V, E>>,
+    E: std::fmt::Display,
+{
+    let mut found = HashMap::new();
+    let mut missing = Vec::new();
+    {
+        let cache = cache.lock().unwrap();
+        for key in keys {
+            match cache.get(key) {
+                Some(value) => {
+                    found.insert(key.clone(), value);
+                }
+                None if !missing.contains(key) => missing.push(key.clone()),
+                None => {}
+            }
+        }
+    }
+    if missing.is_empty() {
+        return found;
+    }
+
+    let results = fetch_concurrently(missing, fetch).await;
+    let mut cache = cache.lock().unwrap();
+    for (key, result) in results {
+        match result {
+            Ok(value) => {
+                cache.insert(key.clone(), value.clone());
+                found.insert(key, value);
+            }
+            Err(e) => log::warn!("[选人查询] 查询 {} 失败: {}", key, e),
+        }
+    }
+    found
+}
+
+/// 批量查询召唤师信息（summonerId -> SummonerInfo），查询失败的召唤师不在结果中
+pub async fn summoners_by_ids(client: &Client, ids: &[u64]) -> HashMap<u64, SummonerInfo> {
+    cached_lookup(&SUMMONERS, ids, |id| get_summoner_by_id(client, id)).await
+}
+
+/// 按名称#标签批量查询召唤师信息并补全段位（名称 -> SummonerInfo），用于只有名称的对局玩家
+pub async fn ranked_summoners_by_names(
+    client: &Client,
+    names: &[String],
+) -> HashMap<String, SummonerInfo> {
+    cached_lookup(&RANKED_SUMMONERS, names, |name| async move {
+        let mut info = get_summoner_by_name(client, &name).await?;
+        fill_summoner_extra_info(client, &mut info).await;
+        Ok::<_, LcuError>(info)
+    })
+    .await
+}
+
+/// 批量查询最近 RECENT_MATCH_COUNT 场战绩（puuid -> MatchStatistics）
+pub async fn recent_matches_by_puuids(
+    client: &Client,
+    puuids: &[String],
+) -> HashMap<String, MatchStatistics> {
+    cached_lookup(&RECENT_MATCHES, puuids, |puuid| async move {
+        get_recent_matches_by_puuid(client, &puuid, RECENT_MATCH_COUNT).await
+    })
+    .await
+}
+
+/// 最近 RECENT_MATCH_COUNT 场对局的原始数据（puuid -> 对局，从新到旧）
+///
+/// 先通过 recent_matches_by_puuids 查询（对局会写入本地战绩数据库），再从数据库读取
+pub async fn recent_games_by_puuids(
+    client: &Client,
+    puuids: &[String],
+) -> HashMap<String, Vec<Value>> {
+    recent_matches_by_puuids(client, puuids).await;
+    let mut result = HashMap::new();
+    for puuid in puuids {
+        let games = store::games_for_puuid(puuid, RECENT_MATCH_COUNT)
+            .await
+            .inspect_err(|e| log::warn!("[选人查询] 读取 {} 的战绩失败: {}", puuid, e))
+            .unwrap_or_default();
+        result.insert(puuid.clone(), games);
+    }
+    result
+}
+
+/// 选人会话中玩家的 puuid（cellId -> puuid），会话中没有时通过召唤师信息查询，机器人和隐藏身份的玩家除外
+pub async fn puuids_by_cell<'a>(
+    client: &Client,
+    players: impl IntoIterator<Item = &'a ChampSelectPlayer>,
+) -> HashMap<i32, String> {
+    let players: Vec<&ChampSelectPlayer> = players
+        .into_iter()
+        .filter(|p| p.summoner_id.as_deref().is_some_and(|id| id != "0"))
+        .collect();
+    let summoner_id = |p: &ChampSelectPlayer| p.summoner_id.as_deref()?.parse::<u64>().ok();
+    let session_puuid = |p: &ChampSelectPlayer| p.puuid.clone().filter(|puuid| !puuid.is_empty());
+    let ids: Vec<u64> = players
+        .iter()
+        .filter(|p| session_puuid(p).is_none())
+        .filter_map(|p| summoner_id(p))
+        .collect();
+    let summoners = summoners_by_ids(client, &ids).await;
+    players
+        .iter()
+        .filter_map(|p| {
+            let puuid = session_puuid(p)
+                .or_else(|| Some(summoners.get(&summoner_id(p)?)?.puuid.clone()))?;
+            Some((p.cell_id, puuid))
+        })
+        .collect()
+}
+
+/// 选人会话中的英雄 id 是否有效（未选择时为 0）
+pub fn valid_champion_id(id: f64) -> bool {
+    id > 0.0 && id < 100_000.0
+}
+
+/// 玩家已选择的英雄，还没有选择时为预选的英雄
+pub fn player_champion_id(player: &ChampSelectPlayer) -> Option<i32> {
+    player
+        .champion_id
+        .filter(|&id| valid_champion_id(id))
+        .or(player
+            .champion_pick_intent
+            .filter(|&id| valid_champion_id(id)))
+        .map(|id| id as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcu;
+    use reqwest::Method;
+
+    #[tokio::test]
+    async fn lookups_are_cached_and_only_new_players_are_fetched() {
+        let mock = MockLcu::start().await;
+        clear();
+        let client = mock.client();
+
+        let first = summoners_by_ids(&client, &[4011000000000001]).await;
+        assert_eq!(first.len(), 1);
+        let both = summoners_by_ids(
+            &client,
+            &[4011000000000001, 4011000000000002, 4011000000000002],
+        )
+        .await;
+        assert_eq!(both.len(), 2);
+        let summoner_requests = mock.requests_to(Method::GET, "/lol-summoner/v1/summoners/");
+        assert_eq!(summoner_requests.len(), 2);
+
+        let puuid = first[&4011000000000001].puuid.clone();
+        for _ in 0..2 {
+            let matches = recent_matches_by_puuids(&client, std::slice::from_ref(&puuid)).await;
+            assert_eq!(matches[&puuid].total_games, 3);
+        }
+        let history_requests = mock.requests_to(Method::GET, "/lol-match-history/v1/products/lol/");
+        assert_eq!(history_requests.len(), 1);
+        clear();
+    }
+}
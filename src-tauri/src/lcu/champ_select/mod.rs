@@ -1,2 +1,4 @@
+pub mod analysis;
 pub mod commands;
+pub mod lookup;
 pub mod service;
@@ -1,14 +1,13 @@
-
-use crate::lcu::matches::service::get_recent_matches_by_puuid;
+use crate::lcu::champ_select::lookup;
+use crate::lcu::error::LcuError;
 use crate::lcu::request::{lcu_get, lcu_patch_no_content};
-use crate::lcu::summoner::service::get_summoner_by_id;
 use crate::lcu::types::{ChampSelectPlayer, ChampSelectSession, MatchStatistics, SummonerInfo};
 use reqwest::Client;
 use serde_json::{Number, Value};
 use std::collections::HashMap;
 
 // 获取选人会话信息 (简化版本，返回 Value)
-pub async fn get_champ_select_session_raw(client: &Client) -> Result<Value, String> {
+pub async fn get_champ_select_session_raw(client: &Client) -> Result<Value, LcuError> {
     lcu_get(client, "/lol-champ-select/v1/session").await
 }
 
@@ -18,14 +17,38 @@ pub async fn champion_action(
     action_id: u64,
     champion_id: u64,
     completed: bool,
-) -> Result<(), String> {
+) -> Result<(), LcuError> {
     let url = format!("/lol-champ-select/v1/session/actions/{}", action_id);
     let body = serde_json::json!({
         "championId": champion_id,
         "completed": completed
     });
 
-    lcu_patch_no_content(client, &url, body).await
+    lcu_patch_no_content(client, &url, body)
+        .await
+        .inspect_err(|e| {
+            // 例如未拥有英雄、当前不是该 action 的回合等，errorCode/message 在错误中
+            log::warn!(
+                "[ChampSelect] action {} 操作英雄 {} 失败 (completed={}): {}",
+                action_id,
+                champion_id,
+                completed,
+                e
+            );
+        })
+}
+
+// 设置本地玩家的召唤师技能（spell1 为 D 键，spell2 为 F 键）
+pub async fn set_summoner_spells(
+    client: &Client,
+    spell1_id: i32,
+    spell2_id: i32,
+) -> Result<(), LcuError> {
+    let body = serde_json::json!({
+        "spell1Id": spell1_id,
+        "spell2Id": spell2_id
+    });
+    lcu_patch_no_content(client, "/lol-champ-select/v1/session/my-selection", body).await
 }
 
 // 选择英雄 (hover 或 lock)
@@ -34,12 +57,16 @@ pub async fn pick_champion(
     action_id: u64,
     champion_id: u64,
     completed: bool,
-) -> Result<(), String> {
+) -> Result<(), LcuError> {
     champion_action(client, action_id, champion_id, completed).await
 }
 
 // 禁用英雄
-pub async fn ban_champion(client: &Client, action_id: u64, champion_id: u64) -> Result<(), String> {
+pub async fn ban_champion(
+    client: &Client,
+    action_id: u64,
+    champion_id: u64,
+) -> Result<(), LcuError> {
     champion_action(client, action_id, champion_id, true).await
 }
 
@@ -88,28 +115,22 @@ fn fix_bans(ban_list: &mut Vec<Value>) {
     }
 }
 
-/// 批量 enrich 召唤师信息
+/// 批量 enrich 召唤师信息（并发查询，结果缓存）
 async fn enrich_champ_select_session(client: &Client, session: &mut ChampSelectSession) {
     // 收集所有 summoner_id
-    let mut all_ids = vec![];
-    for p in session.my_team.iter().chain(session.their_team.iter()) {
-        if let Some(sid) = &p.summoner_id {
-            if sid != "0" && !all_ids.contains(sid) {
-                all_ids.push(sid.clone());
-            }
-        }
-    }
-    // 查询所有召唤师信息和 puuid
-    let mut info_map = std::collections::HashMap::new();
-    let mut puuid_map = std::collections::HashMap::new();
-    for sid in &all_ids {
-        if let Ok(id) = sid.parse::<u64>() {
-            if let Ok(info) = get_summoner_by_id(client, id).await {
-                puuid_map.insert(sid.clone(), info.puuid.clone());
-                info_map.insert(sid.clone(), info);
-            }
-        }
-    }
+    let all_ids: Vec<u64> = session
+        .my_team
+        .iter()
+        .chain(session.their_team.iter())
+        .filter_map(|p| p.summoner_id.as_deref()?.parse::<u64>().ok())
+        .filter(|&id| id != 0)
+        .collect();
+    // 查询所有召唤师信息
+    let info_map: HashMap<String, SummonerInfo> = lookup::summoners_by_ids(client, &all_ids)
+        .await
+        .into_iter()
+        .map(|(id, info)| (id.to_string(), info))
+        .collect();
     // 补全 my_team
     for p in session.my_team.iter_mut() {
         enrich_player(p, &info_map);
@@ -149,10 +170,17 @@ fn enrich_player(
 // ---------- 主函数 ----------
 
 /// 获取当前选人阶段的完整 session 信息（最优实践版）
-pub async fn get_champ_select_session(client: &Client) -> Result<ChampSelectSession, String> {
+pub async fn get_champ_select_session(client: &Client) -> Result<ChampSelectSession, LcuError> {
     // 直接用通用 LCU 请求工具
-    let mut json: Value = lcu_get(client, "/lol-champ-select/v1/session").await?;
+    let json: Value = lcu_get(client, "/lol-champ-select/v1/session").await?;
+    build_champ_select_session(client, json).await
+}
 
+/// 将原始 session JSON（HTTP 或 WebSocket 推送）清洗、反序列化并补全召唤师信息
+pub async fn build_champ_select_session(
+    client: &Client,
+    mut json: Value,
+) -> Result<ChampSelectSession, LcuError> {
     // 数据清洗 -- myTeam & theirTeam
     if let Some(my_team) = json.get_mut("myTeam").and_then(|t| t.as_array_mut()) {
         fix_team_array(my_team);
@@ -171,17 +199,20 @@ pub async fn get_champ_select_session(client: &Client) -> Result<ChampSelectSess
     }
     log::info!("[get_champ_select_session] 原始 session JSON");
     // 反序列化为结构体
-    let mut session = serde_json::from_value::<ChampSelectSession>(json)
-        .map_err(|e| format!("解析 session 响应失败: {}", e))?;
+    let mut session =
+        serde_json::from_value::<ChampSelectSession>(json).map_err(|e| LcuError::Decode {
+            path: "/lol-champ-select/v1/session".to_string(),
+            message: e.to_string(),
+        })?;
     // enrich
     enrich_champ_select_session(client, &mut session).await;
     Ok(session)
 }
 
-// 主函数：批量获取队友和对手信息（无缓存，简洁版）
+// 主函数：批量获取队友和对手信息（并发查询，结果按 summonerId / puuid 缓存）
 pub async fn get_champselect_team_players_info(
     client: &Client,
-) -> Result<HashMap<String, MatchStatistics>, String> {
+) -> Result<HashMap<String, MatchStatistics>, LcuError> {
     // 1. 获取当前选人会话
     let session: serde_json::Value = lcu_get(client, "/lol-champ-select/v1/session").await?;
     let my_team = session
@@ -194,50 +225,103 @@ pub async fn get_champselect_team_players_info(
         .ok_or("theirTeam解析失败")?;
 
     // 2. 收集所有 summoner_id
-    let extract_id = |player: &serde_json::Value| {
-        player
-            .get("summonerId")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0)
-            .to_string()
-    };
-    let my_ids: Vec<String> = my_team
+    let mut all_ids: Vec<u64> = my_team
         .iter()
-        .map(extract_id)
-        .filter(|id| id != "0")
+        .chain(their_team.iter())
+        .filter_map(|player| player.get("summonerId").and_then(|v| v.as_u64()))
+        .filter(|&id| id != 0)
         .collect();
-    let their_ids: Vec<String> = their_team
-        .iter()
-        .map(extract_id)
-        .filter(|id| id != "0")
-        .collect();
-
-    // 3. 批量查SummonerInfo
-    let mut all_ids = my_ids.clone();
-    all_ids.extend(their_ids.iter().cloned());
     all_ids.sort();
     all_ids.dedup();
 
-    let mut info_map = HashMap::new();
-    for sid in &all_ids {
-        if let Ok(id) = sid.parse::<u64>() {
-            if let Ok(info) = get_summoner_by_id(client, id).await {
-                info_map.insert(sid.clone(), info);
+    // 3. 批量查SummonerInfo
+    let info_map = lookup::summoners_by_ids(client, &all_ids).await;
+
+    // 4. 批量查最近战绩
+    log::info!(
+        "准备批量查最近{}场战绩, 总人数: {}",
+        lookup::RECENT_MATCH_COUNT,
+        info_map.len()
+    );
+    let puuids: Vec<String> = info_map.values().map(|info| info.puuid.clone()).collect();
+    let matches = lookup::recent_matches_by_puuids(client, &puuids).await;
+    let match_map = info_map
+        .into_iter()
+        .filter_map(|(sid, info)| Some((sid.to_string(), matches.get(&info.puuid)?.clone())))
+        .collect();
+    Ok(match_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcu;
+
+    #[tokio::test]
+    async fn champ_select_session_is_cleaned_and_enriched() {
+        let mock = MockLcu::start().await;
+        mock.transition("ChampSelect");
+
+        let session = get_champ_select_session(&mock.client()).await.unwrap();
+
+        assert_eq!(session.local_player_cell_id, 0);
+        assert_eq!(session.timer.phase, "BAN_PICK");
+        assert_eq!(session.actions.len(), 2);
+
+        let me = &session.my_team[0];
+        assert_eq!(me.summoner_id.as_deref(), Some("4011000000000001"));
+        assert_eq!(me.display_name.as_deref(), Some("Nidalee#CN1"));
+        assert_eq!(me.champion_id, None);
+        assert_eq!(me.champion_pick_intent, Some(76.0));
+
+        let teammate = &session.my_team[1];
+        assert_eq!(teammate.display_name.as_deref(), Some("Teammate#CN1"));
+        assert_eq!(teammate.profile_icon_id, Some(29));
+
+        // 排位中对方召唤师 ID 隐藏为 0
+        assert_eq!(
+            session.their_team[0].display_name.as_deref(),
+            Some("机器人")
+        );
+    }
+
+    #[tokio::test]
+    async fn champ_select_session_outside_champ_select_is_http_404() {
+        let mock = MockLcu::start().await;
+        mock.transition("Lobby");
+
+        let err = get_champ_select_session(&mock.client()).await.unwrap_err();
+
+        match err {
+            LcuError::HttpStatus {
+                status,
+                error_code,
+                path,
+                ..
+            } => {
+                assert_eq!(status, 404);
+                assert_eq!(error_code.as_deref(), Some("RPC_ERROR"));
+                assert_eq!(path, "/lol-champ-select/v1/session");
             }
+            other => panic!("unexpected error: {:?}", other),
         }
     }
 
-    // 4. 批量查最近战绩
-    let mut match_map = HashMap::new();
-    log::info!("准备批量查最近10场战绩, 总人数: {}", info_map.len());
-    for (sid, info) in &info_map {
-        log::info!("查找召唤师 {} recent matches", sid);
-        if let Ok(matches) = get_recent_matches_by_puuid(client, &info.puuid, 20).await {
-            log::info!("查到 {:?} 场", matches);
-            match_map.insert(sid.clone(), matches);
-        } else {
-            log::warn!("查找 {} 失败", sid);
-        }
+    #[tokio::test]
+    async fn pick_champion_patches_action() {
+        let mock = MockLcu::start().await;
+        mock.transition("ChampSelect");
+
+        pick_champion(&mock.client(), 11, 76, true).await.unwrap();
+
+        let patches = mock.requests_to(
+            reqwest::Method::PATCH,
+            "/lol-champ-select/v1/session/actions/11",
+        );
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            patches[0].body,
+            Some(serde_json::json!({ "championId": 76, "completed": true }))
+        );
     }
-    Ok(match_map)
 }
@@ -1,6 +1,5 @@
-
 use crate::lcu::auth::service::{
-    ensure_valid_auth_info, invalidate_auth_info, validate_auth_connection,
+    ensure_valid_endpoint, invalidate_auth_info, validate_auth_connection,
 };
 use crate::lcu::types::{ConnectionState, LcuAuthInfo};
 use crate::lcu::unified_polling::UnifiedPollingManager;
@@ -90,9 +89,9 @@ impl ConnectionManager {
 
     pub async fn check_connection_state(&self) -> ConnectionState {
         // 1. 尝试获取认证信息
-        let auth_info = ensure_valid_auth_info();
+        let endpoint = ensure_valid_endpoint().await;
 
-        if auth_info.is_none() {
+        if endpoint.is_none() {
             // 无认证信息，检查是否有进程
             if self.has_lol_process().await {
                 self.update_info(|info| {
@@ -115,10 +114,11 @@ impl ConnectionManager {
             }
         }
 
-        let auth = auth_info.unwrap();
+        let endpoint = endpoint.unwrap();
+        let auth = endpoint.auth.clone();
 
         // 2. 验证连接是否真正可用
-        let connection_valid = validate_auth_connection(&auth).await;
+        let connection_valid = validate_auth_connection(&endpoint).await;
 
         if connection_valid {
             self.update_info(|info| {
@@ -175,29 +175,10 @@ impl ConnectionManager {
     }
 
     async fn has_lol_process(&self) -> bool {
-        use sysinfo::{ProcessRefreshKind, RefreshKind, System};
-
-        let mut system = System::new();
-        system.refresh_specifics(
-            RefreshKind::nothing().with_processes(ProcessRefreshKind::everything()),
-        );
-
-        let possible_names = [
-            "LeagueClientUx.exe",
-            "LeagueClient.exe",
-            "LeagueOfLegends.exe",
-        ];
-
-        for (_pid, process) in system.processes() {
-            let process_name = process.name().to_string_lossy();
-            if possible_names
-                .iter()
-                .any(|name| process_name.eq_ignore_ascii_case(name))
-            {
-                return true;
-            }
-        }
-        false
+        // 进程扫描是阻塞操作，放到阻塞线程池中执行
+        tokio::task::spawn_blocking(scan_lol_process)
+            .await
+            .unwrap_or(false)
     }
 
     async fn handle_state_change(&self, old_state: &ConnectionState, new_state: &ConnectionState) {
@@ -289,3 +270,29 @@ impl ConnectionManager {
         .await;
     }
 }
+
+/// 检查是否存在任一 LoL 客户端进程（阻塞）
+fn scan_lol_process() -> bool {
+    use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+
+    let mut system = System::new();
+    system
+        .refresh_specifics(RefreshKind::nothing().with_processes(ProcessRefreshKind::everything()));
+
+    let possible_names = [
+        "LeagueClientUx.exe",
+        "LeagueClient.exe",
+        "LeagueOfLegends.exe",
+    ];
+
+    for (_pid, process) in system.processes() {
+        let process_name = process.name().to_string_lossy();
+        if possible_names
+            .iter()
+            .any(|name| process_name.eq_ignore_ascii_case(name))
+        {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,64 @@
+//! LCU 访问端点：基础地址 + 认证信息，所有 HTTP/WebSocket 请求都通过它拼接地址
+use crate::lcu::types::LcuAuthInfo;
+use base64::{engine::general_purpose, Engine as _};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LcuEndpoint {
+    /// 例如 https://127.0.0.1:54321，不带结尾的 /
+    pub base_url: String,
+    pub auth: LcuAuthInfo,
+}
+
+impl LcuEndpoint {
+    /// 本机客户端（进程发现得到的端口和 token）
+    pub fn local(auth: LcuAuthInfo) -> Self {
+        Self {
+            base_url: format!("https://127.0.0.1:{}", auth.app_port),
+            auth,
+        }
+    }
+
+    /// 任意地址 + token，用于指向模拟的 LCU 服务
+    pub fn custom(base_url: &str, token: &str) -> Result<Self, String> {
+        let url = reqwest::Url::parse(base_url)
+            .map_err(|e| format!("LCU 地址无效 {}: {}", base_url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("LCU 地址仅支持 http/https: {}", base_url));
+        }
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth: LcuAuthInfo {
+                app_port: url.port_or_known_default().unwrap_or_default(),
+                remoting_auth_token: token.to_string(),
+                riotclient_app_port: 0,
+                riotclient_auth_token: String::new(),
+            },
+        })
+    }
+
+    /// 拼接完整请求地址，path 以 / 开头
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// WebSocket 地址：https -> wss，http -> ws
+    pub fn ws_url(&self) -> String {
+        let base = if let Some(rest) = self.base_url.strip_prefix("https://") {
+            format!("wss://{}", rest)
+        } else if let Some(rest) = self.base_url.strip_prefix("http://") {
+            format!("ws://{}", rest)
+        } else {
+            self.base_url.clone()
+        };
+        format!("{}/", base)
+    }
+
+    /// Basic 认证头的值
+    pub fn authorization(&self) -> String {
+        let auth_string = format!("riot:{}", self.auth.remoting_auth_token);
+        format!(
+            "Basic {}",
+            general_purpose::STANDARD.encode(auth_string.as_bytes())
+        )
+    }
+}
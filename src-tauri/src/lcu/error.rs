@@ -0,0 +1,212 @@
+//! LCU 请求错误类型，序列化为带 kind 标签的对象，前端可按类型分别处理
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, export_to = "../../src/types/generated/LcuError.ts")]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum LcuError {
+    /// 未找到客户端进程或认证信息
+    NotConnected { message: String },
+    /// 认证信息失效（401/403），与 HttpStatus 一样保留请求和响应信息
+    AuthExpired {
+        status: u16,
+        method: String,
+        path: String,
+        #[ts(type = "number")]
+        elapsed_ms: u64,
+        error_code: Option<String>,
+        message: Option<String>,
+        body: Option<String>,
+    },
+    /// 客户端返回非 2xx 状态，error_code/message 来自 LCU 的错误响应体
+    HttpStatus {
+        status: u16,
+        method: String,
+        path: String,
+        #[ts(type = "number")]
+        elapsed_ms: u64,
+        error_code: Option<String>,
+        message: Option<String>,
+        body: Option<String>,
+    },
+    /// 请求超时
+    Timeout { path: String },
+    /// 响应反序列化失败
+    Decode { path: String, message: String },
+    /// 连接失败、连接重置等传输层错误
+    Transport { message: String },
+    /// 请求被透传规则拒绝，未发送到客户端
+    Forbidden {
+        method: String,
+        path: String,
+        reason: String,
+    },
+    /// 其他错误（参数非法、数据缺失等）
+    Other { message: String },
+}
+
+/// LCU 错误响应体，如 {"errorCode":"RPC_ERROR","httpStatus":500,"message":"..."}
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LcuErrorBody {
+    error_code: Option<String>,
+    message: Option<String>,
+}
+
+/// 错误响应体最多保留的字符数，避免把整页 HTML 塞进日志
+const MAX_BODY_CHARS: usize = 2048;
+
+impl LcuError {
+    /// 根据非 2xx 响应构造错误（401/403 为 AuthExpired，其余为 HttpStatus），尽量解析 LCU 的 errorCode/message
+    pub fn from_http_status(
+        status: u16,
+        method: &str,
+        path: &str,
+        elapsed_ms: u64,
+        body: &str,
+    ) -> Self {
+        let parsed = serde_json::from_str::<LcuErrorBody>(body).ok();
+        let (error_code, message) = match parsed {
+            Some(b) => (b.error_code, b.message),
+            None => (None, None),
+        };
+        let body = body.trim();
+        let body = if body.is_empty() {
+            None
+        } else if body.chars().count() > MAX_BODY_CHARS {
+            Some(format!(
+                "{}...",
+                body.chars().take(MAX_BODY_CHARS).collect::<String>()
+            ))
+        } else {
+            Some(body.to_string())
+        };
+        let method = method.to_string();
+        let path = path.to_string();
+        if status == 401 || status == 403 {
+            LcuError::AuthExpired {
+                status,
+                method,
+                path,
+                elapsed_ms,
+                error_code,
+                message,
+                body,
+            }
+        } else {
+            LcuError::HttpStatus {
+                status,
+                method,
+                path,
+                elapsed_ms,
+                error_code,
+                message,
+                body,
+            }
+        }
+    }
+
+    pub fn other(message: impl Into<String>) -> Self {
+        LcuError::Other {
+            message: message.into(),
+        }
+    }
+
+    /// 将 reqwest 发送阶段的错误归类为超时或传输错误
+    pub fn from_reqwest(err: reqwest::Error, path: &str) -> Self {
+        if err.is_timeout() {
+            LcuError::Timeout {
+                path: path.to_string(),
+            }
+        } else {
+            LcuError::Transport {
+                message: err.to_string(),
+            }
+        }
+    }
+
+    /// HTTP 状态码（仅 AuthExpired / HttpStatus 有）
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            LcuError::AuthExpired { status, .. } | LcuError::HttpStatus { status, .. } => {
+                Some(*status)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LcuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LcuError::NotConnected { message } => write!(f, "{}", message),
+            LcuError::AuthExpired {
+                status,
+                method,
+                path,
+                error_code,
+                message,
+                ..
+            }
+            | LcuError::HttpStatus {
+                status,
+                method,
+                path,
+                error_code,
+                message,
+                ..
+            } => {
+                let title = if matches!(self, LcuError::AuthExpired { .. }) {
+                    "认证信息已失效"
+                } else {
+                    "服务器返回错误"
+                };
+                write!(f, "{}: {} ({} {})", title, status, method, path)?;
+                if let Some(code) = error_code {
+                    write!(f, " [{}]", code)?;
+                }
+                if let Some(message) = message {
+                    write!(f, " {}", message)?;
+                }
+                Ok(())
+            }
+            LcuError::Timeout { path } => write!(f, "请求超时: {}", path),
+            LcuError::Decode { path, message } => {
+                write!(f, "解析响应失败 ({}): {}", path, message)
+            }
+            LcuError::Transport { message } => write!(f, "请求失败: {}", message),
+            LcuError::Forbidden {
+                method,
+                path,
+                reason,
+            } => write!(f, "请求被拒绝 ({} {}): {}", method, path, reason),
+            LcuError::Other { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for LcuError {}
+
+impl From<String> for LcuError {
+    fn from(message: String) -> Self {
+        LcuError::Other { message }
+    }
+}
+
+impl From<&str> for LcuError {
+    fn from(message: &str) -> Self {
+        LcuError::other(message)
+    }
+}
+
+impl From<LcuError> for String {
+    fn from(err: LcuError) -> Self {
+        err.to_string()
+    }
+}
@@ -1,24 +1,15 @@
 use crate::http_client;
+use crate::lcu::error::LcuError;
+use crate::lcu::live_client::service::{get_player_list, LIVE_CLIENT_URL};
+use crate::lcu::types::LiveClientPlayer;
 
 #[tauri::command]
-pub async fn get_live_player_list() -> Result<String, String> {
-    let client = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true) // 👈 忽略证书错误
-        .build()
-        .map_err(|e| e.to_string())?;
-
-    let resp = client
-        .get("https://127.0.0.1:2999/liveclientdata/playerlist")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let text = resp.text().await.map_err(|e| e.to_string())?;
-    Ok(text)
+pub async fn get_live_player_list() -> Result<Vec<LiveClientPlayer>, LcuError> {
+    get_player_list(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
 }
 
 #[tauri::command]
-pub async fn get_game_version() -> Result<String, String> {
+pub async fn get_game_version() -> Result<String, LcuError> {
     // 尝试从公开的Riot API获取最新版本
     let client = http_client::get_public_client();
 
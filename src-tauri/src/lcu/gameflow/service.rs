@@ -1,8 +1,14 @@
+use crate::lcu::error::LcuError;
 use crate::lcu::request::lcu_get;
 use reqwest::Client;
 
 /// 获取当前游戏流程阶段（如 "Lobby"、"Matchmaking"、"ChampSelect"、"InProgress"、"EndOfGame" 等）
-pub async fn get_gameflow_phase(client: &Client) -> Result<String, String> {
+pub async fn get_gameflow_phase(client: &Client) -> Result<String, LcuError> {
     // 由于 LCU 该接口直接返回 JSON 字符串，所以用 lcu_get::<String>
     lcu_get(client, "/lol-gameflow/v1/gameflow-phase").await
 }
+
+/// 获取当前游戏流程会话（包含队列、地图等信息），不在对局流程中时返回 404
+pub async fn get_gameflow_session(client: &Client) -> Result<serde_json::Value, LcuError> {
+    lcu_get(client, "/lol-gameflow/v1/session").await
+}
@@ -0,0 +1,44 @@
+use crate::http_client;
+use crate::lcu::ddragon;
+use crate::lcu::error::LcuError;
+use crate::lcu::item_sets::service;
+use crate::lcu::opgg::service as opgg_service;
+
+/// 把 OP.GG 推荐出装写入客户端物品集
+#[tauri::command]
+pub async fn apply_opgg_item_set(
+    region: String,
+    mode: String,
+    champion_id: i32,
+    champion_name: String,
+    position: Option<String>,
+    tier: String,
+) -> Result<String, LcuError> {
+    let build =
+        opgg_service::get_champion_build(&region, &mode, champion_id, position, &tier).await?;
+    let blocks = service::blocks_from_opgg(&build.items);
+    let item_set = service::build_item_set(&champion_name, "OP.GG", &mode, blocks);
+    let client = http_client::get_lcu_client();
+    service::write_item_set(client, champion_id, &item_set).await
+}
+
+/// 把 ChampR 出装写入客户端物品集
+#[tauri::command]
+pub async fn apply_champr_item_set(
+    source: String,
+    champion_alias: String,
+    champion_id: i32,
+    champion_name: String,
+    build_index: Option<usize>,
+) -> Result<String, LcuError> {
+    let client = http_client::get_lcu_client();
+    let data = ddragon::get_builds_by_alias(client, &source, &champion_alias).await?;
+    let build = data
+        .get("content")
+        .and_then(|c| c.as_array())
+        .and_then(|c| c.get(build_index.unwrap_or(0)))
+        .ok_or("无法获取详细数据")?;
+    let blocks = service::blocks_from_champr(build);
+    let item_set = service::build_item_set(&champion_name, &source, &source, blocks);
+    service::write_item_set(client, champion_id, &item_set).await
+}
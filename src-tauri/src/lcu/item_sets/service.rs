@@ -0,0 +1,368 @@
+//! 推荐装备（物品集）写入客户端
+//!
+//! 把 OP.GG / ChampR 的出装转换为物品集，通过 /lol-item-sets/v1/item-sets/{summonerId}/sets 上传。
+//! Nidalee 写入的物品集 uid 为 nidalee-{模式}-{英雄ID}，按出装的游戏模式只关联对应的地图，
+//! 只替换同一英雄、同一模式之前由 Nidalee 写入的物品集，玩家自己或其他工具创建的物品集保持不变。
+use crate::lcu::error::LcuError;
+use crate::lcu::opgg::types::{OpggItem, OpggItems};
+use crate::lcu::request::{lcu_get, lcu_put};
+use crate::lcu::types::{ItemBlock, ItemSet, RecommendedItem};
+use reqwest::Client;
+use serde_json::{json, Value};
+
+/// Nidalee 写入的物品集 uid 前缀
+const NIDALEE_UID_PREFIX: &str = "nidalee-";
+
+/// 出装的游戏模式（OP.GG 的 ranked/aram/urf/arena，或 ChampR 的数据源名称，如 op.gg-aram）
+/// 对应的物品集 mode 和 map
+fn item_set_mode(game_mode: &str) -> (&'static str, &'static str) {
+    let name = game_mode.to_ascii_lowercase();
+    if name.contains("aram") || name == "murderbridge" {
+        ("ARAM", "HA")
+    } else if name.contains("urf") {
+        ("URF", "SR")
+    } else if name.contains("arena") {
+        ("CHERRY", "any")
+    } else {
+        ("CLASSIC", "SR")
+    }
+}
+
+/// 物品集 mode 对应的地图：召唤师峡谷 11、嚎哭深渊 12、斗魂竞技场 30
+fn associated_maps(mode: &str) -> Vec<i32> {
+    match mode {
+        "ARAM" => vec![12],
+        "CHERRY" => vec![30],
+        _ => vec![11],
+    }
+}
+
+fn item_set_uid(mode: &str, champion_id: i32) -> String {
+    format!(
+        "{}{}-{}",
+        NIDALEE_UID_PREFIX,
+        mode.to_ascii_lowercase(),
+        champion_id
+    )
+}
+
+/// 同一组合中重复的装备合并为数量（如两瓶药水）
+fn to_items(ids: &[i32]) -> Vec<RecommendedItem> {
+    let mut items: Vec<RecommendedItem> = Vec::new();
+    for id in ids.iter().filter(|&&id| id > 0) {
+        let id = id.to_string();
+        match items.iter_mut().find(|item| item.id == id) {
+            Some(item) => item.count += 1,
+            None => items.push(RecommendedItem { id, count: 1 }),
+        }
+    }
+    items
+}
+
+fn stats_suffix(item: &OpggItem) -> String {
+    if item.play <= 0 {
+        return String::new();
+    }
+    format!(
+        " (胜率 {:.1}% / 出场 {:.1}%)",
+        item.win as f64 * 100.0 / item.play as f64,
+        item.pick_rate * 100.0
+    )
+}
+
+/// 多个组合中出现过的装备，按出现顺序去重
+fn unique_items<'a>(entries: impl Iterator<Item = &'a OpggItem>) -> Vec<RecommendedItem> {
+    let mut seen = Vec::new();
+    for id in entries.flat_map(|entry| entry.ids.iter()) {
+        if *id > 0 && !seen.contains(id) {
+            seen.push(*id);
+        }
+    }
+    seen.into_iter()
+        .map(|id| RecommendedItem {
+            id: id.to_string(),
+            count: 1,
+        })
+        .collect()
+}
+
+/// OP.GG 出装 -> 物品集的块：起始、核心、鞋子、后期装备
+pub fn blocks_from_opgg(items: &OpggItems) -> Vec<ItemBlock> {
+    let mut blocks = Vec::new();
+    if let Some(start) = items.start_items.first() {
+        blocks.push(ItemBlock {
+            block_type: format!("起始装备{}", stats_suffix(start)),
+            items: to_items(&start.ids),
+        });
+    }
+    if let Some(core) = items.core_items.first() {
+        blocks.push(ItemBlock {
+            block_type: format!("核心装备{}", stats_suffix(core)),
+            items: to_items(&core.ids),
+        });
+        let alternatives = unique_items(items.core_items.iter().skip(1).take(4));
+        if !alternatives.is_empty() {
+            blocks.push(ItemBlock {
+                block_type: "核心装备（备选）".to_string(),
+                items: alternatives,
+            });
+        }
+    }
+    if !items.boots.is_empty() {
+        blocks.push(ItemBlock {
+            block_type: "鞋子".to_string(),
+            items: unique_items(items.boots.iter().take(3)),
+        });
+    }
+    if !items.last_items.is_empty() {
+        blocks.push(ItemBlock {
+            block_type: "后期装备".to_string(),
+            items: unique_items(items.last_items.iter().take(8)),
+        });
+    }
+    blocks.retain(|block| !block.items.is_empty());
+    blocks
+}
+
+/// ChampR 出装 -> 物品集的块（ChampR 的 itemBuilds 已经是客户端格式，取第一套）
+pub fn blocks_from_champr(build: &Value) -> Vec<ItemBlock> {
+    let blocks = build
+        .get("itemBuilds")
+        .and_then(|b| b.as_array())
+        .and_then(|b| b.first())
+        .and_then(|b| b.get("blocks"))
+        .and_then(|b| b.as_array())
+        .cloned()
+        .unwrap_or_default();
+    blocks
+        .iter()
+        .filter_map(|block| {
+            let items: Vec<RecommendedItem> = block
+                .get("items")?
+                .as_array()?
+                .iter()
+                .filter_map(|item| {
+                    let id = match item.get("id")? {
+                        Value::String(id) => id.clone(),
+                        Value::Number(id) => id.to_string(),
+                        _ => return None,
+                    };
+                    let count = item.get("count").and_then(|c| c.as_i64()).unwrap_or(1) as i32;
+                    Some(RecommendedItem { id, count })
+                })
+                .collect();
+            (!items.is_empty()).then(|| ItemBlock {
+                block_type: block
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                items,
+            })
+        })
+        .collect()
+}
+
+/// game_mode 为出装的游戏模式，决定物品集适用的模式和地图
+pub fn build_item_set(
+    champion: &str,
+    source: &str,
+    game_mode: &str,
+    blocks: Vec<ItemBlock>,
+) -> ItemSet {
+    let (mode, map) = item_set_mode(game_mode);
+    ItemSet {
+        title: format!("Nidalee : {} ({})", champion, source),
+        champion: champion.to_string(),
+        mode: mode.to_string(),
+        map: map.to_string(),
+        blocks,
+    }
+}
+
+/// 是否为该英雄、该模式之前由 Nidalee 写入的物品集（包括旧版本不区分模式的 nidalee-{英雄ID}）
+fn is_replaced_by(set: &Value, uid: &str, champion_id: i32) -> bool {
+    let legacy_uid = format!("{}{}", NIDALEE_UID_PREFIX, champion_id);
+    set.get("uid")
+        .and_then(|u| u.as_str())
+        .is_some_and(|existing| existing == uid || existing == legacy_uid)
+}
+
+/// 转换为客户端物品集格式
+fn to_lcu_item_set(item_set: &ItemSet, champion_id: i32) -> Value {
+    json!({
+        "uid": item_set_uid(&item_set.mode, champion_id),
+        "title": item_set.title,
+        "type": "custom",
+        "map": item_set.map,
+        "mode": item_set.mode,
+        "sortrank": 0,
+        "startedFrom": "blank",
+        "associatedChampions": [champion_id],
+        "associatedMaps": associated_maps(&item_set.mode),
+        "preferredItemSlots": [],
+        "blocks": item_set.blocks.iter().map(|block| json!({
+            "type": block.block_type,
+            "hideIfSummonerSpell": "",
+            "showIfSummonerSpell": "",
+            "items": block.items,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// 写入物品集：替换该英雄同一模式之前由 Nidalee 写入的物品集，其余物品集原样保留
+pub async fn write_item_set(
+    client: &Client,
+    champion_id: i32,
+    item_set: &ItemSet,
+) -> Result<String, LcuError> {
+    if item_set.blocks.is_empty() {
+        return Err(LcuError::other("出装数据中没有可用的装备"));
+    }
+    let summoner: Value = lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
+    let summoner_id = summoner["summonerId"]
+        .as_u64()
+        .ok_or_else(|| LcuError::other("无法获取召唤师ID"))?;
+    let path = format!("/lol-item-sets/v1/item-sets/{}/sets", summoner_id);
+
+    let mut sets: Value = lcu_get(client, &path).await?;
+    let existing = sets
+        .get("itemSets")
+        .and_then(|s| s.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let uid = item_set_uid(&item_set.mode, champion_id);
+    let replaced = existing
+        .iter()
+        .filter(|set| is_replaced_by(set, &uid, champion_id))
+        .count();
+    let mut item_sets: Vec<Value> = existing
+        .into_iter()
+        .filter(|set| !is_replaced_by(set, &uid, champion_id))
+        .collect();
+    item_sets.push(to_lcu_item_set(item_set, champion_id));
+
+    if !sets.is_object() {
+        sets = json!({});
+    }
+    sets["itemSets"] = Value::Array(item_sets);
+    lcu_put::<Value>(client, &path, sets).await?;
+
+    log::info!(
+        "[ItemSets] 已写入物品集 {}（替换 {} 个旧物品集）",
+        item_set.title,
+        replaced
+    );
+    Ok(format!("成功写入物品集: {}", item_set.title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+    use reqwest::Method;
+
+    fn opgg_item(ids: Vec<i32>) -> OpggItem {
+        OpggItem {
+            id: ids[0],
+            ids,
+            icons: vec![],
+            win: 520,
+            play: 1000,
+            pick_rate: 0.25,
+        }
+    }
+
+    #[test]
+    fn opgg_items_become_blocks() {
+        let items = OpggItems {
+            start_items: vec![opgg_item(vec![1056, 2003, 2003])],
+            core_items: vec![
+                opgg_item(vec![6655, 3020, 4645]),
+                opgg_item(vec![6655, 3157, 4645]),
+            ],
+            boots: vec![opgg_item(vec![3020]), opgg_item(vec![3158])],
+            last_items: vec![],
+        };
+
+        let blocks = blocks_from_opgg(&items);
+
+        let types: Vec<&str> = blocks.iter().map(|b| b.block_type.as_str()).collect();
+        assert_eq!(
+            types,
+            [
+                "起始装备 (胜率 52.0% / 出场 25.0%)",
+                "核心装备 (胜率 52.0% / 出场 25.0%)",
+                "核心装备（备选）",
+                "鞋子"
+            ]
+        );
+        assert_eq!(blocks[0].items.len(), 2);
+        assert_eq!(blocks[0].items[1].id, "2003");
+        assert_eq!(blocks[0].items[1].count, 2);
+    }
+
+    #[tokio::test]
+    async fn write_item_set_replaces_only_own_sets_for_champion_and_mode() {
+        let mock = MockLcu::start().await;
+        let path = "/lol-item-sets/v1/item-sets/4011000000000001/sets";
+        mock.respond(
+            Method::GET,
+            path,
+            MockResponse::json(json!({
+                "accountId": 2945417630000001u64,
+                "timestamp": 1730000000000u64,
+                "itemSets": [
+                    { "uid": "user-made", "title": "我的阿狸", "associatedChampions": [103] },
+                    { "uid": "nidalee-103", "title": "Nidalee : 阿狸", "associatedChampions": [103] },
+                    { "uid": "nidalee-aram-103", "title": "Nidalee : 阿狸 (op.gg-aram)", "associatedChampions": [103] },
+                    { "uid": "nidalee-classic-76", "title": "Nidalee : 豹女", "associatedChampions": [76] }
+                ]
+            })),
+        );
+        mock.handle(Method::PUT, path, |req| {
+            MockResponse::json(req.body.clone().unwrap_or_default())
+        });
+
+        let blocks = vec![ItemBlock {
+            block_type: "核心装备".to_string(),
+            items: to_items(&[6655, 3020]),
+        }];
+        let item_set = build_item_set("阿狸", "OP.GG", "ranked", blocks);
+        write_item_set(&mock.client(), 103, &item_set)
+            .await
+            .unwrap();
+
+        let puts = mock.requests_to(Method::PUT, path);
+        assert_eq!(puts.len(), 1);
+        let body = puts[0].body.as_ref().unwrap();
+        assert_eq!(body["accountId"], 2945417630000001u64);
+        let uids: Vec<&str> = body["itemSets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["uid"].as_str().unwrap())
+            .collect();
+        // 旧版本不区分模式的 nidalee-103 被替换，大乱斗的物品集保留
+        assert_eq!(
+            uids,
+            [
+                "user-made",
+                "nidalee-aram-103",
+                "nidalee-classic-76",
+                "nidalee-classic-103"
+            ]
+        );
+        let written = &body["itemSets"][3];
+        assert_eq!(written["title"], "Nidalee : 阿狸 (OP.GG)");
+        assert_eq!(written["mode"], "CLASSIC");
+        assert_eq!(written["map"], "SR");
+        assert_eq!(written["associatedMaps"], json!([11]));
+        assert_eq!(written["blocks"][0]["items"][0]["id"], "6655");
+
+        let aram = build_item_set("阿狸", "op.gg-aram", "op.gg-aram", vec![]);
+        assert_eq!((aram.mode.as_str(), aram.map.as_str()), ("ARAM", "HA"));
+        assert_eq!(associated_maps(&aram.mode), [12]);
+        assert_eq!(item_set_uid(&aram.mode, 103), "nidalee-aram-103");
+    }
+}
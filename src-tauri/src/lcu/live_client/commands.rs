@@ -0,0 +1,60 @@
+use crate::lcu::error::LcuError;
+use crate::lcu::live_client::service::{self, LIVE_CLIENT_URL};
+use crate::{http_client, lcu};
+
+/// 对局中的全部玩家及其段位和最近战绩（对局加载完成后可用）
+#[tauri::command]
+pub async fn get_live_game_players() -> Result<Vec<lcu::types::LiveGamePlayer>, LcuError> {
+    let client = http_client::get_lcu_client();
+    service::get_live_game_players(client, LIVE_CLIENT_URL).await
+}
+
+#[tauri::command]
+pub async fn get_live_all_game_data() -> Result<lcu::types::LiveClientAllGameData, LcuError> {
+    service::get_all_game_data(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
+}
+
+#[tauri::command]
+pub async fn get_live_active_player() -> Result<lcu::types::LiveClientActivePlayer, LcuError> {
+    service::get_active_player(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
+}
+
+#[tauri::command]
+pub async fn get_live_active_player_abilities() -> Result<lcu::types::LiveClientAbilities, LcuError>
+{
+    service::get_active_player_abilities(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
+}
+
+#[tauri::command]
+pub async fn get_live_active_player_runes() -> Result<lcu::types::LiveClientFullRunes, LcuError> {
+    service::get_active_player_runes(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
+}
+
+/// riot_id 为名称#标签
+#[tauri::command]
+pub async fn get_live_player_scores(
+    riot_id: String,
+) -> Result<lcu::types::LiveClientScores, LcuError> {
+    service::get_player_scores(http_client::get_lcu_client(), LIVE_CLIENT_URL, &riot_id).await
+}
+
+/// riot_id 为名称#标签
+#[tauri::command]
+pub async fn get_live_player_items(
+    riot_id: String,
+) -> Result<Vec<lcu::types::LiveClientItem>, LcuError> {
+    service::get_player_items(http_client::get_lcu_client(), LIVE_CLIENT_URL, &riot_id).await
+}
+
+/// event_id 为空时返回全部事件
+#[tauri::command]
+pub async fn get_live_event_data(
+    event_id: Option<i64>,
+) -> Result<lcu::types::LiveClientEvents, LcuError> {
+    service::get_event_data(http_client::get_lcu_client(), LIVE_CLIENT_URL, event_id).await
+}
+
+#[tauri::command]
+pub async fn get_live_game_stats() -> Result<lcu::types::LiveClientGameData, LcuError> {
+    service::get_game_stats(http_client::get_lcu_client(), LIVE_CLIENT_URL).await
+}
@@ -0,0 +1,166 @@
+//! 对局中轮询 Live Client Data API
+//!
+//! 只在 gameflow 阶段为 InProgress 时运行：每 POLL_INTERVAL 请求一次 allgamedata 并发送完整数据，
+//! 新出现的事件（EventID 大于已发送的事件）另外单独发送，计时器等只需要处理新事件。
+//! 对局加载期间接口不可用，请求失败时继续等待。
+use crate::lcu::live_client::service::get_all_game_data;
+use crate::lcu::types::{LiveClientAllGameData, LiveClientEvent};
+use reqwest::Client;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 同一时间只轮询一局
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 轮询得到的更新
+#[derive(Debug, Clone)]
+pub enum LiveClientUpdate {
+    /// 对局的全部数据
+    GameData(Box<LiveClientAllGameData>),
+    /// 上次轮询之后新出现的事件
+    Events(Vec<LiveClientEvent>),
+}
+
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// 轮询直到 in_progress 返回 false，emit 用于把更新发送给前端
+pub async fn run<A, Fut, F>(client: &Client, base_url: &str, in_progress: A, emit: F)
+where
+    A: Fn() -> Fut,
+    Fut: Future<Output = bool>,
+    F: Fn(LiveClientUpdate),
+{
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        log::debug!("[LiveClient] 已在轮询对局数据，跳过");
+        return;
+    }
+    let _guard = RunningGuard;
+
+    let mut next_event_id = 0;
+    let mut available = false;
+    while in_progress().await {
+        match get_all_game_data(client, base_url).await {
+            Ok(data) => {
+                if !available {
+                    log::info!("[LiveClient] 对局数据可用，开始轮询");
+                    available = true;
+                }
+                let events: Vec<LiveClientEvent> = data
+                    .events
+                    .events
+                    .iter()
+                    .filter(|e| e.event_id >= next_event_id)
+                    .cloned()
+                    .collect();
+                emit(LiveClientUpdate::GameData(Box::new(data)));
+                if let Some(last) = events.iter().map(|e| e.event_id).max() {
+                    next_event_id = last + 1;
+                    emit(LiveClientUpdate::Events(events));
+                }
+            }
+            Err(e) => log::debug!("[LiveClient] 对局数据暂不可用: {}", e),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    log::info!("[LiveClient] 已离开对局，停止轮询");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{fixtures, MockLcu, MockResponse};
+    use reqwest::Method;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn polls_game_data_and_emits_only_new_events() {
+        let mock = MockLcu::start().await;
+        // 第一次请求有 GameStart、MinionsSpawning 两个事件，之后多一次击杀
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        mock.handle(Method::GET, "/liveclientdata/allgamedata", move |_| {
+            let mut events = vec![
+                json!({ "EventID": 0, "EventName": "GameStart", "EventTime": 0.03 }),
+                json!({ "EventID": 1, "EventName": "MinionsSpawning", "EventTime": 65.0 }),
+            ];
+            if handler_calls.fetch_add(1, Ordering::SeqCst) > 0 {
+                events.push(json!({
+                    "EventID": 2,
+                    "EventName": "ChampionKill",
+                    "EventTime": 190.5,
+                    "KillerName": "Nidalee#CN1",
+                    "VictimName": "蓝色方四号#8888",
+                    "Assisters": ["Teammate#CN1"]
+                }));
+            }
+            MockResponse::json(json!({
+                "activePlayer": { "error": "Spectator mode doesn't currently support this feature" },
+                "allPlayers": fixtures::load(fixtures::LIVE_PLAYER_LIST),
+                "events": { "Events": events },
+                "gameData": {
+                    "gameMode": "CLASSIC",
+                    "gameTime": 200.0,
+                    "mapName": "Map11",
+                    "mapNumber": 11,
+                    "mapTerrain": "Default"
+                }
+            }))
+        });
+
+        let polls = AtomicUsize::new(0);
+        let updates = Mutex::new(Vec::new());
+        run(
+            &mock.client(),
+            &mock.endpoint.base_url,
+            || {
+                let n = polls.fetch_add(1, Ordering::SeqCst);
+                async move { n < 2 }
+            },
+            |update| updates.lock().unwrap().push(update),
+        )
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let updates = updates.into_inner().unwrap();
+        let game_data: Vec<_> = updates
+            .iter()
+            .filter_map(|u| match u {
+                LiveClientUpdate::GameData(data) => Some(data),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(game_data.len(), 2);
+        assert!(game_data[0].active_player.is_none());
+        assert_eq!(game_data[0].all_players.len(), 10);
+        assert_eq!(game_data[0].all_players[0].items[0].item_id, 1055);
+        assert_eq!(game_data[0].all_players[0].runes.keystone.id, 8010);
+
+        let events: Vec<Vec<i64>> = updates
+            .iter()
+            .filter_map(|u| match u {
+                LiveClientUpdate::Events(events) => {
+                    Some(events.iter().map(|e| e.event_id).collect())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(events, [vec![0, 1], vec![2]]);
+        let LiveClientUpdate::Events(kill) = &updates[3] else {
+            panic!("第二次轮询应发送新事件");
+        };
+        assert_eq!(kill[0].killer_name.as_deref(), Some("Nidalee#CN1"));
+        assert_eq!(kill[0].assisters, ["Teammate#CN1"]);
+    }
+}
@@ -0,0 +1,275 @@
+//! 游戏进程提供的 Live Client Data API（https://127.0.0.1:2999，无需认证）
+//!
+//! 对局加载完成后才可用，加载期间连接会被拒绝或返回 404。
+//! 排位选人时看不到对方的召唤师信息，进入对局后通过这里的玩家列表按名称查询双方全部玩家。
+use crate::lcu::champ_select::lookup;
+use crate::lcu::error::LcuError;
+use crate::lcu::request::lcu_get;
+use crate::lcu::types::{
+    LiveClientAbilities, LiveClientActivePlayer, LiveClientAllGameData, LiveClientEvents,
+    LiveClientFullRunes, LiveClientGameData, LiveClientItem, LiveClientPlayer, LiveClientScores,
+    LiveGamePlayer,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Instant;
+
+/// Live Client Data API 地址
+pub const LIVE_CLIENT_URL: &str = "https://127.0.0.1:2999";
+
+/// GET 请求 Live Client Data API，非 2xx 转换为 LcuError
+pub async fn live_get<T: DeserializeOwned>(
+    client: &Client,
+    base_url: &str,
+    path: &str,
+) -> Result<T, LcuError> {
+    let start = Instant::now();
+    let response = client
+        .get(format!("{}{}", base_url, path))
+        .send()
+        .await
+        .map_err(|e| LcuError::from_reqwest(e, path))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| LcuError::from_reqwest(e, path))?;
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    log::debug!(
+        "[LiveClient] GET {} -> {} (耗时: {}ms)",
+        path,
+        status,
+        elapsed_ms
+    );
+
+    if !status.is_success() {
+        return Err(LcuError::from_http_status(
+            status.as_u16(),
+            "GET",
+            path,
+            elapsed_ms,
+            &body,
+        ));
+    }
+    serde_json::from_str(&body).map_err(|e| LcuError::Decode {
+        path: path.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// 对局的全部数据（当前玩家、全部玩家、事件和对局信息）
+pub async fn get_all_game_data(
+    client: &Client,
+    base_url: &str,
+) -> Result<LiveClientAllGameData, LcuError> {
+    live_get(client, base_url, "/liveclientdata/allgamedata").await
+}
+
+/// 当前玩家（观战时不可用）
+pub async fn get_active_player(
+    client: &Client,
+    base_url: &str,
+) -> Result<LiveClientActivePlayer, LcuError> {
+    live_get(client, base_url, "/liveclientdata/activeplayer").await
+}
+
+/// 当前玩家的技能
+pub async fn get_active_player_abilities(
+    client: &Client,
+    base_url: &str,
+) -> Result<LiveClientAbilities, LcuError> {
+    live_get(client, base_url, "/liveclientdata/activeplayerabilities").await
+}
+
+/// 当前玩家的完整符文
+pub async fn get_active_player_runes(
+    client: &Client,
+    base_url: &str,
+) -> Result<LiveClientFullRunes, LcuError> {
+    live_get(client, base_url, "/liveclientdata/activeplayerrunes").await
+}
+
+/// 对局中的全部玩家
+pub async fn get_player_list(
+    client: &Client,
+    base_url: &str,
+) -> Result<Vec<LiveClientPlayer>, LcuError> {
+    live_get(client, base_url, "/liveclientdata/playerlist").await
+}
+
+/// 指定玩家（名称#标签）的击杀、死亡、助攻、补刀和视野得分
+pub async fn get_player_scores(
+    client: &Client,
+    base_url: &str,
+    riot_id: &str,
+) -> Result<LiveClientScores, LcuError> {
+    let riot_id = utf8_percent_encode(riot_id, NON_ALPHANUMERIC);
+    let path = format!("/liveclientdata/playerscores?riotId={}", riot_id);
+    live_get(client, base_url, &path).await
+}
+
+/// 指定玩家（名称#标签）的装备
+pub async fn get_player_items(
+    client: &Client,
+    base_url: &str,
+    riot_id: &str,
+) -> Result<Vec<LiveClientItem>, LcuError> {
+    let riot_id = utf8_percent_encode(riot_id, NON_ALPHANUMERIC);
+    let path = format!("/liveclientdata/playeritems?riotId={}", riot_id);
+    live_get(client, base_url, &path).await
+}
+
+/// 对局事件，指定 event_id 时只返回该事件之后（含）的事件
+pub async fn get_event_data(
+    client: &Client,
+    base_url: &str,
+    event_id: Option<i64>,
+) -> Result<LiveClientEvents, LcuError> {
+    let path = match event_id {
+        Some(id) => format!("/liveclientdata/eventdata?eventID={}", id),
+        None => "/liveclientdata/eventdata".to_string(),
+    };
+    live_get(client, base_url, &path).await
+}
+
+/// 对局模式、时间和地图
+pub async fn get_game_stats(
+    client: &Client,
+    base_url: &str,
+) -> Result<LiveClientGameData, LcuError> {
+    live_get(client, base_url, "/liveclientdata/gamestats").await
+}
+
+/// 玩家的名称#标签，旧版本游戏只有 summonerName
+fn riot_id(player: &LiveClientPlayer) -> Option<String> {
+    if let Some(riot_id) = player.riot_id.as_ref().filter(|id| id.contains('#')) {
+        return Some(riot_id.clone());
+    }
+    match (&player.riot_id_game_name, &player.riot_id_tag_line) {
+        (Some(name), Some(tag)) if !name.is_empty() && !tag.is_empty() => {
+            Some(format!("{}#{}", name, tag))
+        }
+        _ => Some(player.summoner_name.clone()).filter(|name| !name.is_empty()),
+    }
+}
+
+/// 对局中的全部玩家及其段位和最近战绩
+///
+/// 按名称查询 puuid（机器人除外），再查询最近 RECENT_MATCH_COUNT 场战绩，查询失败的玩家只保留对局数据。
+/// 己方按当前召唤师所在的队伍判断，找不到自己时（如观战）以 ORDER 为己方。
+pub async fn get_live_game_players(
+    client: &Client,
+    base_url: &str,
+) -> Result<Vec<LiveGamePlayer>, LcuError> {
+    let players = get_player_list(client, base_url).await?;
+    let names: Vec<String> = players
+        .iter()
+        .filter(|p| !p.is_bot)
+        .filter_map(riot_id)
+        .collect();
+    let summoners = lookup::ranked_summoners_by_names(client, &names).await;
+    let puuids: Vec<String> = summoners.values().map(|s| s.puuid.clone()).collect();
+    let mut matches = lookup::recent_matches_by_puuids(client, &puuids).await;
+
+    let me: Value = lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
+    let my_team = players
+        .iter()
+        .find(|p| {
+            riot_id(p)
+                .and_then(|name| summoners.get(&name))
+                .is_some_and(|s| me["puuid"].as_str() == Some(s.puuid.as_str()))
+        })
+        .map_or("ORDER", |p| p.team.as_str())
+        .to_string();
+
+    let result: Vec<LiveGamePlayer> = players
+        .into_iter()
+        .map(|player| {
+            let summoner = riot_id(&player)
+                .filter(|_| !player.is_bot)
+                .and_then(|name| summoners.get(&name).cloned());
+            let puuid = summoner.as_ref().map(|s| s.puuid.clone());
+            LiveGamePlayer {
+                ally: player.team == my_team,
+                recent_matches: puuid.as_ref().and_then(|puuid| matches.remove(puuid)),
+                puuid,
+                summoner,
+                player,
+            }
+        })
+        .collect();
+    log::info!(
+        "[LiveClient] 对局中 {} 名玩家，查询到 {} 名召唤师",
+        result.len(),
+        result.iter().filter(|p| p.summoner.is_some()).count()
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{fixtures, MockLcu, MockResponse};
+    use reqwest::Method;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn live_players_are_resolved_and_enriched() {
+        let mock = MockLcu::start().await;
+        lookup::clear();
+        mock.fixture(
+            Method::GET,
+            "/liveclientdata/playerlist",
+            fixtures::LIVE_PLAYER_LIST,
+        );
+        // 按名称查询：Nidalee#CN1 为自己，其余玩家按名称分配 puuid
+        mock.handle(Method::GET, "/lol-summoner/v1/summoners", |req| {
+            let name = req.path.split("name=").nth(1).unwrap_or_default();
+            let name = percent_encoding::percent_decode_str(name).decode_utf8_lossy();
+            let mut summoner = fixtures::load(fixtures::SUMMONER);
+            let (game_name, tag_line) = name.split_once('#').unwrap_or_default();
+            summoner["gameName"] = json!(game_name);
+            summoner["tagLine"] = json!(tag_line);
+            summoner["puuid"] = if game_name == "Nidalee" {
+                json!("a1b2c3d4-0000-4000-8000-000000000001")
+            } else {
+                json!(format!("puuid-{}", game_name))
+            };
+            MockResponse::json(summoner)
+        });
+
+        let players = get_live_game_players(&mock.client(), &mock.endpoint.base_url)
+            .await
+            .unwrap();
+
+        assert_eq!(players.len(), 10);
+        // 自己在 CHAOS 方
+        assert_eq!(players.iter().filter(|p| p.ally).count(), 5);
+        assert!(players.iter().all(|p| p.ally == (p.player.team == "CHAOS")));
+
+        let enemy = players
+            .iter()
+            .find(|p| p.player.riot_id.as_deref() == Some("蓝色方一号#8888"))
+            .unwrap();
+        assert_eq!(enemy.puuid.as_deref(), Some("puuid-蓝色方一号"));
+        let summoner = enemy.summoner.as_ref().unwrap();
+        assert_eq!(summoner.display_name, "蓝色方一号#8888");
+        assert_eq!(summoner.solo_rank_tier.as_deref(), Some("PLATINUM"));
+        assert!(enemy.recent_matches.is_some());
+        let me = players
+            .iter()
+            .find(|p| p.puuid.as_deref() == Some("a1b2c3d4-0000-4000-8000-000000000001"))
+            .unwrap();
+        assert!(me.ally);
+        assert_eq!(me.recent_matches.as_ref().unwrap().total_games, 3);
+
+        // 机器人不查询
+        let bot = players.iter().find(|p| p.player.is_bot).unwrap();
+        assert!(bot.summoner.is_none() && bot.recent_matches.is_none());
+        let lookups = mock.requests_to(Method::GET, "/lol-summoner/v1/summoners?name=");
+        assert_eq!(lookups.len(), 9);
+        lookup::clear();
+    }
+}
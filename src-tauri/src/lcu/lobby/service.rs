@@ -1,9 +1,10 @@
 
+use crate::lcu::error::LcuError;
 use crate::lcu::request::lcu_get;
 use crate::lcu::types::LobbyInfo;
 use reqwest::Client;
 
 /// 获取当前 Lobby 信息（自动带全局认证、日志、耗时）
-pub async fn get_lobby_info(client: &Client) -> Result<LobbyInfo, String> {
+pub async fn get_lobby_info(client: &Client) -> Result<LobbyInfo, LcuError> {
     lcu_get(client, "/lol-lobby/v2/lobby").await
 }
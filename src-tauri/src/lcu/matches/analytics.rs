@@ -0,0 +1,412 @@
+//! 战绩分析：按英雄、分路汇总以及最近对局的趋势
+//!
+//! 数据来自本地战绩数据库。战绩列表中的对局只包含被查询玩家自己的数据，伤害占比和参团率
+//! 需要队友的数据，所以分析前会为缺少队友数据的最近几场对局补充查询对局详情。
+use crate::lcu::champ_select::lookup::fetch_concurrently;
+use crate::lcu::error::LcuError;
+use crate::lcu::matches::service::{player_participant, query_match_history};
+use crate::lcu::matches::store::{self, int};
+use crate::lcu::request::lcu_get;
+use crate::lcu::types::{
+    AggregateStats, GameLengthWinRate, MatchAnalytics, MatchHistoryQuery, TrendPoint,
+};
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 对局时长分段（分钟），最后一段不设上限
+const GAME_LENGTH_BUCKETS: [(i32, Option<i32>); 3] = [(0, Some(25)), (25, Some(35)), (35, None)];
+/// 每次分析最多补充查询的对局详情数量
+const MAX_DETAIL_FETCHES: usize = 20;
+
+/// 玩家在一场对局中的数据
+#[derive(Debug, Clone, Default)]
+struct GameSample {
+    game_id: u64,
+    game_creation: i64,
+    minutes: f32,
+    champion_id: i32,
+    position: String,
+    win: bool,
+    kills: i64,
+    deaths: i64,
+    assists: i64,
+    cs: i64,
+    gold: i64,
+    vision: i64,
+    damage: i64,
+    /// 队伍的总伤害和总击杀，只有对局中包含队友数据时才有
+    team_damage: Option<i64>,
+    team_kills: Option<i64>,
+    first_blood: bool,
+    double_kills: i64,
+    triple_kills: i64,
+    quadra_kills: i64,
+    penta_kills: i64,
+}
+
+/// 将 timeline 中的 lane/role 转换为分路
+pub fn position_of(participant: &Value) -> String {
+    let timeline = &participant["timeline"];
+    let role = timeline["role"].as_str().unwrap_or_default();
+    match timeline["lane"].as_str().unwrap_or_default() {
+        "TOP" => "TOP",
+        "JUNGLE" => "JUNGLE",
+        "MIDDLE" | "MID" => "MIDDLE",
+        "BOTTOM" | "BOT" if role == "DUO_SUPPORT" => "UTILITY",
+        "BOTTOM" | "BOT" => "BOTTOM",
+        _ => "NONE",
+    }
+    .to_string()
+}
+
+fn sample(game: &Value, puuid: &str) -> Option<GameSample> {
+    let participant = player_participant(game, puuid)?;
+    let participants = game["participants"].as_array()?;
+    let stats = &participant["stats"];
+    let team_id = int(participant, "teamId");
+    let team: Vec<&Value> = participants
+        .iter()
+        .filter(|p| int(p, "teamId") == team_id)
+        .collect();
+    let has_team = team.len() > 1;
+    let team_total = |key: &str| has_team.then(|| team.iter().map(|p| int(&p["stats"], key)).sum());
+
+    Some(GameSample {
+        game_id: game["gameId"].as_u64().unwrap_or(0),
+        game_creation: int(game, "gameCreation"),
+        minutes: int(game, "gameDuration") as f32 / 60.0,
+        champion_id: int(participant, "championId") as i32,
+        position: position_of(participant),
+        win: stats["win"].as_bool().unwrap_or(false),
+        kills: int(stats, "kills"),
+        deaths: int(stats, "deaths"),
+        assists: int(stats, "assists"),
+        cs: int(stats, "totalMinionsKilled") + int(stats, "neutralMinionsKilled"),
+        gold: int(stats, "goldEarned"),
+        vision: int(stats, "visionScore"),
+        damage: int(stats, "totalDamageDealtToChampions"),
+        team_damage: team_total("totalDamageDealtToChampions"),
+        team_kills: team_total("kills"),
+        first_blood: stats["firstBloodKill"].as_bool().unwrap_or(false),
+        double_kills: int(stats, "doubleKills"),
+        triple_kills: int(stats, "tripleKills"),
+        quadra_kills: int(stats, "quadraKills"),
+        penta_kills: int(stats, "pentaKills"),
+    })
+}
+
+fn percent(part: f32, total: f32) -> f32 {
+    if total > 0.0 {
+        part / total * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn per_min(total: i64, minutes: f32) -> f32 {
+    if minutes > 0.0 {
+        total as f32 / minutes
+    } else {
+        0.0
+    }
+}
+
+fn kda(kills: i64, deaths: i64, assists: i64) -> f32 {
+    (kills + assists) as f32 / deaths.max(1) as f32
+}
+
+#[derive(Default)]
+struct Accumulator {
+    samples: Vec<GameSample>,
+}
+
+impl Accumulator {
+    fn finish(self, champion_id: Option<i32>, position: Option<String>) -> AggregateStats {
+        let samples = self.samples;
+        let games = samples.len() as i32;
+        let wins = samples.iter().filter(|s| s.win).count() as i32;
+        let sum = |f: fn(&GameSample) -> i64| samples.iter().map(f).sum::<i64>();
+        let (kills, deaths, assists) = (sum(|s| s.kills), sum(|s| s.deaths), sum(|s| s.assists));
+        let minutes: f32 = samples.iter().map(|s| s.minutes).sum();
+        let average = |f: fn(&GameSample) -> Option<f32>| {
+            let values: Vec<f32> = samples.iter().filter_map(f).collect();
+            (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+        };
+
+        let by_game_length = GAME_LENGTH_BUCKETS
+            .iter()
+            .map(|&(min_minutes, max_minutes)| {
+                let bucket: Vec<&GameSample> = samples
+                    .iter()
+                    .filter(|s| {
+                        s.minutes >= min_minutes as f32
+                            && !matches!(max_minutes, Some(max) if s.minutes >= max as f32)
+                    })
+                    .collect();
+                let wins = bucket.iter().filter(|s| s.win).count() as i32;
+                GameLengthWinRate {
+                    min_minutes,
+                    max_minutes,
+                    games: bucket.len() as i32,
+                    wins,
+                    win_rate: percent(wins as f32, bucket.len() as f32),
+                }
+            })
+            .collect();
+
+        AggregateStats {
+            champion_id,
+            position,
+            games,
+            wins,
+            win_rate: percent(wins as f32, games as f32),
+            avg_kills: kills as f32 / games.max(1) as f32,
+            avg_deaths: deaths as f32 / games.max(1) as f32,
+            avg_assists: assists as f32 / games.max(1) as f32,
+            kda: kda(kills, deaths, assists),
+            cs_per_min: per_min(sum(|s| s.cs), minutes),
+            gold_per_min: per_min(sum(|s| s.gold), minutes),
+            vision_per_min: per_min(sum(|s| s.vision), minutes),
+            damage_share: average(|s| {
+                s.team_damage
+                    .map(|team| percent(s.damage as f32, team as f32))
+            }),
+            kill_participation: average(|s| {
+                s.team_kills
+                    .map(|team| percent((s.kills + s.assists) as f32, team as f32))
+            }),
+            avg_game_duration: minutes * 60.0 / games.max(1) as f32,
+            first_bloods: samples.iter().filter(|s| s.first_blood).count() as i32,
+            double_kills: sum(|s| s.double_kills) as i32,
+            triple_kills: sum(|s| s.triple_kills) as i32,
+            quadra_kills: sum(|s| s.quadra_kills) as i32,
+            penta_kills: sum(|s| s.penta_kills) as i32,
+            by_game_length,
+        }
+    }
+}
+
+/// 分析玩家的对局，trend_window 为趋势中滚动平均的场次
+pub fn analyze_games(games: &[Value], puuid: &str, trend_window: usize) -> MatchAnalytics {
+    let mut samples: Vec<GameSample> = games.iter().filter_map(|g| sample(g, puuid)).collect();
+    samples.sort_by_key(|s| s.game_creation);
+
+    let mut by_champion: HashMap<i32, Accumulator> = HashMap::new();
+    let mut by_position: HashMap<String, Accumulator> = HashMap::new();
+    for s in &samples {
+        by_champion
+            .entry(s.champion_id)
+            .or_default()
+            .samples
+            .push(s.clone());
+        by_position
+            .entry(s.position.clone())
+            .or_default()
+            .samples
+            .push(s.clone());
+    }
+    let mut by_champion: Vec<AggregateStats> = by_champion
+        .into_iter()
+        .map(|(id, acc)| acc.finish(Some(id), None))
+        .collect();
+    by_champion.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then(a.champion_id.cmp(&b.champion_id))
+    });
+    let mut by_position: Vec<AggregateStats> = by_position
+        .into_iter()
+        .map(|(position, acc)| acc.finish(None, Some(position)))
+        .collect();
+    by_position.sort_by(|a, b| b.games.cmp(&a.games).then(a.position.cmp(&b.position)));
+
+    let window = trend_window.max(1);
+    let trend = samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let recent = &samples[(i + 1).saturating_sub(window)..=i];
+            let wins = recent.iter().filter(|r| r.win).count();
+            TrendPoint {
+                game_id: s.game_id,
+                game_creation: s.game_creation,
+                champion_id: s.champion_id,
+                win: s.win,
+                kda: kda(s.kills, s.deaths, s.assists),
+                cs_per_min: per_min(s.cs, s.minutes),
+                gold_per_min: per_min(s.gold, s.minutes),
+                rolling_win_rate: percent(wins as f32, recent.len() as f32),
+                rolling_kda: kda(
+                    recent.iter().map(|r| r.kills).sum(),
+                    recent.iter().map(|r| r.deaths).sum(),
+                    recent.iter().map(|r| r.assists).sum(),
+                ),
+            }
+        })
+        .collect();
+
+    MatchAnalytics {
+        puuid: puuid.to_string(),
+        overall: Accumulator { samples }.finish(None, None),
+        by_champion,
+        by_position,
+        trend,
+    }
+}
+
+/// 先从 LCU 更新最近的战绩和缺少队友数据的对局详情，再用本地战绩数据库中最近 count 场对局分析
+pub async fn get_match_analytics(
+    client: &Client,
+    puuid: &str,
+    count: usize,
+    trend_window: usize,
+) -> Result<MatchAnalytics, LcuError> {
+    let query = MatchHistoryQuery {
+        count,
+        ..Default::default()
+    };
+    if let Err(e) = query_match_history(client, puuid, &query).await {
+        log::warn!("[战绩分析] 更新战绩失败，使用本地数据: {}", e);
+    }
+
+    let games = store::games_for_puuid(puuid, count).await?;
+    let missing: Vec<u64> = games
+        .iter()
+        .filter(|game| sample(game, puuid).is_some_and(|s| s.team_kills.is_none()))
+        .filter_map(|game| game["gameId"].as_u64())
+        .take(MAX_DETAIL_FETCHES)
+        .collect();
+    if missing.is_empty() {
+        return Ok(analyze_games(&games, puuid, trend_window));
+    }
+
+    log::info!("[战绩分析] 补充查询 {} 场对局详情", missing.len());
+    let results = fetch_concurrently(missing, |game_id| async move {
+        lcu_get::<Value>(client, &format!("/lol-match-history/v1/games/{}", game_id)).await
+    })
+    .await;
+    let mut details = Vec::new();
+    for (game_id, result) in results {
+        match result {
+            Ok(game) => details.push(game),
+            Err(e) => log::warn!("[战绩分析] 获取对局 {} 详情失败: {}", game_id, e),
+        }
+    }
+    store::ingest_games(details).await;
+    let games = store::games_for_puuid(puuid, count).await?;
+    Ok(analyze_games(&games, puuid, trend_window))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn participant(id: i64, team: i64, champion: i64, lane: &str, stats: Value) -> Value {
+        json!({
+            "participantId": id,
+            "teamId": team,
+            "championId": champion,
+            "timeline": { "lane": lane, "role": "SOLO" },
+            "stats": stats,
+        })
+    }
+
+    fn game(game_id: i64, minutes: i64, participants: Vec<Value>) -> Value {
+        json!({
+            "gameId": game_id,
+            "gameCreation": 1_730_000_000_000i64 + game_id,
+            "gameDuration": minutes * 60,
+            "participantIdentities": [{ "participantId": 1, "player": { "puuid": "me" } }],
+            "participants": participants,
+        })
+    }
+
+    #[test]
+    fn aggregates_per_champion_position_and_trend() {
+        let games = vec![
+            // 只有自己的数据
+            game(
+                1,
+                20,
+                vec![participant(
+                    1,
+                    100,
+                    76,
+                    "JUNGLE",
+                    json!({ "win": true, "kills": 6, "deaths": 2, "assists": 4,
+                            "totalMinionsKilled": 40, "neutralMinionsKilled": 120,
+                            "goldEarned": 10000, "visionScore": 20, "firstBloodKill": true,
+                            "doubleKills": 2, "totalDamageDealtToChampions": 12000 }),
+                )],
+            ),
+            // 有队友数据：伤害占比 25%，参团率 (4+6)/20 = 50%
+            game(
+                2,
+                40,
+                vec![
+                    participant(
+                        1,
+                        100,
+                        76,
+                        "JUNGLE",
+                        json!({ "win": false, "kills": 4, "deaths": 6, "assists": 6,
+                                "totalMinionsKilled": 60, "neutralMinionsKilled": 180,
+                                "goldEarned": 14000, "visionScore": 40, "firstBloodAssist": true,
+                                "totalDamageDealtToChampions": 10000 }),
+                    ),
+                    participant(
+                        2,
+                        100,
+                        103,
+                        "MIDDLE",
+                        json!({ "kills": 16, "totalDamageDealtToChampions": 30000 }),
+                    ),
+                    participant(3, 200, 64, "JUNGLE", json!({ "kills": 30 })),
+                ],
+            ),
+            game(
+                3,
+                30,
+                vec![participant(
+                    1,
+                    100,
+                    103,
+                    "MIDDLE",
+                    json!({ "win": true, "kills": 10, "deaths": 0, "assists": 5,
+                            "totalMinionsKilled": 270, "goldEarned": 15000,
+                            "visionScore": 15, "pentaKills": 1 }),
+                )],
+            ),
+        ];
+
+        let analytics = analyze_games(&games, "me", 2);
+
+        let overall = &analytics.overall;
+        assert_eq!((overall.games, overall.wins), (3, 2));
+        assert_eq!(overall.first_bloods, 1);
+        assert_eq!((overall.double_kills, overall.penta_kills), (2, 1));
+        assert_eq!(overall.avg_game_duration, 1800.0);
+        assert_eq!(overall.damage_share, Some(25.0));
+        assert_eq!(overall.kill_participation, Some(50.0));
+        let buckets: Vec<_> = overall.by_game_length.iter().map(|b| b.games).collect();
+        assert_eq!(buckets, [1, 1, 1]);
+
+        let nidalee = &analytics.by_champion[0];
+        assert_eq!((nidalee.champion_id, nidalee.games), (Some(76), 2));
+        // (160 + 240) / 60 分钟
+        assert!((nidalee.cs_per_min - 400.0 / 60.0).abs() < 1e-4);
+        assert_eq!(nidalee.gold_per_min, 400.0);
+        assert_eq!(analytics.by_position[0].position.as_deref(), Some("JUNGLE"));
+        assert_eq!(analytics.by_position[1].win_rate, 100.0);
+
+        let rolling: Vec<_> = analytics
+            .trend
+            .iter()
+            .map(|t| (t.game_id, t.rolling_win_rate))
+            .collect();
+        assert_eq!(rolling, [(1, 100.0), (2, 50.0), (3, 50.0)]);
+        assert_eq!(analytics.trend[2].rolling_kda, 25.0 / 6.0);
+    }
+}
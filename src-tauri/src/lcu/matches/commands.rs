@@ -1,17 +1,76 @@
 use crate::{http_client, lcu};
+use crate::lcu::error::LcuError;
 
 #[tauri::command]
-pub async fn get_match_history(count: Option<u32>) -> Result<lcu::types::MatchStatistics, String> {
+pub async fn get_match_history(count: Option<u32>) -> Result<lcu::types::MatchStatistics, LcuError> {
     let client = http_client::get_lcu_client();
     let end_count: usize = count.unwrap_or(20) as usize;
-    println!("🔢 接收到的count参数: {:?}, 转换后的end_count: {}", count, end_count);
+    log::debug!("[我的战绩] count 参数: {:?}，查询 {} 场", count, end_count);
     lcu::matches::service::get_match_history(client, end_count).await
 }
 
 #[tauri::command]
-pub async fn get_game_detail(game_id: u64) -> Result<lcu::types::GameDetail, String> {
+pub async fn get_game_detail(game_id: u64) -> Result<lcu::types::GameDetail, LcuError> {
     log::info!("🔍 ===== 获取游戏详细信息 =====");
     log::info!("🎮 游戏ID: {}", game_id);
     let client = http_client::get_lcu_client();
     lcu::matches::service::get_game_detail_logic(&client, game_id).await
 }
+
+/// puuid 为空时使用当前召唤师
+async fn puuid_or_current(puuid: Option<String>) -> Result<String, LcuError> {
+    if let Some(puuid) = puuid {
+        return Ok(puuid);
+    }
+    let client = http_client::get_lcu_client();
+    let summoner: serde_json::Value =
+        lcu::request::lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
+    Ok(summoner["puuid"]
+        .as_str()
+        .ok_or_else(|| "未找到PUUID".to_string())?
+        .to_string())
+}
+
+/// 分页、按条件查询战绩，puuid 为空时查询当前召唤师
+#[tauri::command]
+pub async fn query_match_history(
+    puuid: Option<String>,
+    query: lcu::types::MatchHistoryQuery,
+) -> Result<lcu::types::MatchHistoryPage, LcuError> {
+    let puuid = puuid_or_current(puuid).await?;
+    let client = http_client::get_lcu_client();
+    lcu::matches::service::query_match_history(client, &puuid, &query).await
+}
+
+/// 从本地战绩数据库统计玩家的对局，puuid 为空时使用当前召唤师
+#[tauri::command]
+pub async fn get_stored_match_history(
+    puuid: Option<String>,
+    count: Option<u32>,
+) -> Result<lcu::types::MatchStatistics, LcuError> {
+    let puuid = puuid_or_current(puuid).await?;
+    lcu::matches::service::get_stored_match_statistics(&puuid, count.unwrap_or(20) as usize).await
+}
+
+#[tauri::command]
+pub async fn get_match_store_summary() -> Result<lcu::types::MatchStoreSummary, LcuError> {
+    lcu::matches::store::summary().await.map_err(LcuError::from)
+}
+
+/// 按英雄、分路汇总最近 count 场对局，并给出最近 trend_window 场的滚动趋势
+#[tauri::command]
+pub async fn get_match_analytics(
+    puuid: Option<String>,
+    count: Option<u32>,
+    trend_window: Option<u32>,
+) -> Result<lcu::types::MatchAnalytics, LcuError> {
+    let puuid = puuid_or_current(puuid).await?;
+    let client = http_client::get_lcu_client();
+    lcu::matches::analytics::get_match_analytics(
+        client,
+        &puuid,
+        count.unwrap_or(50) as usize,
+        trend_window.unwrap_or(5) as usize,
+    )
+    .await
+}
@@ -1,2 +1,4 @@
+pub mod analytics;
 pub mod commands;
 pub mod service;
+pub mod store;
@@ -1,7 +1,9 @@
+use crate::lcu::error::LcuError;
+use crate::lcu::matches::store;
 use crate::lcu::request::{lcu_get, lcu_request_json};
 use crate::lcu::types::{
-    ChampionStats, GameDetail, MatchStatistics, ParticipantInfo, ParticipantStats, RecentGame,
-    TeamInfo, TeamStats,
+    ChampionStats, GameDetail, MatchHistoryPage, MatchHistoryQuery, MatchStatistics,
+    ParticipantInfo, ParticipantStats, RecentGame, TeamInfo, TeamStats,
 };
 use reqwest::{Client, Method};
 use serde::Deserialize;
@@ -11,6 +13,11 @@ use std::collections::HashMap;
 // 以下内容为原 match_history.rs 全部内容，粘贴至此
 // 其余内容保持不变，全部迁移
 
+/// 每次向 LCU 请求的对局数量
+const MATCH_CHUNK_SIZE: usize = 20;
+/// 单次查询最多查找的对局数量，超出后返回游标由前端继续查询
+const MAX_SCANNED_GAMES: usize = 200;
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct ApiGameData {
@@ -93,52 +100,46 @@ struct ApiPlayer {
 }
 
 /// 获取当前玩家历史战绩统计（自动认证、统一请求、日志耗时）
-pub async fn get_match_history(client: &Client, end_count: usize) -> Result<MatchStatistics, String> {
-    println!("\n🔍 ===== 开始获取我的战绩 =====");
-
+pub async fn get_match_history(client: &Client, end_count: usize) -> Result<MatchStatistics, LcuError> {
     // 第1步：获取当前召唤师信息来得到PUUID
-    println!("\n📍 第1步：获取当前召唤师信息");
     let summoner_data: Value = lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
     let puuid = summoner_data
         .get("puuid")
         .and_then(|p| p.as_str())
         .ok_or_else(|| "未找到PUUID".to_string())?;
-    println!("🆔 提取到的PUUID: {}", puuid);
-
-    // 第2步：使用PUUID获取对局列表
-    println!("\n📍 第2步：使用PUUID获取对局列表");
-    let safe_end = if end_count == 0 { 20 } else { end_count.min(100) };
-    // LCU API 的 endIndex 是包含的，所以需要减1
-    let actual_end_index = if safe_end > 0 { safe_end - 1 } else { 0 };
-    println!("🔢 请求的对局数量: end_count={}, safe_end={}, actual_end_index={}", end_count, safe_end, actual_end_index);
-    let match_list_url = format!(
-        "/lol-match-history/v1/products/lol/{}/matches?begIndex=0&endIndex={}",
-        puuid, actual_end_index
+    log::debug!("[我的战绩] PUUID: {}，请求 {} 场", puuid, end_count);
+
+    // 第2步：使用PUUID查询对局列表并分析
+    let query = MatchHistoryQuery {
+        count: end_count,
+        ..Default::default()
+    };
+    let statistics = query_match_history(client, puuid, &query).await?.statistics;
+
+    log::debug!(
+        "[我的战绩] 总对局: {}，胜/负: {}/{}，胜率: {:.1}%，平均KDA: {:.2}，最近对局: {}",
+        statistics.total_games,
+        statistics.wins,
+        statistics.losses,
+        statistics.win_rate,
+        statistics.avg_kda,
+        statistics.recent_performance.len()
     );
-    println!("🌐 请求URL: {}", match_list_url);
-    let match_list_data: Value = lcu_get(client, &match_list_url).await?;
-
-    // 第3步：直接分析对局列表数据
-    println!("\n📍 第3步：分析对局列表数据");
-    let statistics = analyze_match_list_data(match_list_data, puuid)?;
-
-    println!("\n✅ ===== 我的战绩查询完成 =====");
-    println!("📊 最终统计结果:");
-    println!("   - 总对局: {}", statistics.total_games);
-    println!("   - 胜场: {}", statistics.wins);
-    println!("   - 负场: {}", statistics.losses);
-    println!("   - 胜率: {:.1}%", statistics.win_rate);
-    println!("   - 平均KDA: {:.2}", statistics.avg_kda);
-    println!("   - 最近对局数: {}", statistics.recent_performance.len());
 
     Ok(statistics)
 }
 
-pub async fn get_game_detail_logic(client: &Client, game_id: u64) -> Result<GameDetail, String> {
+pub async fn get_game_detail_logic(client: &Client, game_id: u64) -> Result<GameDetail, LcuError> {
     let path = format!("/lol-match-history/v1/games/{}", game_id);
-    let api_game_data: ApiGameData = lcu_request_json(client, Method::GET, &path, None)
+    let game_data: Value = lcu_request_json(client, Method::GET, &path, None)
         .await
-        .map_err(|e| format!("获取游戏详细信息失败: {}", e))?;
+        .inspect_err(|e| log::error!("获取游戏详细信息失败: {}", e))?;
+    store::ingest_games(vec![game_data.clone()]).await;
+    let api_game_data: ApiGameData =
+        serde_json::from_value(game_data).map_err(|e| LcuError::Decode {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
 
     let mut blue_team_stats = TeamStats::default();
     let mut red_team_stats = TeamStats::default();
@@ -261,21 +262,278 @@ pub async fn get_recent_matches_by_puuid(
     client: &Client,
     puuid: &str,
     count: usize,
-) -> Result<MatchStatistics, String> {
+) -> Result<MatchStatistics, LcuError> {
+    let query = MatchHistoryQuery {
+        count,
+        ..Default::default()
+    };
+    Ok(query_match_history(client, puuid, &query).await?.statistics)
+}
+
+/// 玩家在对局中的参与者数据
+pub fn player_participant<'a>(game: &'a Value, puuid: &str) -> Option<&'a Value> {
+    let identity = game["participantIdentities"]
+        .as_array()?
+        .iter()
+        .find(|identity| identity["player"]["puuid"].as_str() == Some(puuid))?;
+    game["participants"]
+        .as_array()?
+        .iter()
+        .find(|p| p["participantId"] == identity["participantId"])
+}
+
+/// 对局是否符合队列、英雄、时间和胜负条件
+fn game_matches(game: &Value, puuid: &str, query: &MatchHistoryQuery) -> bool {
+    let queue_id = game["queueId"].as_i64().unwrap_or(0);
+    if !query.queue_ids.is_empty() && !query.queue_ids.iter().any(|&q| q as i64 == queue_id) {
+        return false;
+    }
+    let created = game["gameCreation"].as_i64().unwrap_or(0);
+    if query.start_time.is_some_and(|t| created < t) || query.end_time.is_some_and(|t| created > t)
+    {
+        return false;
+    }
+    let Some(participant) = player_participant(game, puuid) else {
+        return false;
+    };
+    if let Some(champion_id) = query.champion_id {
+        if participant["championId"].as_i64() != Some(champion_id as i64) {
+            return false;
+        }
+    }
+    if let Some(win) = query.win {
+        if participant["stats"]["win"].as_bool().unwrap_or(false) != win {
+            return false;
+        }
+    }
+    true
+}
+
+/// 按条件逐场筛选玩家战绩列表中的对局（本地数据库和 LCU 共用），index 为下一场对局的位置
+struct Scan<'a> {
+    puuid: &'a str,
+    query: &'a MatchHistoryQuery,
+    count: usize,
+    requested_end: usize,
+    scan_end: usize,
+    index: usize,
+    selected: Vec<Value>,
+    next_index: Option<usize>,
+    finished: bool,
+}
+
+impl<'a> Scan<'a> {
+    fn new(puuid: &'a str, query: &'a MatchHistoryQuery) -> Self {
+        let requested_end = query.end_index.unwrap_or(usize::MAX);
+        Self {
+            puuid,
+            query,
+            count: if query.count == 0 { 20 } else { query.count },
+            requested_end,
+            scan_end: requested_end.min(query.beg_index.saturating_add(MAX_SCANNED_GAMES)),
+            index: query.beg_index,
+            selected: Vec::new(),
+            next_index: None,
+            finished: false,
+        }
+    }
+
+    /// 处理位于 index 的对局，选够 count 场或出现早于 start_time 的对局时结束
+    fn push(&mut self, game: Value) {
+        self.index += 1;
+        let created = game["gameCreation"].as_i64().unwrap_or(0);
+        if self.query.start_time.is_some_and(|t| created < t) {
+            self.finished = true;
+            return;
+        }
+        if game_matches(&game, self.puuid, self.query) {
+            self.selected.push(game);
+            if self.selected.len() >= self.count {
+                self.next_index = Some(self.index);
+                self.finished = true;
+            }
+        }
+    }
+
+    /// 是否已结束，达到单次查找上限但还没到 end_index 时返回游标
+    fn done(&mut self) -> bool {
+        if !self.finished && self.index >= self.scan_end {
+            if self.scan_end < self.requested_end {
+                self.next_index = Some(self.index);
+            }
+            self.finished = true;
+        }
+        self.finished
+    }
+}
+
+/// 玩家战绩列表中最新一场对局的 id
+async fn newest_game_id(client: &Client, puuid: &str) -> Result<Option<i64>, LcuError> {
+    let url = format!(
+        "/lol-match-history/v1/products/lol/{}/matches?begIndex=0&endIndex=0",
+        puuid
+    );
+    let match_list: Value = lcu_get(client, &url).await?;
+    Ok(match_list["games"]["games"][0]["gameId"].as_i64())
+}
+
+/// 从本地数据库读取已同步的对局，同步范围内无法完成查询时返回 false，由 LCU 从 scan.index 继续
+async fn scan_store(scan: &mut Scan<'_>, history: &store::SyncedHistory) -> bool {
+    let limit = history.game_count.min(scan.scan_end);
+    if scan.index < limit {
+        let games = match store::games_for_puuid(scan.puuid, limit).await {
+            Ok(games) if games.len() >= limit => games,
+            Ok(_) => return false,
+            Err(e) => {
+                log::warn!("[战绩查询] 读取本地战绩失败: {}", e);
+                return false;
+            }
+        };
+        for game in games.into_iter().skip(scan.index) {
+            scan.push(game);
+            if scan.finished {
+                return true;
+            }
+        }
+    }
+    if history.complete && scan.index >= history.game_count {
+        scan.finished = true;
+    }
+    scan.done()
+}
+
+/// 查询战绩列表中 [begin, end) 的对局并保存到本地数据库
+async fn fetch_match_chunk(
+    client: &Client,
+    puuid: &str,
+    begin: usize,
+    end: usize,
+) -> Result<Vec<Value>, LcuError> {
+    // LCU API 的 endIndex 是包含的
     let url = format!(
-        "/lol-match-history/v1/products/lol/{}/matches?begIndex=0&endIndex={}",
-        puuid, count
+        "/lol-match-history/v1/products/lol/{}/matches?begIndex={}&endIndex={}",
+        puuid,
+        begin,
+        end - 1
     );
     let match_list_data: Value = lcu_get(client, &url).await?;
-    log::info!("match_list_data (查询到的战绩): {:#}", match_list_data); // 第3步：直接分析对局列表数据
-    let statistics = analyze_match_list_data(match_list_data, puuid)?;
-    Ok(statistics)
+    store::ingest_match_list(&match_list_data).await;
+    let games = match_list_data["games"]["games"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    log::debug!(
+        "[战绩查询] {} 第 {}-{} 场，返回 {} 场",
+        puuid,
+        begin,
+        end - 1,
+        games.len()
+    );
+    Ok(games)
+}
+
+/// 分批向 LCU 查询战绩列表，返回查询后已同步的范围
+///
+/// 只有从第一场开始或紧接着已同步范围的查询才能延长同步范围，其余查询不记录
+async fn scan_lcu(
+    client: &Client,
+    scan: &mut Scan<'_>,
+    history: Option<store::SyncedHistory>,
+) -> Result<Option<store::SyncedHistory>, LcuError> {
+    let mut synced = history.filter(|h| h.game_count >= scan.index);
+    while !scan.done() {
+        let begin = scan.index;
+        let chunk_end = (begin + MATCH_CHUNK_SIZE).min(scan.scan_end);
+        let games = fetch_match_chunk(client, scan.puuid, begin, chunk_end).await?;
+        let exhausted = games.len() < chunk_end - begin;
+        if begin == 0 {
+            synced = games
+                .first()
+                .and_then(|game| game["gameId"].as_i64())
+                .map(|newest_game_id| store::SyncedHistory {
+                    newest_game_id,
+                    game_count: 0,
+                    complete: false,
+                });
+        }
+        if let Some(synced) = synced.as_mut() {
+            synced.game_count = synced.game_count.max(begin + games.len());
+            synced.complete = exhausted;
+        }
+        for game in games.into_iter().take(chunk_end - begin) {
+            scan.push(game);
+            if scan.finished {
+                break;
+            }
+        }
+        if exhausted {
+            break;
+        }
+    }
+    Ok(synced)
+}
+
+/// 分批查询玩家的战绩列表，按条件筛选出 count 场对局并统计
+///
+/// LCU 的战绩按时间从新到旧排列，早于 start_time 的对局出现后停止查找。
+/// next_index 指向最后一场选中对局的下一场，用于继续查询下一页。
+/// 本地数据库已同步到 LCU 最新一场对局时先读取本地数据，只向 LCU 查询缺少的部分。
+pub async fn query_match_history(
+    client: &Client,
+    puuid: &str,
+    query: &MatchHistoryQuery,
+) -> Result<MatchHistoryPage, LcuError> {
+    let mut scan = Scan::new(puuid, query);
+    let history = match store::synced_history(puuid).await {
+        Some(history) if newest_game_id(client, puuid).await? == Some(history.newest_game_id) => {
+            Some(history)
+        }
+        _ => None,
+    };
+    let from_store = match &history {
+        Some(history) => scan_store(&mut scan, history).await,
+        None => false,
+    };
+    let stored = scan.index - query.beg_index;
+    if !from_store {
+        let synced = scan_lcu(client, &mut scan, history.clone()).await?;
+        if let Some(synced) = synced.filter(|synced| Some(synced) != history.as_ref()) {
+            store::save_synced_history(puuid, synced).await;
+        }
+    }
+
+    let scanned = scan.index - query.beg_index;
+    log::info!(
+        "[战绩查询] {} 查找 {} 场（本地 {} 场），选中 {} 场，下一页: {:?}",
+        puuid,
+        scanned,
+        stored,
+        scan.selected.len(),
+        scan.next_index
+    );
+    let next_index = scan.next_index;
+    let match_list = serde_json::json!({ "games": { "games": scan.selected } });
+    Ok(MatchHistoryPage {
+        statistics: analyze_match_list_data(match_list, puuid)?,
+        next_index,
+        scanned,
+    })
+}
+
+/// 使用本地战绩数据库统计玩家最近的对局（包括 LCU 已不再保留的对局）
+pub async fn get_stored_match_statistics(
+    puuid: &str,
+    count: usize,
+) -> Result<MatchStatistics, LcuError> {
+    let games = store::games_for_puuid(puuid, count).await?;
+    let match_list = serde_json::json!({ "games": { "games": games } });
+    analyze_match_list_data(match_list, puuid)
 }
 
 fn analyze_match_list_data(
     match_list_data: Value,
     current_puuid: &str,
-) -> Result<MatchStatistics, String> {
+) -> Result<MatchStatistics, LcuError> {
     println!("📊 开始分析对局列表数据");
     println!("👤 目标玩家PUUID: {}", current_puuid);
 
@@ -500,3 +758,180 @@ fn get_performance_rating(
     }
     "需要加油".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{fixtures, MockLcu, MockResponse};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn match_history_is_summarized_for_current_summoner() {
+        let mock = MockLcu::start().await;
+        let puuid = "a1b2c3d4-0000-4000-8000-000000000001";
+        store::forget_history(puuid).await;
+
+        let stats = get_match_history(&mock.client(), 20).await.unwrap();
+
+        assert_eq!(stats.total_games, 3);
+        assert_eq!(stats.wins, 2);
+        assert_eq!(stats.losses, 1);
+        assert_eq!(stats.recent_performance.len(), 3);
+        assert_eq!(stats.recent_performance[0].champion_id, 76);
+
+        // endIndex 是包含的，请求 20 场时应为 19
+        let requests = mock.requests_to(Method::GET, "/lol-match-history/v1/products/lol/");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].path,
+            "/lol-match-history/v1/products/lol/a1b2c3d4-0000-4000-8000-000000000001/matches?begIndex=0&endIndex=19"
+        );
+        // 战绩列表已全部同步：之后只确认最新一场对局，再从本地数据库读取
+        assert_eq!(
+            store::synced_history(puuid).await,
+            Some(store::SyncedHistory {
+                newest_game_id: stats.recent_performance[0].game_id as i64,
+                game_count: 3,
+                complete: true,
+            })
+        );
+        let cached = get_match_history(&mock.client(), 20).await.unwrap();
+        let game_ids = |stats: &MatchStatistics| {
+            let mut ids: Vec<u64> = stats.recent_performance.iter().map(|g| g.game_id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(game_ids(&cached), game_ids(&stats));
+        assert_eq!(cached.wins, stats.wins);
+        let requests = mock.requests_to(Method::GET, "/lol-match-history/v1/products/lol/");
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].path.ends_with("?begIndex=0&endIndex=0"));
+
+        // LCU 中出现了新的对局时重新查询
+        let mut history = fixtures::load(fixtures::MATCH_HISTORY);
+        let games = history["games"]["games"].as_array_mut().unwrap();
+        games.reverse();
+        let newest = games[0]["gameId"].as_u64().unwrap();
+        mock.respond(
+            Method::GET,
+            "/lol-match-history/v1/products/lol/*/matches",
+            MockResponse::json(history),
+        );
+        let refreshed = get_match_history(&mock.client(), 20).await.unwrap();
+        assert_eq!(refreshed.recent_performance[0].game_id, newest);
+        let requests = mock.requests_to(Method::GET, "/lol-match-history/v1/products/lol/");
+        assert_eq!(requests.len(), 4);
+        assert!(requests[3].path.ends_with("?begIndex=0&endIndex=19"));
+        store::forget_history(puuid).await;
+    }
+
+    #[tokio::test]
+    async fn query_pages_through_history_with_filters() {
+        let mock = MockLcu::start().await;
+        // 共 45 场：偶数场为排位，每 3 场一次 76，每 4 场赢一次
+        mock.handle(
+            Method::GET,
+            "/lol-match-history/v1/products/lol/*/matches",
+            |request| {
+                let param = |name: &str| {
+                    request
+                        .path
+                        .split(['?', '&'])
+                        .find_map(|p| p.strip_prefix(name))
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap()
+                };
+                let games: Vec<_> = (param("begIndex=")..=param("endIndex=").min(44))
+                    .map(|i| {
+                        json!({
+                            "gameId": 8000 + i,
+                            "gameCreation": 1_730_000_000_000i64 - i as i64 * 3_600_000,
+                            "gameDuration": 1800,
+                            "gameMode": "CLASSIC",
+                            "queueId": if i % 2 == 0 { 420 } else { 450 },
+                            "participantIdentities": [{
+                                "participantId": 1,
+                                "player": { "puuid": "query-test" }
+                            }],
+                            "participants": [{
+                                "participantId": 1,
+                                "championId": if i % 3 == 0 { 76 } else { 64 },
+                                "stats": { "win": i % 4 == 0, "kills": 1, "deaths": 1, "assists": 1 }
+                            }]
+                        })
+                    })
+                    .collect();
+                MockResponse::json(json!({ "games": { "games": games } }))
+            },
+        );
+
+        let mut query = MatchHistoryQuery {
+            count: 3,
+            queue_ids: vec![420],
+            champion_id: Some(76),
+            ..Default::default()
+        };
+        let ids = |page: &MatchHistoryPage| {
+            page.statistics
+                .recent_performance
+                .iter()
+                .map(|g| g.game_id - 8000)
+                .collect::<Vec<_>>()
+        };
+
+        store::forget_history("query-test").await;
+        let first = query_match_history(&mock.client(), "query-test", &query)
+            .await
+            .unwrap();
+        assert_eq!(ids(&first), [0, 6, 12]);
+        assert_eq!(first.next_index, Some(13));
+
+        query.beg_index = 13;
+        let second = query_match_history(&mock.client(), "query-test", &query)
+            .await
+            .unwrap();
+        assert_eq!(ids(&second), [18, 24, 30]);
+        assert_eq!(second.next_index, Some(31));
+
+        // 最后一页不足 3 场，没有下一页
+        query.beg_index = 31;
+        let last = query_match_history(&mock.client(), "query-test", &query)
+            .await
+            .unwrap();
+        assert_eq!(ids(&last), [36, 42]);
+        assert_eq!(last.next_index, None);
+        assert_eq!(last.scanned, 14);
+
+        // 只看胜场，并且早于 start_time 的对局出现后停止查找
+        let wins = MatchHistoryQuery {
+            win: Some(true),
+            start_time: Some(1_730_000_000_000 - 10 * 3_600_000),
+            ..Default::default()
+        };
+        let page = query_match_history(&mock.client(), "query-test", &wins)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), [0, 4, 8]);
+        assert_eq!(page.statistics.losses, 0);
+        assert_eq!(page.next_index, None);
+
+        // 前面的查询已同步全部 45 场：第二页起从本地读取已同步的部分，只向 LCU 查询之后的对局
+        let paths: Vec<String> = mock
+            .requests_to(Method::GET, "/lol-match-history/v1/products/lol/")
+            .into_iter()
+            .map(|r| r.path.split_once('?').unwrap().1.to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "begIndex=0&endIndex=19",
+                "begIndex=0&endIndex=0",
+                "begIndex=20&endIndex=39",
+                "begIndex=0&endIndex=0",
+                "begIndex=40&endIndex=59",
+                "begIndex=0&endIndex=0",
+            ]
+        );
+        store::forget_history("query-test").await;
+    }
+}
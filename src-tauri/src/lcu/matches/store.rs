@@ -0,0 +1,412 @@
+//! 本地战绩数据库（nidalee/matches.db）
+//!
+//! LCU 只保留最近的一部分对局，这里把查询过程中见到的每场对局（自己的战绩、选人阶段查询的
+//! 其他玩家战绩以及对局详情）按 game_id 去重保存，之后的查询和统计直接使用本地数据。
+//! games 保存对局的原始 JSON（参与者最完整的一份），participants 按 (game_id, puuid)
+//! 保存每个玩家的参与者数据，战绩列表中每场对局只包含被查询玩家自己的数据。
+//! histories 记录每个玩家从自己的战绩列表同步了多少场，同步过的范围可以直接从本地读取。
+//! SQLite 的读写是阻塞的，所有访问都放到 spawn_blocking 中执行。
+use crate::lcu::types::MatchStoreSummary;
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    game_id INTEGER PRIMARY KEY,
+    game_creation INTEGER NOT NULL,
+    game_duration INTEGER NOT NULL,
+    queue_id INTEGER NOT NULL,
+    game_mode TEXT NOT NULL,
+    identity_count INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS participants (
+    game_id INTEGER NOT NULL,
+    puuid TEXT NOT NULL,
+    participant_id INTEGER NOT NULL,
+    team_id INTEGER NOT NULL,
+    champion_id INTEGER NOT NULL,
+    win INTEGER NOT NULL,
+    kills INTEGER NOT NULL,
+    deaths INTEGER NOT NULL,
+    assists INTEGER NOT NULL,
+    identity TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (game_id, puuid)
+);
+CREATE INDEX IF NOT EXISTS idx_participants_puuid ON participants (puuid, game_id);
+CREATE INDEX IF NOT EXISTS idx_games_creation ON games (game_creation);
+CREATE TABLE IF NOT EXISTS histories (
+    puuid TEXT PRIMARY KEY,
+    newest_game_id INTEGER NOT NULL,
+    game_count INTEGER NOT NULL,
+    complete INTEGER NOT NULL
+);
+";
+
+/// SQLite 的内存数据库文件名
+pub const IN_MEMORY: &str = ":memory:";
+
+/// 数据库文件，未设置时为配置目录下的 nidalee/matches.db
+static DB_FILE: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(Default::default);
+
+static DB: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| {
+    Mutex::new(
+        open()
+            .inspect_err(|e| log::warn!("[战绩库] 打开数据库失败，不保存战绩: {}", e))
+            .ok(),
+    )
+});
+
+/// 指定数据库文件（如 IN_MEMORY），只在第一次访问数据库之前生效
+pub fn set_db_file(file: PathBuf) {
+    *DB_FILE.write().unwrap() = Some(file);
+}
+
+fn db_file() -> Option<PathBuf> {
+    DB_FILE
+        .read()
+        .unwrap()
+        .clone()
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("nidalee").join("matches.db")))
+}
+
+fn open() -> Result<Connection, String> {
+    let file = db_file().ok_or("无法获取配置目录")?;
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let conn = Connection::open(file).map_err(|e| e.to_string())?;
+    conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+    Ok(conn)
+}
+
+/// 玩家战绩列表中已同步到本地的范围：从 newest_game_id 起连续 game_count 场
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncedHistory {
+    pub newest_game_id: i64,
+    pub game_count: usize,
+    /// 已同步到战绩列表的末尾
+    pub complete: bool,
+}
+
+async fn with_db<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let mut db = DB.lock().unwrap();
+        let conn = db.as_mut().ok_or("战绩数据库不可用")?;
+        f(conn).map_err(|e| format!("战绩数据库操作失败: {}", e))
+    })
+    .await
+    .map_err(|e| format!("战绩数据库任务失败: {}", e))?
+}
+
+/// 对局 JSON 中的整数字段，缺少时为 0
+pub fn int(value: &Value, key: &str) -> i64 {
+    value.get(key).and_then(|v| v.as_i64()).unwrap_or(0)
+}
+
+/// 保存战绩列表（/lol-match-history/v1/products/lol/{puuid}/matches 的响应）中的对局
+pub async fn ingest_match_list(match_list: &Value) {
+    if let Some(games) = match_list["games"]["games"].as_array() {
+        ingest_games(games.clone()).await;
+    }
+}
+
+/// 保存对局，已存在的对局只在新数据包含更多玩家时替换。失败只记录日志，不影响查询
+pub async fn ingest_games(games: Vec<Value>) {
+    if games.is_empty() {
+        return;
+    }
+    match with_db(move |conn| insert_games(conn, &games)).await {
+        Ok(inserted) if inserted > 0 => log::debug!("[战绩库] 新增 {} 场对局", inserted),
+        Ok(_) => {}
+        Err(e) => log::warn!("[战绩库] 保存对局失败: {}", e),
+    }
+}
+
+fn insert_games(conn: &mut Connection, games: &[Value]) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    for game in games {
+        let game_id = int(game, "gameId");
+        if game_id <= 0 {
+            continue;
+        }
+        let identities = game["participantIdentities"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let exists = tx
+            .query_row(
+                "SELECT 1 FROM games WHERE game_id = ?1",
+                params![game_id],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if !exists {
+            inserted += 1;
+        }
+        tx.execute(
+            "INSERT INTO games (game_id, game_creation, game_duration, queue_id, game_mode, identity_count, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (game_id) DO UPDATE SET identity_count = excluded.identity_count, data = excluded.data
+             WHERE excluded.identity_count > games.identity_count",
+            params![
+                game_id,
+                int(game, "gameCreation"),
+                int(game, "gameDuration"),
+                int(game, "queueId"),
+                game["gameMode"].as_str().unwrap_or_default(),
+                identities.len() as i64,
+                game.to_string(),
+            ],
+        )?;
+
+        let participants = game["participants"].as_array().cloned().unwrap_or_default();
+        for identity in &identities {
+            let Some(puuid) = identity["player"]["puuid"]
+                .as_str()
+                .filter(|p| !p.is_empty())
+            else {
+                continue;
+            };
+            let participant_id = int(identity, "participantId");
+            let Some(participant) = participants
+                .iter()
+                .find(|p| int(p, "participantId") == participant_id)
+            else {
+                continue;
+            };
+            let stats = &participant["stats"];
+            tx.execute(
+                "INSERT OR REPLACE INTO participants
+                 (game_id, puuid, participant_id, team_id, champion_id, win, kills, deaths, assists, identity, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    game_id,
+                    puuid,
+                    participant_id,
+                    int(participant, "teamId"),
+                    int(participant, "championId"),
+                    stats["win"].as_bool().unwrap_or(false),
+                    int(stats, "kills"),
+                    int(stats, "deaths"),
+                    int(stats, "assists"),
+                    identity.to_string(),
+                    participant.to_string(),
+                ],
+            )?;
+        }
+    }
+    tx.commit()?;
+    Ok(inserted)
+}
+
+/// 保证对局中包含该玩家的身份和参与者数据（保存的原始对局可能来自其他玩家的战绩列表）
+fn with_player(mut game: Value, identity: Value, participant: Value) -> Value {
+    let participant_id = int(&identity, "participantId");
+    for (key, item) in [
+        ("participantIdentities", identity),
+        ("participants", participant),
+    ] {
+        if !game[key].is_array() {
+            game[key] = Value::Array(Vec::new());
+        }
+        let list = game[key].as_array_mut().unwrap();
+        if !list
+            .iter()
+            .any(|p| int(p, "participantId") == participant_id)
+        {
+            list.push(item);
+        }
+    }
+    game
+}
+
+/// 按时间从新到旧读取玩家的对局，格式与战绩列表中的对局相同
+pub async fn games_for_puuid(puuid: &str, limit: usize) -> Result<Vec<Value>, String> {
+    let puuid = puuid.to_string();
+    with_db(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT g.data, p.identity, p.data FROM participants p
+             JOIN games g ON g.game_id = p.game_id
+             WHERE p.puuid = ?1
+             ORDER BY g.game_creation DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![puuid, limit as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut games = Vec::new();
+        for row in rows {
+            let (game, identity, participant) = row?;
+            let parse = |s: &str| serde_json::from_str(s).unwrap_or(Value::Null);
+            games.push(with_player(
+                parse(&game),
+                parse(&identity),
+                parse(&participant),
+            ));
+        }
+        Ok(games)
+    })
+    .await
+}
+
+/// 玩家已同步的战绩范围，读取失败时视为未同步
+pub async fn synced_history(puuid: &str) -> Option<SyncedHistory> {
+    let puuid = puuid.to_string();
+    with_db(move |conn| {
+        conn.query_row(
+            "SELECT newest_game_id, game_count, complete FROM histories WHERE puuid = ?1",
+            params![puuid],
+            |row| {
+                Ok(SyncedHistory {
+                    newest_game_id: row.get(0)?,
+                    game_count: row.get::<_, i64>(1)? as usize,
+                    complete: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+    })
+    .await
+    .inspect_err(|e| log::warn!("[战绩库] 读取同步记录失败: {}", e))
+    .ok()
+    .flatten()
+}
+
+/// 记录玩家已同步的战绩范围，失败只记录日志
+pub async fn save_synced_history(puuid: &str, history: SyncedHistory) {
+    let puuid = puuid.to_string();
+    let result = with_db(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO histories (puuid, newest_game_id, game_count, complete)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                puuid,
+                history.newest_game_id,
+                history.game_count as i64,
+                history.complete
+            ],
+        )
+    })
+    .await;
+    if let Err(e) = result {
+        log::warn!("[战绩库] 保存同步记录失败: {}", e);
+    }
+}
+
+/// 删除玩家的同步记录，之后的查询重新从 LCU 读取
+#[cfg(test)]
+pub async fn forget_history(puuid: &str) {
+    let puuid = puuid.to_string();
+    with_db(move |conn| conn.execute("DELETE FROM histories WHERE puuid = ?1", params![puuid]))
+        .await
+        .unwrap();
+}
+
+pub async fn summary() -> Result<MatchStoreSummary, String> {
+    with_db(|conn| {
+        Ok(MatchStoreSummary {
+            game_count: conn.query_row("SELECT COUNT(*) FROM games", [], |row| row.get(0))?,
+            player_count: conn.query_row(
+                "SELECT COUNT(DISTINCT puuid) FROM participants",
+                [],
+                |row| row.get(0),
+            )?,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn game(game_id: i64, players: &[(&str, i32, bool)]) -> Value {
+        json!({
+            "gameId": game_id,
+            "gameCreation": 1700000000000i64 + game_id,
+            "gameDuration": 1500,
+            "gameMode": "CLASSIC",
+            "queueId": 420,
+            "participantIdentities": players.iter().enumerate().map(|(i, (puuid, _, _))| json!({
+                "participantId": i + 1,
+                "player": { "puuid": puuid, "gameName": puuid, "tagLine": "TEST" }
+            })).collect::<Vec<_>>(),
+            "participants": players.iter().enumerate().map(|(i, (_, champion_id, win))| json!({
+                "participantId": i + 1,
+                "championId": champion_id,
+                "teamId": 100,
+                "stats": { "win": win, "kills": 5, "deaths": 2, "assists": 3 }
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    #[tokio::test]
+    async fn games_are_deduplicated_and_merged_per_player() {
+        set_db_file(IN_MEMORY.into());
+        // 两个玩家各自的战绩列表中都有 9001001，之后又看到了完整的对局详情
+        ingest_match_list(&json!({ "games": { "games": [
+            game(9001001, &[("store-test-a", 76, true)]),
+            game(9001002, &[("store-test-a", 64, false)]),
+        ] } }))
+        .await;
+        ingest_games(vec![game(9001001, &[("store-test-b", 103, true)])]).await;
+        ingest_games(vec![game(
+            9001001,
+            &[("store-test-a", 76, true), ("store-test-b", 103, true)],
+        )])
+        .await;
+
+        let a = games_for_puuid("store-test-a", 10).await.unwrap();
+        assert_eq!(a.len(), 2);
+        assert_eq!(a[0]["gameId"], 9001002);
+        assert_eq!(a[1]["participantIdentities"].as_array().unwrap().len(), 2);
+
+        let b = games_for_puuid("store-test-b", 10).await.unwrap();
+        assert_eq!(b.len(), 1);
+        let identity = b[0]["participantIdentities"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| i["player"]["puuid"] == "store-test-b")
+            .unwrap();
+        assert_eq!(identity["participantId"], 2);
+        assert_eq!(games_for_puuid("store-test-b", 0).await.unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn synced_history_is_replaced() {
+        set_db_file(IN_MEMORY.into());
+        assert_eq!(synced_history("store-test-history").await, None);
+        let history = SyncedHistory {
+            newest_game_id: 9002001,
+            game_count: 20,
+            complete: false,
+        };
+        save_synced_history("store-test-history", history.clone()).await;
+        assert_eq!(synced_history("store-test-history").await, Some(history));
+
+        let history = SyncedHistory {
+            newest_game_id: 9002002,
+            game_count: 3,
+            complete: true,
+        };
+        save_synced_history("store-test-history", history.clone()).await;
+        assert_eq!(synced_history("store-test-history").await, Some(history));
+    }
+}
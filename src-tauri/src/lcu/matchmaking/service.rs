@@ -1,11 +1,12 @@
 
+use crate::lcu::error::LcuError;
 use crate::lcu::request::{lcu_delete, lcu_get, lcu_post};
-use crate::lcu::types::{MatchInfo, MatchmakingState, PlayerInfo};
+use crate::lcu::types::{MatchInfo, MatchmakingState, PlayerInfo, ReadyCheck};
 use reqwest::Client;
 use serde_json::Value;
 
 /// 开始匹配
-pub async fn start_matchmaking(client: &Client) -> Result<(), String> {
+pub async fn start_matchmaking(client: &Client) -> Result<(), LcuError> {
     lcu_post::<Value>(
         client,
         "/lol-lobby/v2/lobby/matchmaking/search",
@@ -16,13 +17,13 @@ pub async fn start_matchmaking(client: &Client) -> Result<(), String> {
 }
 
 /// 停止匹配
-pub async fn stop_matchmaking(client: &Client) -> Result<(), String> {
+pub async fn stop_matchmaking(client: &Client) -> Result<(), LcuError> {
     lcu_delete::<Value>(client, "/lol-lobby/v2/lobby/matchmaking/search").await?;
     Ok(())
 }
 
 /// 接受匹配
-pub async fn accept_match(client: &Client) -> Result<(), String> {
+pub async fn accept_match(client: &Client) -> Result<(), LcuError> {
     lcu_post::<Value>(
         client,
         "/lol-matchmaking/v1/ready-check/accept",
@@ -33,7 +34,7 @@ pub async fn accept_match(client: &Client) -> Result<(), String> {
 }
 
 /// 拒绝匹配
-pub async fn decline_match(client: &Client) -> Result<(), String> {
+pub async fn decline_match(client: &Client) -> Result<(), LcuError> {
     lcu_post::<Value>(
         client,
         "/lol-matchmaking/v1/ready-check/decline",
@@ -44,12 +45,17 @@ pub async fn decline_match(client: &Client) -> Result<(), String> {
 }
 
 /// 获取当前匹配状态
-pub async fn get_matchmaking_state(client: &Client) -> Result<MatchmakingState, String> {
+pub async fn get_matchmaking_state(client: &Client) -> Result<MatchmakingState, LcuError> {
     lcu_get(client, "/lol-lobby/v2/lobby/matchmaking/search-state").await
 }
 
+/// 获取准备确认状态（没有进行中的准备确认时返回 404）
+pub async fn get_ready_check(client: &Client) -> Result<ReadyCheck, LcuError> {
+    lcu_get(client, "/lol-matchmaking/v1/ready-check").await
+}
+
 /// 获取当前对局信息
-pub async fn get_match_info(client: &Client) -> Result<MatchInfo, String> {
+pub async fn get_match_info(client: &Client) -> Result<MatchInfo, LcuError> {
     let session: serde_json::Value = lcu_get(client, "/lol-champ-select/v1/session").await?;
 
     // 解析对局信息
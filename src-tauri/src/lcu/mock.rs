@@ -0,0 +1,440 @@
+//! 测试用的模拟 LCU HTTPS 服务
+//!
+//! 启动后通过 StaticCredentialProvider 接管所有 LCU 请求，返回 tests/fixtures/lcu 下的录制数据，
+//! 并支持按游戏阶段脚本化切换（大厅 -> 匹配 -> 确认 -> 选人 -> 游戏中）
+use crate::lcu::auth::provider::{self, StaticCredentialProvider};
+use crate::lcu::endpoint::LcuEndpoint;
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, OwnedMutexGuard};
+use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+/// 凭据来源是全局的，同一时间只允许一个模拟服务运行
+static MOCK_LOCK: Lazy<Arc<tokio::sync::Mutex<()>>> = Lazy::new(Default::default);
+
+const TOKEN: &str = "mock-remoting-token";
+
+/// 录制的 LCU 响应
+pub mod fixtures {
+    pub const CURRENT_SUMMONER: &str =
+        include_str!("../../tests/fixtures/lcu/current-summoner.json");
+    pub const SUMMONER: &str = include_str!("../../tests/fixtures/lcu/summoner.json");
+    pub const RANKED_STATS: &str = include_str!("../../tests/fixtures/lcu/ranked-stats.json");
+    pub const LOBBY: &str = include_str!("../../tests/fixtures/lcu/lobby.json");
+    pub const SEARCH_STATE_INVALID: &str =
+        include_str!("../../tests/fixtures/lcu/search-state-invalid.json");
+    pub const SEARCH_STATE_SEARCHING: &str =
+        include_str!("../../tests/fixtures/lcu/search-state-searching.json");
+    pub const SEARCH_STATE_FOUND: &str =
+        include_str!("../../tests/fixtures/lcu/search-state-found.json");
+    pub const READY_CHECK: &str = include_str!("../../tests/fixtures/lcu/ready-check.json");
+    pub const CHAMP_SELECT_SESSION: &str =
+        include_str!("../../tests/fixtures/lcu/champ-select-session.json");
+    pub const MATCH_HISTORY: &str = include_str!("../../tests/fixtures/lcu/match-history.json");
+    pub const PERKS_PAGES: &str = include_str!("../../tests/fixtures/lcu/perks-pages.json");
+    pub const PERKS_STYLES: &str = include_str!("../../tests/fixtures/lcu/perks-styles.json");
+    pub const PERKS_PERKS: &str = include_str!("../../tests/fixtures/lcu/perks-perks.json");
+    /// Live Client Data API 的 /liveclientdata/playerlist
+    pub const LIVE_PLAYER_LIST: &str =
+        include_str!("../../tests/fixtures/lcu/live-player-list.json");
+
+    pub fn load(fixture: &str) -> serde_json::Value {
+        serde_json::from_str(fixture).expect("fixture 不是合法 JSON")
+    }
+}
+
+/// 服务收到的请求，供测试断言
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    /// 含查询参数的完整路径
+    pub path: String,
+    pub body: Option<Value>,
+}
+
+/// 模拟响应，body 为 None 时返回空响应体
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub body: Option<Value>,
+}
+
+impl MockResponse {
+    pub fn json(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    pub fn no_content() -> Self {
+        Self {
+            status: 204,
+            body: None,
+        }
+    }
+
+    /// 与 LCU 一致的错误响应体
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: Some(json!({
+                "errorCode": "RPC_ERROR",
+                "httpStatus": status,
+                "implementationDetails": {},
+                "message": message,
+            })),
+        }
+    }
+}
+
+type Handler = Arc<dyn Fn(&RecordedRequest) -> MockResponse + Send + Sync>;
+
+struct Route {
+    method: Method,
+    /// 路径模板，* 匹配任意一段，如 /lol-summoner/v1/summoners/*
+    pattern: String,
+    handler: Handler,
+}
+
+impl Route {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        if self.method != *method {
+            return false;
+        }
+        let path = path.split('?').next().unwrap_or(path);
+        let mut expected = self.pattern.split('/');
+        let mut actual = path.split('/');
+        loop {
+            match (expected.next(), actual.next()) {
+                (None, None) => return true,
+                (Some("*"), Some(_)) => {}
+                (Some(e), Some(a)) if e == a => {}
+                _ => return false,
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+impl MockState {
+    /// 后注册的路由优先
+    fn respond(&mut self, request: RecordedRequest) -> MockResponse {
+        let response = self
+            .routes
+            .iter()
+            .rev()
+            .find(|r| r.matches(&request.method, &request.path))
+            .map(|r| (r.handler)(&request))
+            .unwrap_or_else(|| {
+                MockResponse::error(404, &format!("Invalid URI format: {}", request.path))
+            });
+        self.requests.push(request);
+        response
+    }
+}
+
+pub struct MockLcu {
+    pub endpoint: LcuEndpoint,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl MockLcu {
+    /// 启动模拟服务并加载默认录制数据，初始阶段为 None（客户端空闲）
+    pub async fn start() -> Self {
+        let guard = MOCK_LOCK.clone().lock_owned().await;
+
+        let cert = rcgen::generate_simple_self_signed(vec![
+            "127.0.0.1".to_string(),
+            "localhost".to_string(),
+        ])
+        .expect("生成自签名证书失败");
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![Certificate(cert.serialize_der().expect("序列化证书失败"))],
+                PrivateKey(cert.serialize_private_key_der()),
+            )
+            .expect("创建 TLS 配置失败");
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("绑定端口失败");
+        let port = listener.local_addr().unwrap().port();
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let (shutdown, mut shutdown_rx) = oneshot::channel();
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            loop {
+                let tcp = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((tcp, _)) => tcp,
+                        Err(_) => continue,
+                    },
+                    _ = &mut shutdown_rx => break,
+                };
+                let acceptor = acceptor.clone();
+                let state = server_state.clone();
+                tokio::spawn(async move {
+                    let Ok(tls) = acceptor.accept(tcp).await else {
+                        return;
+                    };
+                    let service = service_fn(move |req| handle(state.clone(), req));
+                    let _ = Http::new()
+                        .http1_only(true)
+                        .serve_connection(tls, service)
+                        .await;
+                });
+            }
+        });
+
+        let endpoint = LcuEndpoint::custom(&format!("https://127.0.0.1:{}", port), TOKEN)
+            .expect("模拟服务地址无效");
+        provider::register_provider(Arc::new(StaticCredentialProvider(endpoint.clone())));
+        // 符文页备份写入临时目录，战绩使用内存数据库，不写入用户的配置目录
+        crate::lcu::matches::store::set_db_file(crate::lcu::matches::store::IN_MEMORY.into());
+        crate::lcu::perks::backup::set_backup_dir(
+            std::env::temp_dir()
+                .join("nidalee-test")
+                .join("rune_backups"),
+        );
+
+        let mock = Self {
+            endpoint,
+            state,
+            shutdown: Some(shutdown),
+            _guard: guard,
+        };
+        mock.load_default_fixtures();
+        mock.transition("None");
+        mock
+    }
+
+    /// 与全局客户端配置一致的 HTTP 客户端（每个测试运行时独立创建，避免复用失效的连接）
+    pub fn client(&self) -> reqwest::Client {
+        reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .expect("创建 HTTP 客户端失败")
+    }
+
+    fn load_default_fixtures(&self) {
+        self.fixture(
+            Method::GET,
+            "/lol-summoner/v1/current-summoner",
+            fixtures::CURRENT_SUMMONER,
+        );
+        self.fixture(
+            Method::GET,
+            "/lol-summoner/v1/summoners/*",
+            fixtures::SUMMONER,
+        );
+        let me = fixtures::load(fixtures::CURRENT_SUMMONER);
+        self.fixture(
+            Method::GET,
+            &format!("/lol-summoner/v1/summoners/{}", me["summonerId"]),
+            fixtures::CURRENT_SUMMONER,
+        );
+        self.fixture(
+            Method::GET,
+            "/lol-ranked/v1/ranked-stats/*",
+            fixtures::RANKED_STATS,
+        );
+        self.fixture(
+            Method::GET,
+            "/lol-match-history/v1/products/lol/*/matches",
+            fixtures::MATCH_HISTORY,
+        );
+        self.fixture(Method::GET, "/lol-perks/v1/pages", fixtures::PERKS_PAGES);
+        self.fixture(Method::GET, "/lol-perks/v1/styles", fixtures::PERKS_STYLES);
+        self.fixture(Method::GET, "/lol-perks/v1/perks", fixtures::PERKS_PERKS);
+        // 新建符文页：回显请求体并分配 id
+        self.handle(Method::POST, "/lol-perks/v1/pages", |req| {
+            let mut page = req.body.clone().unwrap_or_else(|| json!({}));
+            page["id"] = json!(1620099);
+            page["current"] = json!(true);
+            page["isEditable"] = json!(true);
+            page["isDeletable"] = json!(true);
+            page["isValid"] = json!(true);
+            MockResponse::json(page)
+        });
+        self.handle(Method::DELETE, "/lol-perks/v1/pages/*", |_| {
+            MockResponse::no_content()
+        });
+        self.handle(
+            Method::PATCH,
+            "/lol-champ-select/v1/session/actions/*",
+            |_| MockResponse::no_content(),
+        );
+        self.handle(
+            Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+            |_| MockResponse::no_content(),
+        );
+    }
+
+    /// 注册动态路由
+    pub fn handle<F>(&self, method: Method, pattern: &str, handler: F)
+    where
+        F: Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        self.state.lock().unwrap().routes.push(Route {
+            method,
+            pattern: pattern.to_string(),
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// 注册固定响应
+    pub fn respond(&self, method: Method, pattern: &str, response: MockResponse) {
+        self.handle(method, pattern, move |_| response.clone());
+    }
+
+    /// 注册录制数据
+    pub fn fixture(&self, method: Method, pattern: &str, fixture: &str) {
+        self.respond(method, pattern, MockResponse::json(fixtures::load(fixture)));
+    }
+
+    /// 按游戏阶段切换相关接口的返回，模拟客户端状态流转
+    pub fn transition(&self, phase: &str) {
+        let not_found = |path: &str| MockResponse::error(404, &format!("{} 不存在", path));
+        let lobby = MockResponse::json(fixtures::load(fixtures::LOBBY));
+        let search_state = |fixture| MockResponse::json(fixtures::load(fixture));
+        let session = MockResponse::json(fixtures::load(fixtures::CHAMP_SELECT_SESSION));
+
+        let (lobby, search, ready_check, session) = match phase {
+            "Lobby" => (
+                lobby,
+                search_state(fixtures::SEARCH_STATE_INVALID),
+                not_found("ready-check"),
+                not_found("session"),
+            ),
+            "Matchmaking" => (
+                lobby,
+                search_state(fixtures::SEARCH_STATE_SEARCHING),
+                not_found("ready-check"),
+                not_found("session"),
+            ),
+            "ReadyCheck" => (
+                lobby,
+                search_state(fixtures::SEARCH_STATE_FOUND),
+                MockResponse::json(fixtures::load(fixtures::READY_CHECK)),
+                not_found("session"),
+            ),
+            "ChampSelect" => (
+                lobby,
+                not_found("search-state"),
+                not_found("ready-check"),
+                session,
+            ),
+            _ => (
+                not_found("lobby"),
+                not_found("search-state"),
+                not_found("ready-check"),
+                not_found("session"),
+            ),
+        };
+
+        self.respond(
+            Method::GET,
+            "/lol-gameflow/v1/gameflow-phase",
+            MockResponse::json(json!(phase)),
+        );
+        self.respond(Method::GET, "/lol-lobby/v2/lobby", lobby);
+        self.respond(
+            Method::GET,
+            "/lol-lobby/v2/lobby/matchmaking/search-state",
+            search,
+        );
+        self.respond(Method::GET, "/lol-matchmaking/v1/ready-check", ready_check);
+        self.respond(Method::GET, "/lol-champ-select/v1/session", session);
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// 指定方法和路径前缀的请求
+    pub fn requests_to(&self, method: Method, path_prefix: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path.starts_with(path_prefix))
+            .collect()
+    }
+}
+
+impl Drop for MockLcu {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        provider::reset_providers();
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let expected = format!(
+        "Basic {}",
+        base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            format!("riot:{}", TOKEN)
+        )
+    );
+    let authorized = req
+        .headers()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        == Some(expected.as_str());
+
+    let method = req.method().clone();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let bytes = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let body = serde_json::from_slice::<Value>(&bytes).ok();
+
+    // Live Client Data API 无需认证，测试时与 LCU 共用同一个模拟服务
+    let response = if authorized || path.starts_with("/liveclientdata/") {
+        state
+            .lock()
+            .unwrap()
+            .respond(RecordedRequest { method, path, body })
+    } else {
+        MockResponse::error(401, "Unauthorized")
+    };
+
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+    let builder = Response::builder()
+        .status(status)
+        .header("content-type", "application/json");
+    let body = match response.body {
+        Some(body) => Body::from(body.to_string()),
+        None => Body::empty(),
+    };
+    Ok(builder.body(body).unwrap())
+}
@@ -1,18 +1,29 @@
 pub mod auth;
+pub mod automation;
 pub mod champ_select;
 pub mod connection;
 pub mod ddragon;
+pub mod endpoint;
+pub mod error;
 pub mod gameflow;
+pub mod item_sets;
+pub mod live_client;
 pub mod lobby;
 pub mod matches;
 pub mod matchmaking;
+#[cfg(test)]
+pub mod mock;
 pub mod opgg;
+pub mod passthrough;
 pub mod perks;
+pub mod premade;
 pub mod ranked;
 pub mod request;
 pub mod summoner;
+pub mod traffic;
 pub mod types;
 pub mod unified_polling;
+pub mod websocket;
 
 // Re-export 常用的类型和函数，便于外部使用
 pub use connection::service::ConnectionManager;
@@ -1,6 +1,7 @@
 use crate::lcu::opgg::service;
 use crate::lcu::opgg::types::*;
 use crate::http_client;
+use crate::lcu::error::LcuError;
 use crate::lcu::perks::service as perks_service;
 use serde_json::Value;
 
@@ -53,15 +54,15 @@ pub async fn apply_opgg_runes(
     position: Option<String>,
     tier: String,
     build_index: Option<usize>,
-) -> Result<String, String> {
+) -> Result<String, LcuError> {
     let build = service::get_champion_build(&region, &mode, champion_id, position.clone(), &tier).await?;
     let build_idx = build_index.unwrap_or(0);
     if build_idx >= build.perks.len() {
-        return Err(format!(
+        return Err(LcuError::other(format!(
             "详细索引 {} 超出范围，总共有 {} 个详细",
             build_idx,
             build.perks.len()
-        ));
+        )));
     }
     let selected_perk = &build.perks[build_idx];
     let client = http_client::get_lcu_client();
@@ -75,6 +76,9 @@ pub async fn apply_opgg_runes(
     .await
     {
         Ok(message) => Ok(format!("OP.GG符文配置应用成功！{}", message)),
-        Err(e) => Err(format!("OP.GG符文配置应用失败: {}", e)),
+        Err(e) => {
+            log::error!("OP.GG符文配置应用失败: {}", e);
+            Err(e)
+        }
     }
 }
@@ -0,0 +1,32 @@
+use crate::http_client;
+use crate::lcu::error::LcuError;
+use crate::lcu::passthrough::service;
+use crate::lcu::types::{PassthroughAuditEntry, PassthroughConfig};
+use serde_json::Value;
+
+/// 调用任意 LCU 接口（受透传规则限制），如 method = "GET", path = "/lol-loot/v1/player-loot"
+#[tauri::command]
+pub async fn lcu_passthrough(
+    method: String,
+    path: String,
+    body: Option<Value>,
+) -> Result<Value, LcuError> {
+    let client = http_client::get_lcu_client();
+    service::passthrough(client, &method, &path, body).await
+}
+
+#[tauri::command]
+pub fn get_passthrough_config() -> PassthroughConfig {
+    service::get_config()
+}
+
+#[tauri::command]
+pub fn save_passthrough_config(config: PassthroughConfig) -> Result<PassthroughConfig, LcuError> {
+    service::save_config(config).map_err(LcuError::from)
+}
+
+/// 最近的透传审计记录，最新的在前
+#[tauri::command]
+pub fn get_passthrough_audit(limit: Option<usize>) -> Vec<PassthroughAuditEntry> {
+    service::recent_audit(limit.unwrap_or(50))
+}
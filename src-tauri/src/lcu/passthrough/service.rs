@@ -0,0 +1,286 @@
+//! 通用 LCU 透传：转发未封装的接口（战利品、挑战、荣誉、冠军杯赛等）
+//!
+//! 每次调用先按 passthrough_config.json 中的规则检查方法和路径，
+//! deny 规则在任何模式下都优先生效；无论放行与否都会写一条审计记录。
+use crate::lcu::error::LcuError;
+use crate::lcu::request::lcu_request_raw;
+use crate::lcu::types::{
+    PassthroughAuditEntry, PassthroughConfig, PassthroughMode, PassthroughRule,
+};
+use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
+use reqwest::{Client, Method};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 内存中保留的审计记录条数（完整记录在 passthrough_audit.jsonl）
+const MAX_RECENT_AUDIT: usize = 200;
+
+const ALLOWED_METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+static PASSTHROUGH_CONFIG: Lazy<RwLock<PassthroughConfig>> =
+    Lazy::new(|| RwLock::new(load_config()));
+static RECENT_AUDIT: Lazy<RwLock<VecDeque<PassthroughAuditEntry>>> =
+    Lazy::new(|| RwLock::new(VecDeque::new()));
+
+fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nidalee"))
+}
+
+fn load_config() -> PassthroughConfig {
+    let Some(file) = config_dir().map(|dir| dir.join("passthrough_config.json")) else {
+        return PassthroughConfig::default();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return PassthroughConfig::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!(
+            "[LCU] 解析 passthrough_config.json 失败，使用默认配置: {}",
+            e
+        );
+        PassthroughConfig::default()
+    })
+}
+
+pub fn get_config() -> PassthroughConfig {
+    PASSTHROUGH_CONFIG.read().unwrap().clone()
+}
+
+/// 校验并保存透传配置，方法统一转为大写
+pub fn save_config(mut config: PassthroughConfig) -> Result<PassthroughConfig, String> {
+    for rule in config.allow.iter_mut().chain(config.deny.iter_mut()) {
+        if !rule.path_prefix.starts_with('/') {
+            return Err(format!("路径前缀必须以 / 开头: {}", rule.path_prefix));
+        }
+        for method in rule.methods.iter_mut() {
+            *method = method.trim().to_uppercase();
+            if !ALLOWED_METHODS.contains(&method.as_str()) {
+                return Err(format!("不支持的请求方法: {}", method));
+            }
+        }
+    }
+
+    let dir = config_dir().ok_or("无法获取配置目录")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    let content =
+        serde_json::to_string_pretty(&config).map_err(|e| format!("序列化配置失败: {}", e))?;
+    fs::write(dir.join("passthrough_config.json"), content)
+        .map_err(|e| format!("保存配置失败: {}", e))?;
+
+    *PASSTHROUGH_CONFIG.write().unwrap() = config.clone();
+    log::info!(
+        "[LCU] 透传规则已更新: {:?}，allow {} 条，deny {} 条",
+        config.mode,
+        config.allow.len(),
+        config.deny.len()
+    );
+    Ok(config)
+}
+
+fn rule_matches(rule: &PassthroughRule, method: &Method, path: &str) -> bool {
+    let prefix = rule.path_prefix.to_lowercase();
+    let method_matches = rule.methods.is_empty()
+        || rule
+            .methods
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(method.as_str()));
+    method_matches && path.starts_with(&prefix)
+}
+
+/// 按规则检查一次调用，返回拒绝原因
+pub fn check(config: &PassthroughConfig, method: &Method, path: &str) -> Result<(), String> {
+    if !ALLOWED_METHODS.contains(&method.as_str()) {
+        return Err(format!("不支持的请求方法: {}", method));
+    }
+    if !path.starts_with('/') {
+        return Err("路径必须以 / 开头".to_string());
+    }
+    // 解码后统一小写再匹配，避免用 %2F、大小写绕过 deny 规则
+    let path_only = path.split(['?', '#']).next().unwrap_or_default();
+    let decoded = percent_decode_str(path_only)
+        .decode_utf8_lossy()
+        .to_lowercase();
+    if decoded.contains("..") || decoded.contains("//") || decoded.contains('\\') {
+        return Err("路径不合法".to_string());
+    }
+
+    if let Some(rule) = config
+        .deny
+        .iter()
+        .find(|r| rule_matches(r, method, &decoded))
+    {
+        return Err(format!("命中 deny 规则 {}", rule.path_prefix));
+    }
+    match config.mode {
+        PassthroughMode::Denylist => Ok(()),
+        PassthroughMode::Allowlist => {
+            if config
+                .allow
+                .iter()
+                .any(|r| rule_matches(r, method, &decoded))
+            {
+                Ok(())
+            } else {
+                Err("不在 allow 列表中".to_string())
+            }
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// 写入审计记录：内存保留最近的记录，同时追加到 passthrough_audit.jsonl
+fn audit(entry: PassthroughAuditEntry) {
+    if entry.allowed {
+        log::info!(
+            "[LCU] 透传 {} {} -> {:?} (耗时: {}ms)",
+            entry.method,
+            entry.path,
+            entry.status,
+            entry.elapsed_ms
+        );
+    } else {
+        log::warn!(
+            "[LCU] 透传被拒绝 {} {}: {}",
+            entry.method,
+            entry.path,
+            entry.error.as_deref().unwrap_or_default()
+        );
+    }
+
+    if let Some(dir) = config_dir() {
+        let result = fs::create_dir_all(&dir)
+            .and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(dir.join("passthrough_audit.jsonl"))
+            })
+            .and_then(|mut file| {
+                let line = serde_json::to_string(&entry).unwrap_or_default();
+                writeln!(file, "{}", line)
+            });
+        if let Err(e) = result {
+            log::warn!("[LCU] 写入透传审计日志失败: {}", e);
+        }
+    }
+
+    let mut recent = RECENT_AUDIT.write().unwrap();
+    if recent.len() >= MAX_RECENT_AUDIT {
+        recent.pop_front();
+    }
+    recent.push_back(entry);
+}
+
+/// 最近的审计记录，最新的在前
+pub fn recent_audit(limit: usize) -> Vec<PassthroughAuditEntry> {
+    RECENT_AUDIT
+        .read()
+        .unwrap()
+        .iter()
+        .rev()
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// 检查规则后转发请求，返回客户端的 JSON 响应（204 为 null）
+pub async fn passthrough(
+    client: &Client,
+    method: &str,
+    path: &str,
+    body: Option<Value>,
+) -> Result<Value, LcuError> {
+    let method_name = method.trim().to_uppercase();
+    let mut entry = PassthroughAuditEntry {
+        timestamp_ms: now_ms(),
+        method: method_name.clone(),
+        path: path.to_string(),
+        has_body: body.is_some(),
+        allowed: false,
+        status: None,
+        elapsed_ms: 0,
+        error: None,
+    };
+
+    let checked = Method::from_bytes(method_name.as_bytes())
+        .map_err(|_| format!("不支持的请求方法: {}", method_name))
+        .and_then(|m| check(&get_config(), &m, path).map(|_| m));
+    let method = match checked {
+        Ok(method) => method,
+        Err(reason) => {
+            entry.error = Some(reason.clone());
+            audit(entry);
+            return Err(LcuError::Forbidden {
+                method: method_name,
+                path: path.to_string(),
+                reason,
+            });
+        }
+    };
+
+    entry.allowed = true;
+    let start = Instant::now();
+    let result = lcu_request_raw(client, method, path, body).await;
+    entry.elapsed_ms = start.elapsed().as_millis() as u64;
+    match &result {
+        Ok(response) => entry.status = Some(response.status.as_u16()),
+        Err(e) => {
+            entry.status = e.status();
+            entry.error = Some(e.to_string());
+        }
+    }
+    audit(entry);
+    result?.json()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_only_allows_listed_reads() {
+        let config = PassthroughConfig::default();
+
+        assert!(check(&config, &Method::GET, "/lol-loot/v1/player-loot").is_ok());
+        assert!(check(
+            &config,
+            &Method::GET,
+            "/lol-clash/v1/tournament-summary?x=1"
+        )
+        .is_ok());
+        assert!(check(&config, &Method::POST, "/lol-loot/v1/recipes/craft").is_err());
+        assert!(check(&config, &Method::GET, "/lol-summoner/v1/current-summoner").is_err());
+    }
+
+    #[test]
+    fn deny_rules_win_and_cannot_be_bypassed() {
+        let config = PassthroughConfig {
+            mode: PassthroughMode::Denylist,
+            allow: vec![],
+            deny: vec![
+                PassthroughRule::new("/process-control/", &[]),
+                PassthroughRule::new("/lol-loot/", &["POST"]),
+            ],
+        };
+
+        assert!(check(&config, &Method::GET, "/lol-loot/v1/player-loot").is_ok());
+        assert!(check(&config, &Method::POST, "/lol-loot/v1/recipes/craft").is_err());
+        assert!(check(&config, &Method::POST, "/process-control/v1/process/quit").is_err());
+        assert!(check(&config, &Method::POST, "/Process-Control/v1/process/quit").is_err());
+        assert!(check(&config, &Method::POST, "/process%2Dcontrol/v1/process/quit").is_err());
+        assert!(check(&config, &Method::POST, "/lol-chat/../process-control/v1").is_err());
+        assert!(check(&config, &Method::OPTIONS, "/lol-chat/v1/me").is_err());
+    }
+}
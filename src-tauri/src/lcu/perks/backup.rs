@@ -0,0 +1,242 @@
+//! 符文页备份与恢复
+//!
+//! 删除任何符文页之前先把全部符文页导出到 nidalee/rune_backups/rune_pages-{时间戳}.json，
+//! 最多保留 MAX_BACKUPS 个备份。恢复时只在符文页数量上限内创建客户端中不存在的自定义符文页，
+//! 不会删除任何符文页。
+use crate::lcu::error::LcuError;
+use crate::lcu::perks::service::{create_rune_page, get_perk_inventory, get_rune_pages};
+use crate::lcu::types::{RunePage, RunePageBackupInfo, RuneRestoreResult};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_BACKUPS: usize = 20;
+const BACKUP_PREFIX: &str = "rune_pages-";
+
+/// 备份目录，未设置时为配置目录下的 nidalee/rune_backups
+static BACKUP_DIR: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunePageBackup {
+    timestamp_ms: u64,
+    pages: Vec<RunePage>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// 指定备份目录（模拟 LCU 服务使用临时目录）
+pub fn set_backup_dir(dir: PathBuf) {
+    *BACKUP_DIR.write().unwrap() = Some(dir);
+}
+
+fn backup_dir() -> Option<PathBuf> {
+    BACKUP_DIR
+        .read()
+        .unwrap()
+        .clone()
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("nidalee").join("rune_backups")))
+}
+
+/// 导出全部符文页
+pub fn backup_pages(pages: &[RunePage]) -> Result<RunePageBackupInfo, String> {
+    let dir = backup_dir().ok_or("无法获取配置目录")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("创建备份目录失败: {}", e))?;
+    let timestamp_ms = now_ms();
+    let file = format!("{}{}.json", BACKUP_PREFIX, timestamp_ms);
+    let backup = RunePageBackup {
+        timestamp_ms,
+        pages: pages.to_vec(),
+    };
+    let content =
+        serde_json::to_string_pretty(&backup).map_err(|e| format!("序列化符文页失败: {}", e))?;
+    fs::write(dir.join(&file), content).map_err(|e| format!("写入符文页备份失败: {}", e))?;
+    log::info!("[符文页] 已备份 {} 个符文页到 {}", pages.len(), file);
+
+    // 只保留最近的备份
+    if let Ok(backups) = list_backups() {
+        for old in backups.iter().skip(MAX_BACKUPS) {
+            let _ = fs::remove_file(dir.join(&old.file));
+        }
+    }
+    Ok(RunePageBackupInfo {
+        file,
+        timestamp_ms,
+        page_count: pages.len(),
+    })
+}
+
+/// 从客户端获取全部符文页并备份
+pub async fn backup_current_pages(client: &Client) -> Result<RunePageBackupInfo, LcuError> {
+    let pages = get_rune_pages(client).await?;
+    Ok(backup_pages(&pages)?)
+}
+
+fn read_backup(path: &Path) -> Result<RunePageBackup, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取符文页备份失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析符文页备份失败: {}", e))
+}
+
+/// 列出备份，最新的在前
+pub fn list_backups() -> Result<Vec<RunePageBackupInfo>, String> {
+    let dir = backup_dir().ok_or("无法获取配置目录")?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut backups: Vec<RunePageBackupInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().to_string();
+            if !file.starts_with(BACKUP_PREFIX) || !file.ends_with(".json") {
+                return None;
+            }
+            let backup = read_backup(&entry.path()).ok()?;
+            Some(RunePageBackupInfo {
+                file,
+                timestamp_ms: backup.timestamp_ms,
+                page_count: backup.pages.len(),
+            })
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.timestamp_ms));
+    Ok(backups)
+}
+
+/// 读取指定的备份，未指定时读取最新的备份
+fn load_backup(file: Option<&str>) -> Result<RunePageBackup, String> {
+    let dir = backup_dir().ok_or("无法获取配置目录")?;
+    let file = match file {
+        // 只接受备份目录中的文件名
+        Some(file) if Path::new(file).file_name() == Some(file.as_ref()) => file.to_string(),
+        Some(file) => return Err(format!("无效的备份文件名: {}", file)),
+        None => list_backups()?
+            .into_iter()
+            .next()
+            .map(|b| b.file)
+            .ok_or("没有符文页备份")?,
+    };
+    read_backup(&dir.join(file))
+}
+
+fn same_page(a: &RunePage, b: &RunePage) -> bool {
+    a.name == b.name
+        && a.primary_style_id == b.primary_style_id
+        && a.sub_style_id == b.sub_style_id
+        && a.selected_perk_ids == b.selected_perk_ids
+}
+
+/// 从备份恢复自定义符文页，已存在的符文页跳过，超出数量上限时停止创建
+pub async fn restore_pages(
+    client: &Client,
+    file: Option<&str>,
+) -> Result<RuneRestoreResult, LcuError> {
+    let backup = load_backup(file)?;
+    let pages = get_rune_pages(client).await?;
+    let mut custom_count = pages.iter().filter(|p| p.is_deletable).count() as i32;
+    let limit = match get_perk_inventory(client).await {
+        Ok(inventory) => Some(inventory.owned_page_count),
+        Err(e) => {
+            log::warn!("[符文页] 获取符文页数量上限失败: {}", e);
+            None
+        }
+    };
+
+    let mut result = RuneRestoreResult::default();
+    for page in backup.pages.iter().filter(|p| p.is_deletable) {
+        if pages.iter().any(|p| same_page(p, page)) {
+            result.skipped.push(format!("{}（已存在）", page.name));
+            continue;
+        }
+        if limit.is_some_and(|limit| custom_count >= limit) {
+            result.skipped.push(format!("{}（符文页已满）", page.name));
+            continue;
+        }
+        match create_rune_page(
+            client,
+            &page.name,
+            page.primary_style_id,
+            page.sub_style_id,
+            page.selected_perk_ids.clone(),
+        )
+        .await
+        {
+            Ok(_) => {
+                custom_count += 1;
+                result.restored.push(page.name.clone());
+            }
+            Err(e) => result.skipped.push(format!("{}（{}）", page.name, e)),
+        }
+    }
+    log::info!(
+        "[符文页] 恢复完成: 恢复 {} 个，跳过 {} 个",
+        result.restored.len(),
+        result.skipped.len()
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn page(id: i64, name: &str, perks: Vec<i32>) -> RunePage {
+        RunePage {
+            id,
+            name: name.to_string(),
+            current: false,
+            is_editable: true,
+            is_deletable: true,
+            is_valid: true,
+            primary_style_id: 8100,
+            sub_style_id: 8200,
+            selected_perk_ids: perks,
+        }
+    }
+
+    #[tokio::test]
+    async fn restore_creates_missing_pages_within_limit() {
+        let mock = MockLcu::start().await;
+        mock.respond(
+            Method::GET,
+            "/lol-perks/v1/inventory",
+            MockResponse::json(json!({ "ownedPageCount": 3, "customPageCount": 3 })),
+        );
+        // 客户端中已有 Nidalee 页（与备份中相同）
+        let client_pages = get_rune_pages(&mock.client()).await.unwrap();
+        let mut backup = client_pages.clone();
+        backup.push(page(1, "我的中单", vec![8112, 8139]));
+        backup.push(page(2, "我的打野", vec![8128, 8143]));
+        backup.push(page(3, "我的辅助", vec![8214, 8226]));
+        let info = backup_pages(&backup).unwrap();
+        assert_eq!(info.page_count, 5);
+
+        let result = restore_pages(&mock.client(), Some(&info.file))
+            .await
+            .unwrap();
+
+        assert_eq!(result.restored, ["我的中单", "我的打野"]);
+        assert_eq!(
+            result.skipped,
+            ["Nidalee : Nidalee（已存在）", "我的辅助（符文页已满）"]
+        );
+        assert_eq!(
+            mock.requests_to(Method::POST, "/lol-perks/v1/pages").len(),
+            2
+        );
+        assert!(restore_pages(&mock.client(), Some("../other.json"))
+            .await
+            .is_err());
+    }
+}
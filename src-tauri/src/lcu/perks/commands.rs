@@ -1,19 +1,58 @@
+use crate::lcu::error::LcuError;
 use crate::{http_client, lcu};
 
 #[tauri::command]
-pub async fn get_lcu_rune_styles() -> Result<Vec<lcu::types::RuneStyle>, String> {
+pub async fn get_lcu_rune_styles() -> Result<Vec<lcu::types::RuneStyle>, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::perks::service::list_all_styles(&client).await
 }
 
 #[tauri::command]
-pub async fn get_lcu_perks() -> Result<Vec<lcu::types::Perk>, String> {
+pub async fn get_lcu_perks() -> Result<Vec<lcu::types::Perk>, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::perks::service::list_all_perks(&client).await
 }
 
 #[tauri::command]
-pub async fn get_lcu_perk_icon(icon_path: String) -> Result<Vec<u8>, String> {
+pub async fn get_lcu_perk_icon(icon_path: String) -> Result<Vec<u8>, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::perks::service::get_perk_icon(&client, &icon_path).await
 }
+
+/// 立即备份全部符文页
+#[tauri::command]
+pub async fn backup_rune_pages() -> Result<lcu::types::RunePageBackupInfo, LcuError> {
+    let client = http_client::get_lcu_client();
+    lcu::perks::backup::backup_current_pages(client).await
+}
+
+#[tauri::command]
+pub fn list_rune_page_backups() -> Result<Vec<lcu::types::RunePageBackupInfo>, LcuError> {
+    lcu::perks::backup::list_backups().map_err(LcuError::from)
+}
+
+/// 从备份恢复符文页，file 为空时使用最新的备份
+#[tauri::command]
+pub async fn restore_rune_pages(
+    file: Option<String>,
+) -> Result<lcu::types::RuneRestoreResult, LcuError> {
+    let client = http_client::get_lcu_client();
+    lcu::perks::backup::restore_pages(client, file.as_deref()).await
+}
+
+/// 校验符文选择，repair 为 true 时尝试用 popular_perk_ids 中更常用的有效符文修复
+#[tauri::command]
+pub async fn validate_rune_selection(
+    selection: lcu::types::RuneSelection,
+    repair: bool,
+    popular_perk_ids: Option<Vec<Vec<i32>>>,
+) -> Result<lcu::types::RuneValidation, LcuError> {
+    let client = http_client::get_lcu_client();
+    lcu::perks::validator::check_rune_selection(
+        client,
+        &selection,
+        repair,
+        &popular_perk_ids.unwrap_or_default(),
+    )
+    .await
+}
@@ -1,2 +1,4 @@
+pub mod backup;
 pub mod commands;
 pub mod service;
+pub mod validator;
@@ -1,30 +1,35 @@
 
 //! LCU 符文相关 API
+use crate::lcu::error::LcuError;
+use crate::lcu::perks::{backup, validator};
 use crate::lcu::request::lcu_request_raw;
 use crate::lcu::request::{lcu_delete, lcu_get, lcu_post, lcu_put};
 use crate::lcu::types::{
-    CreateRunePageRequest, ItemBlock, ItemSet, Perk, RecommendedItem, RunePage, RuneStyle,
+    CreateRunePageRequest, Perk, PerkInventory, RunePage, RuneSelection, RuneStyle,
 };
 use reqwest::Client;
 use serde_json::json;
 
+/// Nidalee 创建的符文页名称前缀，只有这些符文页会被覆盖
+pub const NIDALEE_PAGE_PREFIX: &str = "Nidalee :";
+
 /// 获取所有符文样式
 /// 对应 LCU API: /lol-perks/v1/styles
-pub async fn list_all_styles(client: &Client) -> Result<Vec<RuneStyle>, String> {
+pub async fn list_all_styles(client: &Client) -> Result<Vec<RuneStyle>, LcuError> {
     let path = "/lol-perks/v1/styles";
     lcu_get(client, path).await
 }
 
 /// 获取所有符文详细信息
 /// 对应 LCU API: /lol-perks/v1/perks
-pub async fn list_all_perks(client: &Client) -> Result<Vec<Perk>, String> {
+pub async fn list_all_perks(client: &Client) -> Result<Vec<Perk>, LcuError> {
     let path = "/lol-perks/v1/perks";
     lcu_get(client, path).await
 }
 
 /// 获取符文图标资源
 /// 对应 LCU API: GET /lol-game-data/assets/v1/perk-images/...
-pub async fn get_perk_icon(client: &Client, icon_path: &str) -> Result<Vec<u8>, String> {
+pub async fn get_perk_icon(client: &Client, icon_path: &str) -> Result<Vec<u8>, LcuError> {
     // 确保路径以 / 开头
     let path = if icon_path.starts_with('/') {
         icon_path.to_string()
@@ -33,23 +38,14 @@ pub async fn get_perk_icon(client: &Client, icon_path: &str) -> Result<Vec<u8>,
     };
 
     let response = lcu_request_raw(client, reqwest::Method::GET, &path, None).await?;
-
-    if !response.status().is_success() {
-        return Err(format!("获取图标失败，状态码: {}", response.status()));
-    }
-
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("读取图片数据失败: {}", e))?;
-    Ok(bytes.to_vec())
+    Ok(response.body)
 }
 
 // 以下内容为原 build_application.rs 全部内容，粘贴至此
 /// 获取当前所有符文页面
-pub async fn get_rune_pages(client: &Client) -> Result<Vec<RunePage>, String> {
+pub async fn get_rune_pages(client: &Client) -> Result<Vec<RunePage>, LcuError> {
     log::info!("🔧 开始获取符文页面列表");
-    let result: Result<Vec<RunePage>, String> = lcu_get(client, "/lol-perks/v1/pages").await;
+    let result: Result<Vec<RunePage>, LcuError> = lcu_get(client, "/lol-perks/v1/pages").await;
     match &result {
         Ok(pages) => log::info!("🔧 成功获取到 {} 个符文页面", pages.len()),
         Err(e) => log::error!("🔧 获取符文页面失败: {}", e),
@@ -57,8 +53,13 @@ pub async fn get_rune_pages(client: &Client) -> Result<Vec<RunePage>, String> {
     result
 }
 
+/// 获取符文页库存（可创建的符文页数量）
+pub async fn get_perk_inventory(client: &Client) -> Result<PerkInventory, LcuError> {
+    lcu_get(client, "/lol-perks/v1/inventory").await
+}
+
 /// 获取当前活跃的符文页面
-pub async fn get_current_rune_page(client: &Client) -> Result<Option<RunePage>, String> {
+pub async fn get_current_rune_page(client: &Client) -> Result<Option<RunePage>, LcuError> {
     let pages: Vec<RunePage> = get_rune_pages(client).await?;
     Ok(pages.into_iter().find(|page| page.current))
 }
@@ -70,7 +71,7 @@ pub async fn create_rune_page(
     primary_style_id: i32,
     sub_style_id: i32,
     selected_perk_ids: Vec<i32>,
-) -> Result<RunePage, String> {
+) -> Result<RunePage, LcuError> {
     log::info!("🔧 开始创建符文页面: {}", name);
     log::info!("🔧 主系ID: {}, 副系ID: {}", primary_style_id, sub_style_id);
     log::info!("🔧 符文IDs: {:?}", selected_perk_ids);
@@ -82,11 +83,11 @@ pub async fn create_rune_page(
         selected_perk_ids,
     };
 
-    let body =
-        serde_json::to_value(request).map_err(|e| format!("序列化创建符文页面请求失败: {}", e))?;
+    let body = serde_json::to_value(request)
+        .map_err(|e| LcuError::other(format!("序列化创建符文页面请求失败: {}", e)))?;
 
     log::info!("🔧 发送创建符文页面请求到: /lol-perks/v1/pages");
-    let result: Result<RunePage, String> = lcu_post(client, "/lol-perks/v1/pages", body).await;
+    let result: Result<RunePage, LcuError> = lcu_post(client, "/lol-perks/v1/pages", body).await;
     match &result {
         Ok(page) => log::info!("🔧 成功创建符文页面: {}", page.name),
         Err(e) => log::error!("🔧 创建符文页面失败: {}", e),
@@ -95,9 +96,9 @@ pub async fn create_rune_page(
 }
 
 /// 删除指定的符文页面
-pub async fn delete_rune_page(client: &Client, page_id: i64) -> Result<(), String> {
+pub async fn delete_rune_page(client: &Client, page_id: i64) -> Result<(), LcuError> {
     log::info!("🔧 开始删除符文页面: {}", page_id);
-    let result: Result<(), String> =
+    let result: Result<(), LcuError> =
         lcu_delete(client, &format!("/lol-perks/v1/pages/{}", page_id)).await;
     match &result {
         Ok(_) => log::info!("🔧 成功删除符文页面: {}", page_id),
@@ -107,56 +108,212 @@ pub async fn delete_rune_page(client: &Client, page_id: i64) -> Result<(), Strin
 }
 
 /// 应用符文配置到游戏中
-/// 采用先删除后创建的策略，确保符文页面正确应用
+/// 只覆盖 Nidalee 创建的符文页：先备份全部符文页，再删除后创建，确保新页面成为当前页；
+/// 没有可覆盖的页面时在符文页数量上限内新建，不会删除玩家自己的符文页
 pub async fn apply_rune_build(
     client: &Client,
     champion_name: &str,
     primary_style_id: i32,
     sub_style_id: i32,
     selected_perk_ids: Vec<i32>,
-) -> Result<String, String> {
-    // 1. 获取当前所有符文页面
-    let pages: Vec<RunePage> = get_rune_pages(client).await?;
+) -> Result<String, LcuError> {
+    // 1. 创建前先校验符文，避免删除旧页面后才被客户端拒绝；无法获取符文数据时同样不删除
+    let selection = RuneSelection {
+        primary_style_id,
+        sub_style_id,
+        selected_perk_ids,
+    };
+    let validation = validator::check_rune_selection(client, &selection, false, &[])
+        .await
+        .map_err(|e| {
+            log::warn!("获取符文数据失败，无法校验符文配置: {}", e);
+            e
+        })?;
+    if !validation.valid {
+        return Err(LcuError::other(format!(
+            "符文配置无效: {}",
+            validator::describe(&validation.problems)
+        )));
+    }
 
-    // 2. 找到要删除的符文页面ID
-    let mut delete_id = 0i64;
+    // 2. 获取当前所有符文页面
+    let pages: Vec<RunePage> = get_rune_pages(client).await?;
 
-    // 优先删除当前使用的符文页
-    for page in &pages {
-        if page.current {
-            delete_id = page.id;
-            break;
-        }
-    }
+    // 3. 找到可覆盖的 Nidalee 符文页，优先当前使用的
+    let target = pages
+        .iter()
+        .filter(|page| page.is_deletable && page.name.starts_with(NIDALEE_PAGE_PREFIX))
+        .min_by_key(|page| !page.current);
 
-    // 如果当前页面不可删除，找一个可删除的页面
-    if delete_id == 0 {
-        for page in &pages {
-            if page.is_deletable {
-                delete_id = page.id;
-                break;
+    match target {
+        // 4. 删除前先备份全部符文页，备份失败时不删除
+        Some(page) => {
+            backup::backup_pages(&pages)?;
+            if let Err(e) = delete_rune_page(client, page.id).await {
+                // 删除失败时记录但不阻止创建新页面
+                log::warn!("删除符文页面 {} 失败: {}", page.id, e);
             }
         }
-    }
-
-    // 3. 删除旧的符文页面
-    if delete_id > 0 {
-        if let Err(e) = delete_rune_page(client, delete_id).await {
-            // 删除失败时记录但不阻止创建新页面
-            log::warn!("删除符文页面 {} 失败: {}", delete_id, e);
+        // 没有可覆盖的页面时检查符文页数量上限
+        None => {
+            let custom_count = pages.iter().filter(|page| page.is_deletable).count() as i32;
+            match get_perk_inventory(client).await {
+                Ok(inventory) if custom_count >= inventory.owned_page_count => {
+                    return Err(LcuError::other(format!(
+                        "符文页已达上限 ({}/{})，且没有可覆盖的 Nidalee 符文页，请先删除一个符文页",
+                        custom_count, inventory.owned_page_count
+                    )));
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("获取符文页数量上限失败，直接尝试创建: {}", e),
+            }
         }
     }
 
-    // 4. 创建新的符文页面
-    let page_name = format!("Nidalee : {}", champion_name);
+    // 5. 创建新的符文页面
+    let page_name = format!("{} {}", NIDALEE_PAGE_PREFIX, champion_name);
     let new_page = create_rune_page(
         client,
         &page_name,
-        primary_style_id,
-        sub_style_id,
-        selected_perk_ids,
+        selection.primary_style_id,
+        selection.sub_style_id,
+        selection.selected_perk_ids,
     )
     .await?;
 
     Ok(format!("成功创建符文页面: {}", new_page.name))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+    use reqwest::Method;
+
+    fn ahri_perks() -> Vec<i32> {
+        vec![8214, 8226, 8210, 8237, 8345, 8347, 5008, 5008, 5002]
+    }
+
+    #[tokio::test]
+    async fn apply_rune_build_replaces_current_page() {
+        let mock = MockLcu::start().await;
+        let perks = ahri_perks();
+
+        let message = apply_rune_build(&mock.client(), "Ahri", 8200, 8300, perks.clone())
+            .await
+            .unwrap();
+        assert_eq!(message, "成功创建符文页面: Nidalee : Ahri");
+
+        // 当前页可删除，应先删除当前页
+        let deletes = mock.requests_to(Method::DELETE, "/lol-perks/v1/pages/");
+        assert_eq!(deletes.len(), 1);
+        assert_eq!(deletes[0].path, "/lol-perks/v1/pages/1620001");
+
+        let creates = mock.requests_to(Method::POST, "/lol-perks/v1/pages");
+        assert_eq!(creates.len(), 1);
+        let body = creates[0].body.as_ref().unwrap();
+        assert_eq!(body["name"], "Nidalee : Ahri");
+        assert_eq!(body["primaryStyleId"], 8200);
+        assert_eq!(body["subStyleId"], 8300);
+        assert_eq!(body["selectedPerkIds"], serde_json::json!(perks));
+    }
+
+    #[tokio::test]
+    async fn apply_rune_build_keeps_user_pages_and_respects_limit() {
+        let mock = MockLcu::start().await;
+        mock.respond(
+            Method::GET,
+            "/lol-perks/v1/pages",
+            MockResponse::json(serde_json::json!([{
+                "id": 1620002, "name": "我的中单", "current": true, "isEditable": true,
+                "isDeletable": true, "isValid": true, "primaryStyleId": 8100,
+                "subStyleId": 8200, "selectedPerkIds": [8112]
+            }])),
+        );
+        mock.respond(
+            Method::GET,
+            "/lol-perks/v1/inventory",
+            MockResponse::json(serde_json::json!({ "ownedPageCount": 1 })),
+        );
+
+        // 符文页已满且没有 Nidalee 符文页：不删除玩家的符文页
+        let result = apply_rune_build(&mock.client(), "Ahri", 8200, 8300, ahri_perks()).await;
+        assert!(result.unwrap_err().to_string().contains("符文页已达上限"));
+
+        // 还有空位：直接新建
+        mock.respond(
+            Method::GET,
+            "/lol-perks/v1/inventory",
+            MockResponse::json(serde_json::json!({ "ownedPageCount": 2 })),
+        );
+        apply_rune_build(&mock.client(), "Ahri", 8200, 8300, ahri_perks())
+            .await
+            .unwrap();
+
+        assert!(mock
+            .requests_to(Method::DELETE, "/lol-perks/v1/pages/")
+            .is_empty());
+        assert_eq!(
+            mock.requests_to(Method::POST, "/lol-perks/v1/pages").len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_rune_build_still_creates_page_when_delete_fails() {
+        let mock = MockLcu::start().await;
+        mock.respond(
+            Method::DELETE,
+            "/lol-perks/v1/pages/*",
+            crate::lcu::mock::MockResponse::error(400, "Page is not deletable"),
+        );
+
+        let result = apply_rune_build(&mock.client(), "Ahri", 8200, 8300, ahri_perks()).await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            mock.requests_to(Method::POST, "/lol-perks/v1/pages").len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_rune_build_is_rejected_before_deleting_pages() {
+        let mock = MockLcu::start().await;
+        // 8128 黑暗收割是主宰系基石，不能用在巫术系
+        let mut perks = ahri_perks();
+        perks[0] = 8128;
+
+        let err = apply_rune_build(&mock.client(), "Ahri", 8200, 8300, perks)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("符文配置无效"));
+        assert!(mock
+            .requests_to(Method::DELETE, "/lol-perks/v1/pages/")
+            .is_empty());
+        assert!(mock
+            .requests_to(Method::POST, "/lol-perks/v1/pages")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn apply_rune_build_fails_when_styles_are_unavailable() {
+        let mock = MockLcu::start().await;
+        mock.respond(
+            Method::GET,
+            "/lol-perks/v1/styles",
+            MockResponse::error(500, "Internal Server Error"),
+        );
+
+        let result = apply_rune_build(&mock.client(), "Ahri", 8200, 8300, ahri_perks()).await;
+
+        assert_eq!(result.unwrap_err().status(), Some(500));
+        assert!(mock
+            .requests_to(Method::DELETE, "/lol-perks/v1/pages/")
+            .is_empty());
+        assert!(mock
+            .requests_to(Method::POST, "/lol-perks/v1/pages")
+            .is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+//! 符文选择校验
+//!
+//! 根据客户端的符文数据（/lol-perks/v1/styles、/lol-perks/v1/perks）检查数据源返回的符文：
+//! 主副系搭配、基石、主系每行的符文、副系两个符文不能在同一行以及属性碎片。
+//! 修复时每个位置优先保留原符文，其次按数据源中更常用的符文页顺序选择有效符文，最后使用该行的第一个符文。
+use crate::lcu::error::LcuError;
+use crate::lcu::perks::service::{list_all_perks, list_all_styles};
+use crate::lcu::types::{
+    Perk, RuneProblem, RuneProblemKind, RuneSelection, RuneStyle, RuneValidation, Slot,
+};
+use reqwest::Client;
+
+const PAGE_SIZE: usize = 9;
+const KEYSTONE: &str = "kKeyStone";
+const REGULAR: &str = "kMixedRegularSplashable";
+const STAT_MOD: &str = "kStatMod";
+
+fn find_style(styles: &[RuneStyle], style_id: i32) -> Option<&RuneStyle> {
+    styles.iter().find(|style| style.id == style_id as i64)
+}
+
+fn slots<'a>(style: &'a RuneStyle, slot_type: &str) -> Vec<&'a Slot> {
+    style
+        .slots
+        .iter()
+        .filter(|slot| slot.r#type == slot_type)
+        .collect()
+}
+
+fn in_slot(slot: Option<&&Slot>, perk_id: i32) -> bool {
+    slot.is_some_and(|slot| slot.perks.contains(&(perk_id as i64)))
+}
+
+/// 符文所在的行
+fn row_of(rows: &[&Slot], perk_id: i32) -> Option<usize> {
+    rows.iter()
+        .position(|slot| slot.perks.contains(&(perk_id as i64)))
+}
+
+fn sub_style_allowed(primary: &RuneStyle, styles: &[RuneStyle], sub_style_id: i32) -> bool {
+    sub_style_id as i64 != primary.id
+        && primary.allowed_sub_styles.contains(&(sub_style_id as i64))
+        && find_style(styles, sub_style_id).is_some()
+}
+
+/// 检查符文选择，返回发现的问题（为空表示有效）
+pub fn validate(
+    styles: &[RuneStyle],
+    perks: &[Perk],
+    selection: &RuneSelection,
+) -> Vec<RuneProblem> {
+    let mut problems = Vec::new();
+    let mut push = |kind, index: Option<usize>, perk_id: Option<i32>, message: String| {
+        problems.push(RuneProblem {
+            kind,
+            index,
+            perk_id,
+            message,
+        })
+    };
+    let perk_name = |id: i32| {
+        perks
+            .iter()
+            .find(|perk| perk.id == id as i64)
+            .map(|perk| perk.name.clone())
+    };
+
+    let Some(primary) = find_style(styles, selection.primary_style_id) else {
+        push(
+            RuneProblemKind::UnknownStyle,
+            None,
+            None,
+            format!("主系 {} 不存在", selection.primary_style_id),
+        );
+        return problems;
+    };
+    let sub = find_style(styles, selection.sub_style_id)
+        .filter(|_| sub_style_allowed(primary, styles, selection.sub_style_id));
+    if sub.is_none() {
+        push(
+            RuneProblemKind::InvalidSubStyle,
+            None,
+            None,
+            format!(
+                "副系 {} 不能搭配主系 {}",
+                selection.sub_style_id, primary.name
+            ),
+        );
+    }
+
+    let ids = &selection.selected_perk_ids;
+    if ids.len() != PAGE_SIZE {
+        push(
+            RuneProblemKind::PerkCount,
+            None,
+            None,
+            format!("需要 {} 个符文，实际为 {} 个", PAGE_SIZE, ids.len()),
+        );
+    }
+
+    let keystone = slots(primary, KEYSTONE);
+    let primary_rows = slots(primary, REGULAR);
+    let shards = slots(primary, STAT_MOD);
+    let sub_rows = sub.map(|sub| slots(sub, REGULAR)).unwrap_or_default();
+    let mut used_sub_rows = Vec::new();
+
+    for (index, &id) in ids.iter().enumerate().take(PAGE_SIZE) {
+        let Some(name) = perk_name(id) else {
+            push(
+                RuneProblemKind::UnknownPerk,
+                Some(index),
+                Some(id),
+                format!("符文 {} 不存在（可能已在版本更新中移除）", id),
+            );
+            continue;
+        };
+        match index {
+            0 if !in_slot(keystone.first(), id) => push(
+                RuneProblemKind::InvalidKeystone,
+                Some(index),
+                Some(id),
+                format!("{} 不是 {} 的基石符文", name, primary.name),
+            ),
+            1..=3 if !in_slot(primary_rows.get(index - 1), id) => push(
+                RuneProblemKind::WrongSlot,
+                Some(index),
+                Some(id),
+                format!("{} 不在 {} 的第 {} 行", name, primary.name, index),
+            ),
+            4 | 5 if sub.is_some() => match row_of(&sub_rows, id) {
+                None => push(
+                    RuneProblemKind::WrongSlot,
+                    Some(index),
+                    Some(id),
+                    format!("{} 不属于副系", name),
+                ),
+                Some(row) if used_sub_rows.contains(&row) => push(
+                    RuneProblemKind::DuplicateSecondarySlot,
+                    Some(index),
+                    Some(id),
+                    format!("{} 与另一个副系符文在同一行", name),
+                ),
+                Some(row) => used_sub_rows.push(row),
+            },
+            6..=8 if !in_slot(shards.get(index - 6), id) => push(
+                RuneProblemKind::InvalidShard,
+                Some(index),
+                Some(id),
+                format!("{} 不是第 {} 行的属性碎片", name, index - 5),
+            ),
+            _ => {}
+        }
+    }
+    problems
+}
+
+/// 修复符文选择，主系不存在或修复后仍然无效时返回 None
+///
+/// popular 为同一数据源中其他的符文选择，按常用程度排列
+pub fn repair(
+    styles: &[RuneStyle],
+    perks: &[Perk],
+    selection: &RuneSelection,
+    popular: &[Vec<i32>],
+) -> Option<RuneSelection> {
+    let primary = find_style(styles, selection.primary_style_id)?;
+    let sub_style_id = if sub_style_allowed(primary, styles, selection.sub_style_id) {
+        selection.sub_style_id
+    } else {
+        std::iter::once(primary.default_sub_style)
+            .chain(primary.allowed_sub_styles.iter().copied())
+            .map(|id| id as i32)
+            .find(|&id| sub_style_allowed(primary, styles, id))?
+    };
+    let sub = find_style(styles, sub_style_id)?;
+
+    let ids = &selection.selected_perk_ids;
+    let known = |id: i32| perks.iter().any(|perk| perk.id == id as i64);
+    let candidates = |index: usize| {
+        ids.get(index)
+            .copied()
+            .into_iter()
+            .chain(popular.iter().flatten().copied())
+    };
+    let pick = |slot: &Slot, index: usize| {
+        candidates(index)
+            .chain(slot.perks.iter().map(|&id| id as i32))
+            .find(|&id| slot.perks.contains(&(id as i64)) && known(id))
+    };
+
+    let mut repaired = Vec::with_capacity(PAGE_SIZE);
+    repaired.push(pick(slots(primary, KEYSTONE).first()?, 0)?);
+    for (row, slot) in slots(primary, REGULAR).iter().take(3).enumerate() {
+        repaired.push(pick(slot, row + 1)?);
+    }
+
+    // 副系：两个符文来自不同的行，按行排序
+    let sub_rows = slots(sub, REGULAR);
+    let mut secondary: Vec<(usize, i32)> = Vec::new();
+    let secondary_candidates = ids
+        .iter()
+        .skip(4)
+        .take(2)
+        .copied()
+        .chain(popular.iter().flatten().copied())
+        .chain(
+            sub_rows
+                .iter()
+                .flat_map(|slot| slot.perks.iter().map(|&id| id as i32)),
+        );
+    for id in secondary_candidates {
+        if secondary.len() == 2 {
+            break;
+        }
+        if let Some(row) = row_of(&sub_rows, id) {
+            if known(id) && secondary.iter().all(|&(used, _)| used != row) {
+                secondary.push((row, id));
+            }
+        }
+    }
+    secondary.sort();
+    repaired.extend(secondary.into_iter().map(|(_, id)| id));
+
+    for (row, slot) in slots(primary, STAT_MOD).iter().take(3).enumerate() {
+        repaired.push(pick(slot, row + 6)?);
+    }
+
+    let repaired = RuneSelection {
+        primary_style_id: selection.primary_style_id,
+        sub_style_id,
+        selected_perk_ids: repaired,
+    };
+    validate(styles, perks, &repaired)
+        .is_empty()
+        .then_some(repaired)
+}
+
+/// 问题列表的简短描述
+pub fn describe(problems: &[RuneProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| problem.message.as_str())
+        .collect::<Vec<_>>()
+        .join("；")
+}
+
+/// 使用客户端的符文数据校验，repair 为 true 且存在问题时尝试修复
+pub async fn check_rune_selection(
+    client: &Client,
+    selection: &RuneSelection,
+    repair_invalid: bool,
+    popular: &[Vec<i32>],
+) -> Result<RuneValidation, LcuError> {
+    let styles = list_all_styles(client).await?;
+    let perks = list_all_perks(client).await?;
+    let problems = validate(&styles, &perks, selection);
+    let repaired = if repair_invalid && !problems.is_empty() {
+        repair(&styles, &perks, selection, popular)
+    } else {
+        None
+    };
+    Ok(RuneValidation {
+        valid: problems.is_empty(),
+        problems,
+        repaired,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(slot_type: &str, perks: &[i64]) -> Slot {
+        Slot {
+            perks: perks.to_vec(),
+            slot_label: String::new(),
+            r#type: slot_type.to_string(),
+        }
+    }
+
+    fn style(id: i64, name: &str, sub_styles: &[i64], rows: [&[i64]; 4]) -> RuneStyle {
+        RuneStyle {
+            id,
+            name: name.to_string(),
+            allowed_sub_styles: sub_styles.to_vec(),
+            default_sub_style: sub_styles[0],
+            slots: vec![
+                slot(KEYSTONE, rows[0]),
+                slot(REGULAR, rows[1]),
+                slot(REGULAR, rows[2]),
+                slot(REGULAR, rows[3]),
+                slot(STAT_MOD, &[5008, 5005, 5007]),
+                slot(STAT_MOD, &[5008, 5010, 5001]),
+                slot(STAT_MOD, &[5011, 5013, 5001]),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn data() -> (Vec<RuneStyle>, Vec<Perk>) {
+        let styles = vec![
+            style(
+                8100,
+                "主宰",
+                &[8200, 8300],
+                [&[8112, 8128], &[8126, 8139], &[8138, 8120], &[8135, 8105]],
+            ),
+            style(
+                8200,
+                "巫术",
+                &[8100, 8300],
+                [&[8214, 8229], &[8224, 8226], &[8210, 8234], &[8237, 8236]],
+            ),
+        ];
+        let perks = styles
+            .iter()
+            .flat_map(|s| s.slots.iter().flat_map(|slot| slot.perks.clone()))
+            .map(|id| Perk {
+                id,
+                name: id.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        (styles, perks)
+    }
+
+    #[test]
+    fn validate_reports_problems_and_repair_prefers_popular_perks() {
+        let (styles, perks) = data();
+        let valid = RuneSelection {
+            primary_style_id: 8100,
+            sub_style_id: 8200,
+            selected_perk_ids: vec![8112, 8139, 8138, 8135, 8226, 8210, 5008, 5008, 5001],
+        };
+        assert!(validate(&styles, &perks, &valid).is_empty());
+
+        // 8143 已移除、8126 放错行、副系同一行、碎片放错行
+        let stale = RuneSelection {
+            selected_perk_ids: vec![8112, 8143, 8126, 8135, 8226, 8224, 5008, 5008, 5005],
+            ..valid.clone()
+        };
+        let problems = validate(&styles, &perks, &stale);
+        let kinds: Vec<_> = problems.iter().map(|p| (p.kind, p.index)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (RuneProblemKind::UnknownPerk, Some(1)),
+                (RuneProblemKind::WrongSlot, Some(2)),
+                (RuneProblemKind::DuplicateSecondarySlot, Some(5)),
+                (RuneProblemKind::InvalidShard, Some(8)),
+            ]
+        );
+
+        let popular = vec![vec![8128, 8126, 8120, 8105, 8237, 8226, 5005, 5010, 5013]];
+        let repaired = repair(&styles, &perks, &stale, &popular).unwrap();
+        assert_eq!(
+            repaired.selected_perk_ids,
+            [8112, 8126, 8120, 8135, 8226, 8237, 5008, 5008, 5013]
+        );
+        assert!(validate(&styles, &perks, &repaired).is_empty());
+    }
+}
@@ -0,0 +1,12 @@
+use crate::lcu::error::LcuError;
+use crate::{http_client, lcu};
+
+/// 检测当前选人或对局中的开黑组，min_games 为最少同队场次（默认 2）
+#[tauri::command]
+pub async fn detect_premades(
+    min_games: Option<u32>,
+) -> Result<lcu::types::PremadeReport, LcuError> {
+    let client = http_client::get_lcu_client();
+    let min_games = min_games.map_or(lcu::premade::service::DEFAULT_MIN_GAMES, |n| n as usize);
+    lcu::premade::service::detect_premades(client, min_games).await
+}
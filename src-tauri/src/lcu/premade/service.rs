@@ -0,0 +1,332 @@
+//! 开黑组检测
+//!
+//! 查询选人或对局中所有玩家的最近对局，对局中（包括对局详情里的其他玩家）同一队伍出现的
+//! 会话内玩家两两计数，同队达到 min_games 场的两人相连，连通的玩家即为一个开黑组。
+//! 两人的可信度为同队场次 / CONFIDENT_GAMES（最大为 1），组的可信度为组内相连两人的平均值。
+use crate::lcu::champ_select::lookup;
+use crate::lcu::champ_select::service::get_champ_select_session;
+use crate::lcu::error::LcuError;
+use crate::lcu::gameflow::service::{get_gameflow_phase, get_gameflow_session};
+use crate::lcu::request::lcu_get;
+use crate::lcu::types::{PremadeGroup, PremadeMember, PremadeReport};
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// 同队达到该场次时可信度为 1
+const CONFIDENT_GAMES: f32 = 5.0;
+/// 默认的最少同队场次
+pub const DEFAULT_MIN_GAMES: usize = 2;
+
+/// 会话中的玩家
+#[derive(Debug, Clone, Default)]
+pub struct SessionPlayer {
+    pub puuid: String,
+    pub display_name: Option<String>,
+    pub champion_id: Option<i32>,
+    /// 是否为己方
+    pub ally: bool,
+}
+
+/// 组成中的开黑组：玩家下标、同队对局和相连两人的可信度
+#[derive(Default)]
+struct GroupParts {
+    members: Vec<usize>,
+    game_ids: Vec<u64>,
+    confidences: Vec<f32>,
+}
+
+/// 并查集查找
+fn find(parent: &mut [usize], i: usize) -> usize {
+    if parent[i] != i {
+        parent[i] = find(parent, parent[i]);
+    }
+    parent[i]
+}
+
+/// 根据每名玩家的最近对局检测开黑组，games 与 players 一一对应
+pub fn detect_groups(
+    players: &[SessionPlayer],
+    games: &[Vec<Value>],
+    min_games: usize,
+) -> Vec<PremadeGroup> {
+    let index: HashMap<&str, usize> = players
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.puuid.as_str(), i))
+        .collect();
+
+    // game_id -> (玩家 -> 队伍)，对局详情中的其他玩家也会计入
+    let mut teams: HashMap<u64, HashMap<usize, i64>> = HashMap::new();
+    let mut creation: HashMap<u64, i64> = HashMap::new();
+    for game in games.iter().flatten() {
+        let Some(game_id) = game["gameId"].as_u64() else {
+            continue;
+        };
+        creation.insert(game_id, game["gameCreation"].as_i64().unwrap_or(0));
+        let participants = game["participants"].as_array().cloned().unwrap_or_default();
+        for identity in game["participantIdentities"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            let Some(&player) = identity["player"]["puuid"]
+                .as_str()
+                .and_then(|puuid| index.get(puuid))
+            else {
+                continue;
+            };
+            if let Some(team_id) = participants
+                .iter()
+                .find(|p| p["participantId"] == identity["participantId"])
+                .and_then(|p| p["teamId"].as_i64())
+            {
+                teams.entry(game_id).or_default().insert(player, team_id);
+            }
+        }
+    }
+
+    // 当前同一方的两人在历史对局中同队的场次
+    let mut pairs: BTreeMap<(usize, usize), Vec<u64>> = BTreeMap::new();
+    for (&game_id, members) in &teams {
+        for (&a, team_a) in members {
+            for (&b, team_b) in members {
+                if a < b && team_a == team_b && players[a].ally == players[b].ally {
+                    pairs.entry((a, b)).or_default().push(game_id);
+                }
+            }
+        }
+    }
+    let edges: Vec<((usize, usize), Vec<u64>)> = pairs
+        .into_iter()
+        .filter(|(_, shared)| shared.len() >= min_games.max(1))
+        .collect();
+
+    let mut parent: Vec<usize> = (0..players.len()).collect();
+    for ((a, b), _) in &edges {
+        let (root_a, root_b) = (find(&mut parent, *a), find(&mut parent, *b));
+        parent[root_b] = root_a;
+    }
+
+    let mut groups: BTreeMap<usize, GroupParts> = BTreeMap::new();
+    for ((a, b), shared) in &edges {
+        let root = find(&mut parent, *a);
+        let group = groups.entry(root).or_default();
+        for member in [*a, *b] {
+            if !group.members.contains(&member) {
+                group.members.push(member);
+            }
+        }
+        group.game_ids.extend(shared);
+        group
+            .confidences
+            .push((shared.len() as f32 / CONFIDENT_GAMES).min(1.0));
+    }
+
+    let mut result: Vec<PremadeGroup> = groups
+        .into_values()
+        .map(|group| {
+            let GroupParts {
+                mut members,
+                mut game_ids,
+                confidences,
+            } = group;
+            members.sort();
+            game_ids.sort_by_key(|id| std::cmp::Reverse((creation[id], *id)));
+            game_ids.dedup();
+            PremadeGroup {
+                ally: players[members[0]].ally,
+                members: members
+                    .iter()
+                    .map(|&i| PremadeMember {
+                        puuid: players[i].puuid.clone(),
+                        display_name: players[i].display_name.clone(),
+                        champion_id: players[i].champion_id,
+                    })
+                    .collect(),
+                game_ids,
+                confidence: confidences.iter().sum::<f32>() / confidences.len() as f32,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.ally
+            .cmp(&a.ally)
+            .then(b.confidence.total_cmp(&a.confidence))
+    });
+    result
+}
+
+/// 选人阶段的双方玩家（对方身份隐藏时只有己方）
+async fn champ_select_players(client: &Client) -> Result<Vec<SessionPlayer>, LcuError> {
+    let session = get_champ_select_session(client).await?;
+    let puuids = lookup::puuids_by_cell(
+        client,
+        session.my_team.iter().chain(session.their_team.iter()),
+    )
+    .await;
+    let players = session
+        .my_team
+        .iter()
+        .map(|p| (p, true))
+        .chain(session.their_team.iter().map(|p| (p, false)))
+        .filter_map(|(p, ally)| {
+            Some(SessionPlayer {
+                puuid: puuids.get(&p.cell_id)?.clone(),
+                display_name: p.display_name.clone(),
+                champion_id: lookup::player_champion_id(p),
+                ally,
+            })
+        })
+        .collect();
+    Ok(players)
+}
+
+/// 对局中的双方玩家（/lol-gameflow/v1/session 的 gameData.teamOne / teamTwo）
+async fn in_game_players(client: &Client) -> Result<Vec<SessionPlayer>, LcuError> {
+    let session = get_gameflow_session(client).await?;
+    let me: Value = lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
+    let my_puuid = me["puuid"].as_str().unwrap_or_default();
+    let team = |key: &str| {
+        session["gameData"][key]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    };
+    let (team_one, team_two) = (team("teamOne"), team("teamTwo"));
+    // 观战等情况下自己不在对局中，以 teamOne 为己方
+    let me_in_two = team_two
+        .iter()
+        .any(|p| p["puuid"].as_str() == Some(my_puuid));
+
+    let player = |p: &Value, ally: bool| {
+        let puuid = p["puuid"].as_str().filter(|puuid| !puuid.is_empty())?;
+        let display_name = match (p["gameName"].as_str(), p["tagLine"].as_str()) {
+            (Some(name), Some(tag)) if !name.is_empty() => Some(format!("{}#{}", name, tag)),
+            _ => p["summonerName"].as_str().map(str::to_string),
+        };
+        Some(SessionPlayer {
+            puuid: puuid.to_string(),
+            display_name,
+            champion_id: p["championId"]
+                .as_i64()
+                .filter(|&id| id > 0)
+                .map(|id| id as i32),
+            ally,
+        })
+    };
+    Ok(team_one
+        .iter()
+        .filter_map(|p| player(p, !me_in_two))
+        .chain(team_two.iter().filter_map(|p| player(p, me_in_two)))
+        .collect())
+}
+
+/// 检测当前选人或对局中的开黑组
+pub async fn detect_premades(client: &Client, min_games: usize) -> Result<PremadeReport, LcuError> {
+    let phase = get_gameflow_phase(client).await?;
+    let players = match phase.as_str() {
+        "ChampSelect" => champ_select_players(client).await?,
+        "InProgress" | "Reconnect" => in_game_players(client).await?,
+        other => {
+            return Err(LcuError::from(format!(
+                "当前阶段 {} 不在选人或对局中",
+                other
+            )))
+        }
+    };
+
+    let puuids: Vec<String> = players.iter().map(|p| p.puuid.clone()).collect();
+    let mut recent = lookup::recent_games_by_puuids(client, &puuids).await;
+    let games: Vec<Vec<Value>> = puuids
+        .iter()
+        .map(|puuid| recent.remove(puuid).unwrap_or_default())
+        .collect();
+
+    let groups = detect_groups(&players, &games, min_games);
+    log::info!(
+        "[开黑检测] {} 阶段 {} 名玩家，发现 {} 组",
+        phase,
+        players.len(),
+        groups.len()
+    );
+    Ok(PremadeReport {
+        phase,
+        players_checked: players.len() as i32,
+        groups,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 一场对局，members 为 (puuid, teamId)
+    fn game(game_id: u64, members: &[(&str, i64)]) -> Value {
+        json!({
+            "gameId": game_id,
+            "gameCreation": 1_730_000_000_000u64 + game_id,
+            "participantIdentities": members.iter().enumerate().map(|(i, (puuid, _))| json!({
+                "participantId": i + 1,
+                "player": { "puuid": puuid }
+            })).collect::<Vec<_>>(),
+            "participants": members.iter().enumerate().map(|(i, (_, team))| json!({
+                "participantId": i + 1,
+                "teamId": team
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    fn player(puuid: &str, ally: bool) -> SessionPlayer {
+        SessionPlayer {
+            puuid: puuid.to_string(),
+            display_name: Some(format!("{}#1", puuid)),
+            ally,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_players_seen_on_the_same_team() {
+        let players = [
+            player("a", true),
+            player("b", true),
+            player("f", true),
+            player("c", false),
+            player("d", false),
+        ];
+        let games = vec![
+            // a 的战绩：11、12 与 b 同队（12 为对局详情，b 自己的战绩里没有），13 与 c 同队
+            vec![
+                game(11, &[("a", 100)]),
+                game(12, &[("a", 100), ("b", 100), ("c", 200)]),
+                game(13, &[("a", 200), ("c", 200)]),
+                game(14, &[("a", 100), ("c", 100)]),
+            ],
+            vec![
+                game(11, &[("b", 100)]),
+                game(15, &[("b", 200)]),
+                game(16, &[("b", 100)]),
+                game(17, &[("b", 100)]),
+            ],
+            vec![game(15, &[("f", 200)]), game(16, &[("f", 100)])],
+            vec![game(13, &[("c", 200)]), game(18, &[("c", 100)])],
+            vec![game(18, &[("d", 100)]), game(19, &[("d", 200)])],
+        ];
+
+        // 至少 2 场：a-b (11,12)、b-f (15,16) 相连；c-d 只有 18 一场；a-c 现在是对手
+        let groups = detect_groups(&players, &games, 2);
+        assert_eq!(groups.len(), 1);
+        let names: Vec<_> = groups[0].members.iter().map(|m| m.puuid.as_str()).collect();
+        assert_eq!(names, ["a", "b", "f"]);
+        assert!(groups[0].ally);
+        assert_eq!(groups[0].game_ids, [16, 15, 12, 11]);
+        assert!((groups[0].confidence - 0.4).abs() < 1e-6);
+
+        let groups = detect_groups(&players, &games, 1);
+        assert_eq!(groups.len(), 2);
+        assert!(!groups[1].ally);
+        assert_eq!(groups[1].game_ids, [18]);
+    }
+}
@@ -1,11 +1,12 @@
 
+use crate::lcu::error::LcuError;
 use crate::lcu::request::lcu_get;
 use crate::lcu::types::RankedStats;
 use reqwest::Client;
 
 /// 获取当前召唤师的段位统计
 #[allow(dead_code)]
-pub async fn get_current_ranked_stats(client: &Client) -> Result<RankedStats, String> {
+pub async fn get_current_ranked_stats(client: &Client) -> Result<RankedStats, LcuError> {
     lcu_get(client, "/lol-ranked/v1/current-ranked-stats").await
 }
 
@@ -14,7 +15,7 @@ pub async fn get_current_ranked_stats(client: &Client) -> Result<RankedStats, St
 pub async fn get_ranked_stats_by_id(
     client: &Client,
     summoner_id: u64,
-) -> Result<RankedStats, String> {
+) -> Result<RankedStats, LcuError> {
     let url = format!("/lol-ranked/v1/ranked-stats/{}", summoner_id);
     lcu_get(client, &url).await
 }
@@ -1,33 +1,127 @@
 //! LCU HTTP 通用请求工具，支持全局认证、自动重试、统一错误处理、泛型反序列化
-use crate::lcu::auth::service::{ensure_valid_auth_info, refresh_auth_info};
-use base64::{engine::general_purpose, Engine as _};
-use reqwest::{Client, Method, Response};
+//!
+//! 所有方法（GET/POST/PUT/PATCH/DELETE 以及原始字节）都经过同一条请求管线：
+//! 发送 -> 读取完整响应体 -> 按 RetryPolicy 决定是否刷新认证/退避重试
+use crate::lcu::auth::service::{ensure_valid_endpoint, refresh_auth_info};
+use crate::lcu::error::LcuError;
+use crate::lcu::traffic;
+use reqwest::{Client, Method, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-/// 内部通用：带全局认证的 HTTP 请求（返回原始 Response） 如果只想拿原始字节，可用 lcu_request_raw，然后 response.bytes().await
-pub async fn lcu_request_raw(
+/// 请求重试策略
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（含第一次），认证刷新后的重试不计入
+    pub max_attempts: u32,
+    /// 401/403 时强制刷新认证信息并重试一次
+    pub refresh_auth: bool,
+    /// 5xx 时退避重试（仅幂等方法）
+    pub retry_server_errors: bool,
+    /// 连接失败、连接重置时退避重试（仅幂等方法）
+    pub retry_connection_errors: bool,
+    /// 第一次退避的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+    /// 单次退避等待上限
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// 默认策略：刷新认证 + 5xx/连接错误最多重试 2 次
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        refresh_auth: true,
+        retry_server_errors: true,
+        retry_connection_errors: true,
+        base_delay: Duration::from_millis(200),
+        max_delay: Duration::from_secs(2),
+    };
+
+    /// 不做任何重试（轮询等本身会周期执行的场景）
+    #[allow(dead_code)]
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        refresh_auth: false,
+        retry_server_errors: false,
+        retry_connection_errors: false,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    /// 第 attempt 次失败后的等待时间（attempt 从 1 开始）
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << (attempt - 1).min(16);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// 判断某个错误在该策略下是否值得退避重试
+    fn should_retry(&self, method: &Method, err: &LcuError) -> bool {
+        // POST/PATCH 可能已在服务端生效，重试可能造成重复操作
+        if !method.is_idempotent() {
+            return false;
+        }
+        match err {
+            LcuError::HttpStatus { status, .. } => self.retry_server_errors && *status >= 500,
+            LcuError::Transport { .. } => self.retry_connection_errors,
+            _ => false,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::DEFAULT
+    }
+}
+
+/// 已读取完毕的 LCU 响应
+#[derive(Debug, Clone)]
+pub struct LcuResponse {
+    pub status: StatusCode,
+    pub path: String,
+    pub body: Vec<u8>,
+    pub elapsed_ms: u64,
+}
+
+impl LcuResponse {
+    /// 反序列化响应体，空响应体（如 204）视为 null
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, LcuError> {
+        let bytes: &[u8] = if self.body.is_empty() {
+            b"null"
+        } else {
+            &self.body
+        };
+        serde_json::from_slice(bytes).map_err(|e| LcuError::Decode {
+            path: self.path.clone(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// 单次请求：带全局认证发送并读取完整响应体，非 2xx 转换为 LcuError
+async fn send_once(
     client: &Client,
-    method: Method,
+    method: &Method,
     path: &str,
-    body: Option<Value>,
-) -> Result<Response, String> {
-    let auth = ensure_valid_auth_info().ok_or("认证信息不存在，请检查LCU进程或重试")?;
-    let url = format!("https://127.0.0.1:{}{}", auth.app_port, path);
+    body_json: Option<&Value>,
+) -> Result<LcuResponse, LcuError> {
+    let endpoint = ensure_valid_endpoint()
+        .await
+        .ok_or_else(|| LcuError::NotConnected {
+            message: "认证信息不存在，请检查LCU进程或重试".to_string(),
+        })?;
+    let url = endpoint.url(path);
 
     // 记录起始时间
     let start = Instant::now();
     log::info!("[LCU] {} {}", method, url);
 
-    let auth_string = format!("riot:{}", auth.remoting_auth_token);
-    let base64_auth = general_purpose::STANDARD.encode(auth_string.as_bytes());
-
     let builder = client
         .request(method.clone(), &url)
-        .header("Authorization", format!("Basic {}", base64_auth));
+        .header("Authorization", endpoint.authorization());
 
-    let builder = if let Some(ref body) = body {
+    let builder = if let Some(body) = body_json {
         log::debug!("[LCU] 请求体: {}", body);
         builder.json(body)
     } else {
@@ -36,81 +130,173 @@ pub async fn lcu_request_raw(
 
     let response = builder.send().await.map_err(|e| {
         log::error!("[LCU] {} {} 发送失败: {}", method, url, e);
-        format!("请求失败: {}", e)
+        LcuError::from_reqwest(e, path)
     })?;
 
-    let duration = start.elapsed();
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| LcuError::from_reqwest(e, path))?
+        .to_vec();
+    let elapsed_ms = start.elapsed().as_millis() as u64;
     log::info!(
         "[LCU] {} {} -> {} (耗时: {}ms)",
         method,
         url,
-        response.status(),
-        duration.as_millis()
+        status,
+        elapsed_ms
     );
 
-    if !response.status().is_success() {
-        log::warn!(
-            "[LCU] {} {} 返回非成功状态: {} (耗时: {}ms)",
-            method,
-            url,
-            response.status(),
-            duration.as_millis()
+    traffic::service::record(
+        &endpoint, method, path, body_json, status, &body, elapsed_ms,
+    );
+    into_response(method, path, status, body, elapsed_ms)
+}
+
+/// 将响应转换为结果，非 2xx 转换为 LcuError（回放时同样使用）
+fn into_response(
+    method: &Method,
+    path: &str,
+    status: StatusCode,
+    body: Vec<u8>,
+    elapsed_ms: u64,
+) -> Result<LcuResponse, LcuError> {
+    if !status.is_success() {
+        // LCU 会在错误响应体中给出 errorCode/message
+        let body_text = String::from_utf8_lossy(&body);
+        let err = LcuError::from_http_status(
+            status.as_u16(),
+            method.as_str(),
+            path,
+            elapsed_ms,
+            &body_text,
         );
-        return Err(format!("服务器返回错误: {}", response.status()));
+        let body_text = if body_text.is_empty() {
+            "<空>"
+        } else {
+            &body_text
+        };
+        if matches!(err, LcuError::AuthExpired { .. }) {
+            log::warn!(
+                "[LCU] {} {} 认证失败: {} (耗时: {}ms) {}",
+                method,
+                path,
+                status,
+                elapsed_ms,
+                body_text
+            );
+        } else {
+            log::warn!(
+                "[LCU] {} {} 返回非成功状态: {} (耗时: {}ms) 响应体: {}",
+                method,
+                path,
+                status,
+                elapsed_ms,
+                body_text
+            );
+        }
+        return Err(err);
     }
-    Ok(response)
+
+    Ok(LcuResponse {
+        status,
+        path: path.to_string(),
+        body,
+        elapsed_ms,
+    })
 }
 
-/// 自动反序列化为 T，带 401/403 自动刷新重试
-pub async fn lcu_request_json<T: DeserializeOwned>(
+/// 统一请求管线：按策略处理认证刷新、5xx 与连接错误的退避重试
+pub async fn lcu_request_with_policy(
     client: &Client,
     method: Method,
     path: &str,
     body: Option<Value>,
-) -> Result<T, String> {
-    // 最多两次尝试（第一次失败尝试刷新认证后再来一次）
-    for try_num in 0..2 {
-        let response = lcu_request_raw(client, method.clone(), path, body.clone()).await;
-        match response {
-            Ok(resp) => {
-                // 检查 401/403
-                let status = resp.status();
-                if status == 401 || status == 403 {
-                    log::warn!(
-                        "[LCU] {} {} 返回 {}，尝试强制刷新认证({}次)",
-                        method,
-                        path,
-                        status,
-                        try_num + 1
-                    );
-                    // 强制刷新认证
-                    if refresh_auth_info().is_ok() {
-                        continue; // 再试一次
-                    } else {
-                        return Err("强制刷新认证失败，请检查LCU进程".to_string());
-                    }
-                }
-                if status == reqwest::StatusCode::NO_CONTENT {
-                    // 返回默认值
-                    return serde_json::from_str("null")
-                        .map_err(|e| format!("204返回空，解析失败: {}", e));
-                }
-                return resp
-                    .json::<T>()
-                    .await
-                    .map_err(|e| format!("解析响应失败: {}", e));
+    policy: RetryPolicy,
+) -> Result<LcuResponse, LcuError> {
+    // 回放模式下直接返回录制的响应，不访问客户端
+    if let Some(replayed) = traffic::service::replay(&method, path) {
+        let replayed = replayed?;
+        log::info!("[LCU] {} {} -> {} (回放)", method, path, replayed.status);
+        return into_response(
+            &method,
+            path,
+            replayed.status,
+            replayed.body,
+            replayed.elapsed_ms,
+        );
+    }
+
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    let mut auth_refreshed = false;
+    loop {
+        let err = match send_once(client, &method, path, body.as_ref()).await {
+            Ok(resp) => return Ok(resp),
+            Err(e) => e,
+        };
+
+        if let LcuError::AuthExpired { status, .. } = err {
+            if !policy.refresh_auth || auth_refreshed {
+                return Err(err);
             }
-            Err(e) => {
-                // lcu_request_raw 已有日志
-                return Err(e);
+            log::warn!(
+                "[LCU] {} {} 返回 {}，尝试强制刷新认证",
+                method,
+                path,
+                status
+            );
+            // 认证失效说明请求未被处理，非幂等方法也可以安全重试
+            if refresh_auth_info().await.is_err() {
+                return Err(LcuError::NotConnected {
+                    message: "强制刷新认证失败，请检查LCU进程".to_string(),
+                });
             }
+            auth_refreshed = true;
+            continue;
+        }
+
+        if attempt >= max_attempts || !policy.should_retry(&method, &err) {
+            return Err(err);
         }
+        let delay = policy.backoff(attempt);
+        log::warn!(
+            "[LCU] {} {} 失败，{}ms 后重试 ({}/{}): {}",
+            method,
+            path,
+            delay.as_millis(),
+            attempt,
+            max_attempts - 1,
+            err
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
     }
-    Err("多次认证失败，请重启游戏客户端或检查LCU进程".to_string())
+}
+
+/// 带默认重试策略的请求，返回已读取的原始响应（只想拿字节时用 .body）
+pub async fn lcu_request_raw(
+    client: &Client,
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+) -> Result<LcuResponse, LcuError> {
+    lcu_request_with_policy(client, method, path, body, RetryPolicy::default()).await
+}
+
+/// 自动反序列化为 T，带默认重试策略
+pub async fn lcu_request_json<T: DeserializeOwned>(
+    client: &Client,
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+) -> Result<T, LcuError> {
+    lcu_request_raw(client, method, path, body).await?.json()
 }
 
 /// GET 方法，自动反序列化为 T
-pub async fn lcu_get<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T, String> {
+pub async fn lcu_get<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T, LcuError> {
     lcu_request_json(client, Method::GET, path, None).await
 }
 
@@ -119,7 +305,7 @@ pub async fn lcu_post<T: DeserializeOwned>(
     client: &Client,
     path: &str,
     body: Value,
-) -> Result<T, String> {
+) -> Result<T, LcuError> {
     lcu_request_json(client, Method::POST, path, Some(body)).await
 }
 
@@ -129,34 +315,28 @@ pub async fn lcu_put<T: DeserializeOwned>(
     client: &Client,
     path: &str,
     body: Value,
-) -> Result<T, String> {
+) -> Result<T, LcuError> {
     lcu_request_json(client, Method::PUT, path, Some(body)).await
 }
 
 /// DELETE 方法，自动反序列化为 T
-pub async fn lcu_delete<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T, String> {
+pub async fn lcu_delete<T: DeserializeOwned>(client: &Client, path: &str) -> Result<T, LcuError> {
     lcu_request_json(client, Method::DELETE, path, None).await
 }
 
 #[allow(dead_code)]
-pub async fn lcu_post_no_content(client: &Client, path: &str, body: Value) -> Result<(), String> {
+pub async fn lcu_post_no_content(client: &Client, path: &str, body: Value) -> Result<(), LcuError> {
     // 只关心成功，不需要反序列化
-    let response = lcu_request_raw(client, Method::POST, path, Some(body)).await?;
-    if response.status() == reqwest::StatusCode::NO_CONTENT {
-        Ok(())
-    } else if response.status().is_success() {
-        Ok(())
-    } else {
-        Err(format!("服务器返回错误: {}", response.status()))
-    }
+    lcu_request_raw(client, Method::POST, path, Some(body)).await?;
+    Ok(())
 }
-pub async fn lcu_patch_no_content(client: &Client, path: &str, body: Value) -> Result<(), String> {
-    let response = lcu_request_raw(client, Method::PATCH, path, Some(body)).await?;
-    if response.status().is_success() {
-        Ok(())
-    } else {
-        Err(format!("服务器返回错误: {}", response.status()))
-    }
+pub async fn lcu_patch_no_content(
+    client: &Client,
+    path: &str,
+    body: Value,
+) -> Result<(), LcuError> {
+    lcu_request_raw(client, Method::PATCH, path, Some(body)).await?;
+    Ok(())
 }
 
 /// 通用 champ-r HTTP 请求，返回反序列化后的数据
@@ -211,3 +391,48 @@ pub async fn forin_request_json<T: DeserializeOwned>(
         .await
         .map_err(|e| format!("解析响应失败: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+
+    #[tokio::test]
+    async fn auth_failure_keeps_request_details() {
+        let mock = MockLcu::start().await;
+        mock.respond(
+            Method::GET,
+            "/lol-chat/v1/me",
+            MockResponse::error(403, "Forbidden by policy"),
+        );
+
+        let err = lcu_request_with_policy(
+            &mock.client(),
+            Method::GET,
+            "/lol-chat/v1/me",
+            None,
+            RetryPolicy::NONE,
+        )
+        .await
+        .unwrap_err();
+
+        let LcuError::AuthExpired {
+            status,
+            method,
+            path,
+            error_code,
+            message,
+            body,
+            ..
+        } = err
+        else {
+            panic!("403 应返回 AuthExpired: {:?}", err);
+        };
+        assert_eq!(status, 403);
+        assert_eq!(method, "GET");
+        assert_eq!(path, "/lol-chat/v1/me");
+        assert_eq!(error_code.as_deref(), Some("RPC_ERROR"));
+        assert_eq!(message.as_deref(), Some("Forbidden by policy"));
+        assert!(body.unwrap().contains("Forbidden by policy"));
+    }
+}
@@ -1,30 +1,26 @@
 use crate::{http_client, lcu};
+use crate::lcu::error::LcuError;
 
 #[tauri::command]
-pub async fn get_recent_matches_by_puuid(puuid: String, count: Option<usize>) -> Result<lcu::types::MatchStatistics, String> {
+pub async fn get_recent_matches_by_puuid(puuid: String, count: Option<usize>) -> Result<lcu::types::MatchStatistics, LcuError> {
     let client = http_client::get_lcu_client();
     let count = count.unwrap_or(20);
     lcu::matches::service::get_recent_matches_by_puuid(&client, &puuid, count).await
 }
 
 #[tauri::command]
-pub async fn get_current_summoner() -> Result<lcu::types::SummonerInfo, String> {
+pub async fn get_current_summoner() -> Result<lcu::types::SummonerInfo, LcuError> {
     let client = http_client::get_lcu_client();
     lcu::summoner::service::get_current_summoner(client).await
 }
 
 #[tauri::command]
-pub async fn get_summoner_by_id(id: u64) -> Result<Option<lcu::types::SummonerInfo>, String> {
+pub async fn get_summoner_by_id(id: u64) -> Result<Option<lcu::types::SummonerInfo>, LcuError> {
     let client = http_client::get_lcu_client();
     match lcu::summoner::service::get_summoner_by_id(client, id).await {
         Ok(info) => Ok(Some(info)),
-        Err(e) => {
-            if e.contains("404") {
-                Ok(None)
-            } else {
-                Err(e)
-            }
-        }
+        Err(LcuError::HttpStatus { status: 404, .. }) => Ok(None),
+        Err(e) => Err(e),
     }
 }
 
@@ -32,12 +28,12 @@ pub async fn get_summoner_by_id(id: u64) -> Result<Option<lcu::types::SummonerIn
 pub async fn get_summoners_and_histories(
     names: Vec<String>,
     count: Option<usize>,
-) -> Result<Vec<lcu::types::SummonerWithMatches>, String> {
+) -> Result<Vec<lcu::types::SummonerWithMatches>, LcuError> {
     use lcu::summoner::service::fill_summoner_extra_info;
     let client = http_client::get_lcu_client();
     let mut summoners = lcu::summoner::service::get_summoners_by_names(client, names)
         .await
-        .map_err(|e| format!("批量获取召唤师信息失败: {}", e))?;
+        .inspect_err(|e| log::error!("批量获取召唤师信息失败: {}", e))?;
     let mut result = Vec::new();
     for summoner in &mut summoners {
         let puuid = summoner.puuid.clone();
@@ -77,7 +73,7 @@ pub async fn get_summoners_and_histories(
 }
 
 #[tauri::command]
-pub async fn set_summoner_background_skin(skin_id: u64) -> Result<(), String> {
+pub async fn set_summoner_background_skin(skin_id: u64) -> Result<(), LcuError> {
     let client = http_client::get_lcu_client();
     lcu::summoner::service::set_summoner_background(client, skin_id).await
 }
@@ -88,7 +84,7 @@ pub async fn set_summoner_chat_profile(
     queue: Option<String>,
     tier: Option<String>,
     division: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), LcuError> {
     let client = http_client::get_lcu_client();
     lcu::summoner::service::set_summoner_chat_profile(client, status_message, queue, tier, division)
         .await
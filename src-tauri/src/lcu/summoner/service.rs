@@ -1,5 +1,7 @@
+use crate::lcu::error::LcuError;
 use crate::lcu::request::{lcu_get, lcu_post, lcu_put};
 use crate::lcu::types::{RankInfo, SummonerInfo};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{to_value, Value};
@@ -13,7 +15,7 @@ pub struct ProfileUpdateRequest {
     pub value: u64,
 }
 
-pub async fn get_current_summoner(client: &Client) -> Result<SummonerInfo, String> {
+pub async fn get_current_summoner(client: &Client) -> Result<SummonerInfo, LcuError> {
     let mut summoner_info: SummonerInfo =
         lcu_get(client, "/lol-summoner/v1/current-summoner").await?;
     // 获取段位信息
@@ -42,7 +44,7 @@ pub async fn fill_summoner_extra_info(client: &Client, summoner_info: &mut Summo
         summoner_info.display_name = format!("{}#{}", game_name, tag_line);
     }
 }
-pub async fn get_rank_info(client: &Client, puuid: &str) -> Result<RankInfo, String> {
+pub async fn get_rank_info(client: &Client, puuid: &str) -> Result<RankInfo, LcuError> {
     let path = &format!("/lol-ranked/v1/ranked-stats/{}", puuid);
     let rank_data: Value = lcu_get(client, path).await?;
 
@@ -98,23 +100,33 @@ pub async fn get_rank_info(client: &Client, puuid: &str) -> Result<RankInfo, Str
 }
 
 // 获取指定ID的召唤师
-pub async fn get_summoner_by_id(client: &Client, summoner_id: u64) -> Result<SummonerInfo, String> {
+pub async fn get_summoner_by_id(
+    client: &Client,
+    summoner_id: u64,
+) -> Result<SummonerInfo, LcuError> {
     let path = &format!("/lol-summoner/v1/summoners/{}", summoner_id);
     lcu_get(client, path).await
 }
 
+// 按名称（名称#标签）获取召唤师
+pub async fn get_summoner_by_name(client: &Client, name: &str) -> Result<SummonerInfo, LcuError> {
+    let name = utf8_percent_encode(name, NON_ALPHANUMERIC);
+    let path = &format!("/lol-summoner/v1/summoners?name={}", name);
+    lcu_get(client, path).await
+}
+
 // 批量获取召唤师信息
 pub async fn get_summoners_by_names(
     client: &Client,
     names: Vec<String>,
-) -> Result<Vec<SummonerInfo>, String> {
+) -> Result<Vec<SummonerInfo>, LcuError> {
     let path = &format!("/lol-summoner/v2/summoners/names");
     let summoners: Vec<SummonerInfo> = lcu_post(client, path, names.into()).await?;
     Ok(summoners)
 }
 
 // 设置生涯背景皮肤（使用正确的API - POST请求）
-pub async fn set_summoner_background(client: &Client, skin_id: u64) -> Result<(), String> {
+pub async fn set_summoner_background(client: &Client, skin_id: u64) -> Result<(), LcuError> {
     let path = "/lol-summoner/v1/current-summoner/summoner-profile";
 
     let request_body = ProfileUpdateRequest {
@@ -123,8 +135,8 @@ pub async fn set_summoner_background(client: &Client, skin_id: u64) -> Result<()
     };
 
     // 将结构体序列化为 serde_json::Value
-    let body_value =
-        serde_json::to_value(request_body).map_err(|e| format!("序列化请求体失败: {}", e))?;
+    let body_value = serde_json::to_value(request_body)
+        .map_err(|e| LcuError::other(format!("序列化请求体失败: {}", e)))?;
 
     // 使用 POST 请求而不是 PUT
     match lcu_post::<Value>(client, path, body_value).await {
@@ -136,16 +148,9 @@ pub async fn set_summoner_background(client: &Client, skin_id: u64) -> Result<()
             Ok(())
         }
         Err(e) => {
+            // errorCode/message 已包含在 LcuError::HttpStatus 中，直接交给前端判断
             println!("设置生涯背景皮肤失败: {}", e);
-            // 尝试解析错误信息
-            if let Ok(error_json) = serde_json::from_str::<Value>(&e) {
-                if let Some(error_code) = error_json.get("errorCode") {
-                    if let Some(message) = error_json.get("message") {
-                        return Err(format!("设置背景失败 [{}]: {}", error_code, message));
-                    }
-                }
-            }
-            Err(format!("设置生涯背景失败: {}", e))
+            Err(e)
         }
     }
 }
@@ -175,7 +180,7 @@ pub async fn set_summoner_chat_profile(
     queue: Option<String>,
     tier: Option<String>,
     division: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), LcuError> {
     let path = "/lol-chat/v1/me";
     let lol = if queue.is_some() || tier.is_some() || division.is_some() {
         Some(ChatProfileLolInfo {
@@ -0,0 +1,26 @@
+use crate::lcu::error::LcuError;
+use crate::lcu::traffic::service;
+use crate::lcu::types::LcuTrafficStatus;
+use std::path::PathBuf;
+
+/// 开始录制，path 为空时写到配置目录下的 lcu_sessions
+#[tauri::command]
+pub fn start_lcu_capture(path: Option<String>) -> Result<LcuTrafficStatus, LcuError> {
+    service::start_capture(path.map(PathBuf::from)).map_err(LcuError::from)
+}
+
+#[tauri::command]
+pub fn start_lcu_replay(path: String) -> Result<LcuTrafficStatus, LcuError> {
+    service::start_replay(PathBuf::from(path)).map_err(LcuError::from)
+}
+
+/// 停止录制/回放，返回停止前的状态（含会话文件路径）
+#[tauri::command]
+pub fn stop_lcu_traffic() -> LcuTrafficStatus {
+    service::stop()
+}
+
+#[tauri::command]
+pub fn get_lcu_traffic_status() -> LcuTrafficStatus {
+    service::status()
+}
@@ -0,0 +1,455 @@
+//! LCU 流量录制与回放
+//!
+//! 录制：send_once 每收到一个响应就向会话文件追加一行 JSON，认证 token 统一替换为 <redacted>。
+//! 回放：lcu_request_with_policy 优先从会话文件取响应，同一 (method, path) 按录制顺序依次返回，
+//! 用完后重复最后一条。这样用户反馈的解析问题可以直接变成离线可复现的用例。
+//! 会话文件由单独的写入线程追加，请求路径上只做序列化和发送到通道。
+//!
+//! 回放只替换 HTTP 请求的响应。UnifiedPollingManager 的连接检查仍然需要真实的 LCU 端点
+//! （进程/lockfile 或注入的 CredentialProvider），WebSocket 事件也不会回放，所以客户端离线时
+//! 轮询管理器不会进入业务轮询；离线复现需要直接调用 service 层函数，或在测试中使用 MockLcu 提供端点。
+//!
+//! 也可以通过环境变量在启动时开启：NIDALEE_LCU_CAPTURE=<文件>（空值使用默认目录）、
+//! NIDALEE_LCU_REPLAY=<文件>
+use crate::lcu::endpoint::LcuEndpoint;
+use crate::lcu::error::LcuError;
+use crate::lcu::types::{LcuTrafficMode, LcuTrafficStatus};
+use base64::{engine::general_purpose, Engine as _};
+use once_cell::sync::Lazy;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 环境变量：启动即录制到指定文件
+pub const ENV_CAPTURE: &str = "NIDALEE_LCU_CAPTURE";
+/// 环境变量：启动即从指定文件回放
+pub const ENV_REPLAY: &str = "NIDALEE_LCU_REPLAY";
+
+const REDACTED: &str = "<redacted>";
+
+/// 会话文件中的一行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficRecord {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    pub status: u16,
+    /// JSON 响应体
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// 非 JSON 响应体（如图标），base64 编码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+    pub elapsed_ms: u64,
+    pub timestamp_ms: u64,
+}
+
+impl TrafficRecord {
+    fn body_bytes(&self) -> Vec<u8> {
+        if let Some(body) = &self.body {
+            return serde_json::to_vec(body).unwrap_or_default();
+        }
+        self.body_base64
+            .as_deref()
+            .and_then(|b| general_purpose::STANDARD.decode(b).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// 回放得到的响应
+#[derive(Debug, Clone)]
+pub struct ReplayedResponse {
+    pub status: StatusCode,
+    pub body: Vec<u8>,
+    pub elapsed_ms: u64,
+}
+
+enum TrafficState {
+    Off,
+    Capture {
+        file: PathBuf,
+        /// 发送给写入线程的记录行，关闭后写入线程退出
+        sender: mpsc::Sender<String>,
+        writer: JoinHandle<()>,
+        /// 已写入文件的记录数，由写入线程更新
+        count: Arc<AtomicU32>,
+    },
+    Replay {
+        file: PathBuf,
+        /// "GET /path" -> (录制顺序的记录, 下一条的下标)
+        records: HashMap<String, (Vec<TrafficRecord>, usize)>,
+        count: u32,
+    },
+}
+
+impl TrafficState {
+    fn status(&self) -> LcuTrafficStatus {
+        match self {
+            TrafficState::Off => LcuTrafficStatus {
+                mode: LcuTrafficMode::Off,
+                file: None,
+                count: 0,
+            },
+            TrafficState::Capture { file, count, .. } => LcuTrafficStatus {
+                mode: LcuTrafficMode::Capture,
+                file: Some(file.to_string_lossy().to_string()),
+                count: count.load(Ordering::SeqCst),
+            },
+            TrafficState::Replay { file, count, .. } => LcuTrafficStatus {
+                mode: LcuTrafficMode::Replay,
+                file: Some(file.to_string_lossy().to_string()),
+                count: *count,
+            },
+        }
+    }
+}
+
+static STATE: Lazy<Mutex<TrafficState>> = Lazy::new(|| Mutex::new(state_from_env()));
+
+fn state_from_env() -> TrafficState {
+    let result = if let Ok(path) = std::env::var(ENV_REPLAY) {
+        open_replay(PathBuf::from(path))
+    } else if let Ok(path) = std::env::var(ENV_CAPTURE) {
+        open_capture((!path.is_empty()).then(|| PathBuf::from(path)))
+    } else {
+        return TrafficState::Off;
+    };
+    result.unwrap_or_else(|e| {
+        log::warn!("[LCU] 根据环境变量开启录制/回放失败: {}", e);
+        TrafficState::Off
+    })
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn record_key(method: &str, path: &str) -> String {
+    format!("{} {}", method, path)
+}
+
+fn default_session_file() -> Result<PathBuf, String> {
+    let dir = dirs::config_dir()
+        .ok_or("无法获取配置目录")?
+        .join("nidalee")
+        .join("lcu_sessions");
+    Ok(dir.join(format!("session-{}.jsonl", now_ms())))
+}
+
+fn open_capture(path: Option<PathBuf>) -> Result<TrafficState, String> {
+    let file = match path {
+        Some(path) => path,
+        None => default_session_file()?,
+    };
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("创建会话目录失败: {}", e))?;
+    }
+    let writer = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file)
+        .map_err(|e| format!("打开会话文件失败 {}: {}", file.display(), e))?;
+    let (sender, receiver) = mpsc::channel();
+    let count = Arc::new(AtomicU32::new(0));
+    let writer = spawn_writer(file.clone(), writer, receiver, count.clone())?;
+    log::info!("[LCU] 开始录制 LCU 流量: {}", file.display());
+    Ok(TrafficState::Capture {
+        file,
+        sender,
+        writer,
+        count,
+    })
+}
+
+/// 写入线程：逐行追加到会话文件，发送端全部关闭后退出
+fn spawn_writer(
+    file: PathBuf,
+    mut writer: File,
+    receiver: mpsc::Receiver<String>,
+    count: Arc<AtomicU32>,
+) -> Result<JoinHandle<()>, String> {
+    std::thread::Builder::new()
+        .name("lcu-traffic-writer".to_string())
+        .spawn(move || {
+            for line in receiver {
+                if let Err(e) = writeln!(writer, "{}", line) {
+                    log::warn!("[LCU] 写入会话文件失败 {}: {}", file.display(), e);
+                    continue;
+                }
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .map_err(|e| format!("启动会话写入线程失败: {}", e))
+}
+
+fn open_replay(file: PathBuf) -> Result<TrafficState, String> {
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("读取会话文件失败 {}: {}", file.display(), e))?;
+    let mut records: HashMap<String, (Vec<TrafficRecord>, usize)> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: TrafficRecord = serde_json::from_str(line)
+            .map_err(|e| format!("会话文件第 {} 行解析失败: {}", index + 1, e))?;
+        records
+            .entry(record_key(&record.method, &record.path))
+            .or_default()
+            .0
+            .push(record);
+    }
+    log::info!(
+        "[LCU] 开始回放 LCU 流量: {} ({} 个接口)",
+        file.display(),
+        records.len()
+    );
+    Ok(TrafficState::Replay {
+        file,
+        records,
+        count: 0,
+    })
+}
+
+/// 结束一个录制/回放状态；录制时等写入线程把已发送的记录写完，返回最终状态
+fn finish(state: TrafficState) -> LcuTrafficStatus {
+    match state {
+        TrafficState::Capture {
+            file,
+            sender,
+            writer,
+            count,
+        } => {
+            drop(sender);
+            if writer.join().is_err() {
+                log::warn!("[LCU] 会话写入线程异常退出: {}", file.display());
+            }
+            LcuTrafficStatus {
+                mode: LcuTrafficMode::Capture,
+                file: Some(file.to_string_lossy().to_string()),
+                count: count.load(Ordering::SeqCst),
+            }
+        }
+        state => state.status(),
+    }
+}
+
+/// 开始录制（会替换当前的录制/回放）
+pub fn start_capture(path: Option<PathBuf>) -> Result<LcuTrafficStatus, String> {
+    let state = open_capture(path)?;
+    let status = state.status();
+    let previous = std::mem::replace(&mut *STATE.lock().unwrap(), state);
+    finish(previous);
+    Ok(status)
+}
+
+/// 开始回放（会替换当前的录制/回放）
+pub fn start_replay(path: PathBuf) -> Result<LcuTrafficStatus, String> {
+    let state = open_replay(path)?;
+    let status = state.status();
+    let previous = std::mem::replace(&mut *STATE.lock().unwrap(), state);
+    finish(previous);
+    Ok(status)
+}
+
+/// 停止录制/回放，返回停止前的状态
+pub fn stop() -> LcuTrafficStatus {
+    let previous = std::mem::replace(&mut *STATE.lock().unwrap(), TrafficState::Off);
+    let status = finish(previous);
+    if let Some(file) = &status.file {
+        log::info!("[LCU] 停止录制/回放: {} ({} 条)", file, status.count);
+    }
+    status
+}
+
+pub fn status() -> LcuTrafficStatus {
+    STATE.lock().unwrap().status()
+}
+
+/// 把响应中出现的认证 token 替换掉，避免用户上传会话文件时泄露
+fn redact(line: String, endpoint: &LcuEndpoint) -> String {
+    let auth = &endpoint.auth;
+    let basic = general_purpose::STANDARD.encode(format!("riot:{}", auth.remoting_auth_token));
+    [
+        auth.remoting_auth_token.as_str(),
+        auth.riotclient_auth_token.as_str(),
+        basic.as_str(),
+    ]
+    .iter()
+    .filter(|secret| !secret.is_empty())
+    .fold(line, |line, secret| line.replace(secret, REDACTED))
+}
+
+/// 录制模式下把记录交给写入线程，其他模式直接返回
+pub fn record(
+    endpoint: &LcuEndpoint,
+    method: &Method,
+    path: &str,
+    request_body: Option<&Value>,
+    status: StatusCode,
+    body: &[u8],
+    elapsed_ms: u64,
+) {
+    let sender = match &*STATE.lock().unwrap() {
+        TrafficState::Capture { sender, .. } => sender.clone(),
+        _ => return,
+    };
+
+    let json_body = if body.is_empty() {
+        None
+    } else {
+        serde_json::from_slice::<Value>(body).ok()
+    };
+    let body_base64 =
+        (json_body.is_none() && !body.is_empty()).then(|| general_purpose::STANDARD.encode(body));
+    let record = TrafficRecord {
+        method: method.to_string(),
+        path: path.to_string(),
+        request_body: request_body.cloned(),
+        status: status.as_u16(),
+        body: json_body,
+        body_base64,
+        elapsed_ms,
+        timestamp_ms: now_ms(),
+    };
+    let line = match serde_json::to_string(&record) {
+        Ok(line) => redact(line, endpoint),
+        Err(e) => {
+            log::warn!("[LCU] 序列化录制记录失败: {}", e);
+            return;
+        }
+    };
+    // 只有写入线程异常退出时才会发送失败，此时丢弃这条记录
+    let _ = sender.send(line);
+}
+
+/// 回放模式下返回录制的响应；非回放模式返回 None
+pub fn replay(method: &Method, path: &str) -> Option<Result<ReplayedResponse, LcuError>> {
+    let mut state = STATE.lock().unwrap();
+    let TrafficState::Replay { records, count, .. } = &mut *state else {
+        return None;
+    };
+
+    let Some((list, next)) = records.get_mut(&record_key(method.as_str(), path)) else {
+        return Some(Err(LcuError::other(format!(
+            "回放会话中没有 {} {} 的记录",
+            method, path
+        ))));
+    };
+    // 用完后一直重复最后一条，轮询类接口才能继续工作
+    let record = &list[(*next).min(list.len() - 1)];
+    *next += 1;
+    *count += 1;
+
+    let status = match StatusCode::from_u16(record.status) {
+        Ok(status) => status,
+        Err(e) => return Some(Err(LcuError::other(format!("录制的状态码无效: {}", e)))),
+    };
+    Some(Ok(ReplayedResponse {
+        status,
+        body: record.body_bytes(),
+        elapsed_ms: record.elapsed_ms,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcu, MockResponse};
+    use crate::lcu::request::lcu_get;
+    use crate::lcu::summoner::service::get_current_summoner;
+
+    #[tokio::test]
+    async fn captured_session_replays_without_client() {
+        let mock = MockLcu::start().await;
+        let file = std::env::temp_dir().join(format!("nidalee-traffic-{}.jsonl", now_ms()));
+
+        // 模拟一个响应体中带 token 的接口
+        mock.respond(
+            Method::GET,
+            "/riotclient/auth-token",
+            MockResponse::json(Value::String("mock-remoting-token".to_string())),
+        );
+
+        start_capture(Some(file.clone())).unwrap();
+        let captured = get_current_summoner(&mock.client()).await.unwrap();
+        lcu_get::<Value>(&mock.client(), "/riotclient/auth-token")
+            .await
+            .unwrap();
+        let capture_status = stop();
+        assert_eq!(capture_status.mode, LcuTrafficMode::Capture);
+        assert!(capture_status.count > 0);
+
+        // 会话文件中不能出现认证 token
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(content.contains(REDACTED));
+        assert!(!content.contains("mock-remoting-token"));
+
+        // 回放时客户端返回错误也不影响结果
+        mock.respond(Method::GET, "/*", MockResponse::error(500, "offline"));
+        let requests_before = mock.requests().len();
+        start_replay(file.clone()).unwrap();
+        let replayed = get_current_summoner(&mock.client()).await;
+        let unknown = replay(&Method::GET, "/lol-not-recorded/v1/anything");
+        stop();
+        fs::remove_file(&file).ok();
+
+        let replayed = replayed.unwrap();
+        assert_eq!(replayed.puuid, captured.puuid);
+        assert_eq!(replayed.display_name, captured.display_name);
+        assert_eq!(mock.requests().len(), requests_before);
+        assert!(matches!(unknown, Some(Err(LcuError::Other { .. }))));
+    }
+
+    #[tokio::test]
+    async fn replay_repeats_last_record_when_exhausted() {
+        let record = |status: u16| TrafficRecord {
+            method: "GET".to_string(),
+            path: "/lol-gameflow/v1/gameflow-phase".to_string(),
+            request_body: None,
+            status,
+            body: Some(Value::String("Lobby".to_string())),
+            body_base64: None,
+            elapsed_ms: 1,
+            timestamp_ms: 0,
+        };
+        let mut records = HashMap::new();
+        records.insert(
+            record_key("GET", "/lol-gameflow/v1/gameflow-phase"),
+            (vec![record(404), record(200)], 0),
+        );
+        // 占用模拟服务的锁，避免与其他用例共享全局状态
+        let _mock = MockLcu::start().await;
+        *STATE.lock().unwrap() = TrafficState::Replay {
+            file: PathBuf::from("memory"),
+            records,
+            count: 0,
+        };
+
+        let statuses: Vec<u16> = (0..3)
+            .map(|_| {
+                replay(&Method::GET, "/lol-gameflow/v1/gameflow-phase")
+                    .unwrap()
+                    .unwrap()
+                    .status
+                    .as_u16()
+            })
+            .collect();
+        let count = stop().count;
+
+        assert_eq!(statuses, vec![404, 200, 200]);
+        assert_eq!(count, 3);
+    }
+}
@@ -48,44 +48,857 @@ fn empty_rune_type() -> String {
     "rune".to_string()
 }
 
+/// 对局中的玩家（Live Client Data API 的 /liveclientdata/playerlist）
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(
     export,
     export_to = "../../src/types/generated/LiveClientPlayer.ts",
     rename_all = "camelCase"
 )]
+#[serde(rename_all = "camelCase")]
 pub struct LiveClientPlayer {
     pub summoner_name: String,
+    /// 名称#标签，旧版本游戏没有 riotId 相关字段
+    #[serde(default)]
+    pub riot_id: Option<String>,
+    #[serde(default)]
+    pub riot_id_game_name: Option<String>,
+    #[serde(default)]
+    pub riot_id_tag_line: Option<String>,
     pub champion_name: String,
     pub is_bot: bool,
     pub is_dead: bool,
-    #[ts(type = "any")]
-    pub items: Vec<Value>,
+    pub items: Vec<LiveClientItem>,
     pub level: i32,
     pub position: String,
     pub raw_champion_name: String,
     pub respawn_timer: f64,
-    #[ts(type = "any")]
-    pub runes: Value,
-    #[ts(type = "any")]
-    pub scores: Value,
+    /// 机器人没有符文
+    #[serde(default)]
+    pub runes: LiveClientPlayerRunes,
+    pub scores: LiveClientScores,
+    #[serde(rename = "skinID")]
     pub skin_id: i32,
-    #[ts(type = "any")]
-    pub summoner_spells: Value,
+    pub summoner_spells: LiveClientSummonerSpells,
+    /// ORDER（蓝色方）或 CHAOS（红色方）
     pub team: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientScores.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientScores {
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub creep_score: i32,
+    pub ward_score: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientItem.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientItem {
+    #[serde(rename = "itemID")]
+    pub item_id: i32,
+    pub display_name: String,
+    pub raw_description: String,
+    pub raw_display_name: String,
+    pub count: i32,
+    pub price: i32,
+    /// 物品栏位置，0-5 为装备栏，6 为饰品
+    pub slot: i32,
+    pub can_use: bool,
+    pub consumable: bool,
+}
+
+/// 符文或符文系
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientRune.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientRune {
+    pub id: i32,
+    pub display_name: String,
+    pub raw_description: String,
+    pub raw_display_name: String,
+}
+
+/// 玩家列表中的符文：只有基石符文和主副系
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientPlayerRunes.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientPlayerRunes {
+    pub keystone: LiveClientRune,
+    pub primary_rune_tree: LiveClientRune,
+    pub secondary_rune_tree: LiveClientRune,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientSummonerSpell.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientSummonerSpell {
+    pub display_name: String,
+    pub raw_description: String,
+    pub raw_display_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientSummonerSpells.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientSummonerSpells {
+    pub summoner_spell_one: LiveClientSummonerSpell,
+    pub summoner_spell_two: LiveClientSummonerSpell,
+}
+
+/// 当前玩家的技能，被动没有等级
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientAbility.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientAbility {
+    pub id: String,
+    #[serde(default)]
+    pub ability_level: Option<i32>,
+    pub display_name: String,
+    pub raw_description: String,
+    pub raw_display_name: String,
+}
+
+/// /liveclientdata/activeplayerabilities
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(export, export_to = "../../src/types/generated/LiveClientAbilities.ts")]
+pub struct LiveClientAbilities {
+    #[serde(rename = "Passive")]
+    pub passive: LiveClientAbility,
+    #[serde(rename = "Q")]
+    pub q: LiveClientAbility,
+    #[serde(rename = "W")]
+    pub w: LiveClientAbility,
+    #[serde(rename = "E")]
+    pub e: LiveClientAbility,
+    #[serde(rename = "R")]
+    pub r: LiveClientAbility,
+}
+
+/// 属性碎片
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientStatRune.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientStatRune {
+    pub id: i32,
+    pub raw_description: String,
+}
+
+/// /liveclientdata/activeplayerrunes，当前玩家的完整符文
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientFullRunes.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientFullRunes {
+    pub keystone: LiveClientRune,
+    pub primary_rune_tree: LiveClientRune,
+    pub secondary_rune_tree: LiveClientRune,
+    pub general_runes: Vec<LiveClientRune>,
+    pub stat_runes: Vec<LiveClientStatRune>,
+}
+
+/// 当前玩家的英雄属性，百分比类属性为 0-1 的小数
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientChampionStats.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LiveClientChampionStats {
+    pub current_health: f64,
+    pub max_health: f64,
+    pub health_regen_rate: f64,
+    /// MANA / ENERGY / NONE 等
+    pub resource_type: String,
+    pub resource_value: f64,
+    pub resource_max: f64,
+    pub resource_regen_rate: f64,
+    pub attack_damage: f64,
+    pub ability_power: f64,
+    pub attack_speed: f64,
+    pub attack_range: f64,
+    pub ability_haste: f64,
+    pub armor: f64,
+    pub magic_resist: f64,
+    pub move_speed: f64,
+    pub crit_chance: f64,
+    pub crit_damage: f64,
+    pub armor_penetration_flat: f64,
+    pub armor_penetration_percent: f64,
+    pub bonus_armor_penetration_percent: f64,
+    pub physical_lethality: f64,
+    pub magic_penetration_flat: f64,
+    pub magic_penetration_percent: f64,
+    pub bonus_magic_penetration_percent: f64,
+    pub magic_lethality: f64,
+    pub life_steal: f64,
+    pub physical_vamp: f64,
+    pub spell_vamp: f64,
+    pub omnivamp: f64,
+    pub heal_shield_power: f64,
+    pub tenacity: f64,
+}
+
+/// /liveclientdata/activeplayer，当前玩家（观战时不可用）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientActivePlayer.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientActivePlayer {
+    #[serde(default)]
+    pub summoner_name: String,
+    #[serde(default)]
+    pub riot_id: Option<String>,
+    #[serde(default)]
+    pub riot_id_game_name: Option<String>,
+    #[serde(default)]
+    pub riot_id_tag_line: Option<String>,
+    pub level: i32,
+    pub current_gold: f64,
+    pub abilities: LiveClientAbilities,
+    pub champion_stats: LiveClientChampionStats,
+    pub full_runes: LiveClientFullRunes,
+    /// 游戏设置中的“队伍相对颜色”
+    #[serde(default)]
+    pub team_relative_colors: bool,
+}
+
+/// 对局事件，EventName 决定哪些字段有值：
+/// GameStart / MinionsSpawning / FirstBrick / TurretKilled / InhibKilled / DragonKill /
+/// HeraldKill / BaronKill / ChampionKill / Multikill / Ace / FirstBlood / GameEnd 等
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientEvent.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "PascalCase"))]
+pub struct LiveClientEvent {
+    #[serde(rename(deserialize = "EventID"))]
+    pub event_id: i64,
+    pub event_name: String,
+    /// 对局开始后的秒数
+    pub event_time: f64,
+    #[serde(default)]
+    pub killer_name: Option<String>,
+    #[serde(default)]
+    pub victim_name: Option<String>,
+    #[serde(default)]
+    pub assisters: Vec<String>,
+    /// FirstBrick / FirstBlood 的获得者
+    #[serde(default)]
+    pub recipient: Option<String>,
+    /// Multikill 的连杀数
+    #[serde(default)]
+    pub kill_streak: Option<i32>,
+    #[serde(default)]
+    pub turret_killed: Option<String>,
+    #[serde(default)]
+    pub inhib_killed: Option<String>,
+    /// Fire / Water / Earth / Air / Hextech / Chemtech / Elder
+    #[serde(default)]
+    pub dragon_type: Option<String>,
+    /// 是否抢龙（接口返回 "True" / "False"）
+    #[serde(default)]
+    pub stolen: Option<String>,
+    #[serde(default)]
+    pub acer: Option<String>,
+    /// Ace 的队伍：ORDER / CHAOS
+    #[serde(default)]
+    pub acing_team: Option<String>,
+    /// GameEnd 的结果：Win / Lose
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+/// /liveclientdata/eventdata
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientEvents.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "PascalCase"))]
+pub struct LiveClientEvents {
+    pub events: Vec<LiveClientEvent>,
+}
+
+/// /liveclientdata/gamestats
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientGameData.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientGameData {
+    /// CLASSIC / ARAM / CHERRY 等
+    pub game_mode: String,
+    /// 对局开始后的秒数
+    pub game_time: f64,
+    pub map_name: String,
+    pub map_number: i32,
+    pub map_terrain: String,
+}
+
+/// /liveclientdata/allgamedata，对局的全部数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveClientAllGameData.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveClientAllGameData {
+    /// 观战时为错误信息，解析为 None
+    #[serde(default, deserialize_with = "ok_or_none")]
+    pub active_player: Option<LiveClientActivePlayer>,
+    pub all_players: Vec<LiveClientPlayer>,
+    pub events: LiveClientEvents,
+    pub game_data: LiveClientGameData,
+}
+
+/// 解析失败时返回 None 的反序列化 helper
+fn ok_or_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LcuAuthInfo.ts",
+    rename_all = "camelCase"
+)]
+pub struct LcuAuthInfo {
+    pub app_port: u16,
+    pub remoting_auth_token: String,
+    pub riotclient_app_port: u16,
+    pub riotclient_auth_token: String,
+}
+
+/// LCU 认证信息的获取方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/AuthStrategy.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum AuthStrategy {
+    /// 解析 LeagueClientUx 进程启动参数
+    CommandLine,
+    /// 读取安装目录下的 lockfile
+    Lockfile,
+}
+
+/// 认证发现配置，按 strategies 顺序依次尝试
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AuthDiscoveryConfig.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthDiscoveryConfig {
+    pub strategies: Vec<AuthStrategy>,
+    /// 指定 LCU 地址和 token（如本地模拟服务），设置后跳过进程发现
+    #[serde(default)]
+    pub endpoint: Option<LcuEndpointConfig>,
+}
+
+impl Default for AuthDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            strategies: vec![AuthStrategy::CommandLine, AuthStrategy::Lockfile],
+            endpoint: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LcuEndpointConfig.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LcuEndpointConfig {
+    pub base_url: String,
+    pub token: String,
+}
+
+/// 单个策略的尝试结果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AuthStrategyAttempt.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthStrategyAttempt {
+    pub strategy: AuthStrategy,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+/// 最近一次认证发现的报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AuthDiscoveryReport.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthDiscoveryReport {
+    /// 成功的策略，全部失败时为 None
+    pub strategy: Option<AuthStrategy>,
+    pub attempts: Vec<AuthStrategyAttempt>,
+}
+
+/// LCU 流量录制/回放模式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/LcuTrafficMode.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum LcuTrafficMode {
+    Off,
+    /// 把每一对请求/响应追加写入会话文件
+    Capture,
+    /// 从会话文件返回响应，不访问客户端
+    Replay,
+}
+
+/// 当前的录制/回放状态
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LcuTrafficStatus.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LcuTrafficStatus {
+    pub mode: LcuTrafficMode,
+    /// 会话文件路径，Off 时为 None
+    pub file: Option<String>,
+    /// 已录制或已回放的响应数
+    pub count: u32,
+}
+
+/// 透传规则的匹配方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/PassthroughMode.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum PassthroughMode {
+    /// 只放行命中 allow 规则的请求
+    Allowlist,
+    /// 放行所有未命中 deny 规则的请求
+    Denylist,
+}
+
+/// 透传规则：路径前缀 + 方法
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PassthroughRule.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PassthroughRule {
+    /// 例如 /lol-loot/，不含查询参数
+    pub path_prefix: String,
+    /// 为空表示所有方法
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+/// 通用 LCU 透传的配置（持久化到 passthrough_config.json）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PassthroughConfig.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PassthroughConfig {
+    pub mode: PassthroughMode,
+    #[serde(default)]
+    pub allow: Vec<PassthroughRule>,
+    /// 两种模式下都优先生效
+    #[serde(default)]
+    pub deny: Vec<PassthroughRule>,
+}
+
+impl PassthroughRule {
+    pub fn new(path_prefix: &str, methods: &[&str]) -> Self {
+        Self {
+            path_prefix: path_prefix.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+}
+
+impl Default for PassthroughConfig {
+    /// 默认只读放行常见的未封装接口，并屏蔽会影响账号或客户端进程的接口
+    fn default() -> Self {
+        Self {
+            mode: PassthroughMode::Allowlist,
+            allow: vec![
+                PassthroughRule::new("/lol-loot/", &["GET"]),
+                PassthroughRule::new("/lol-challenges/", &["GET"]),
+                PassthroughRule::new("/lol-honor-v2/", &["GET"]),
+                PassthroughRule::new("/lol-clash/", &["GET"]),
+            ],
+            deny: vec![
+                PassthroughRule::new("/process-control/", &[]),
+                PassthroughRule::new("/riotclient/", &[]),
+                PassthroughRule::new("/lol-login/", &[]),
+                PassthroughRule::new("/lol-rso-auth/", &[]),
+                PassthroughRule::new("/entitlements/", &[]),
+                PassthroughRule::new("/lol-store/", &[]),
+                PassthroughRule::new("/lol-purchase-widget/", &[]),
+            ],
+        }
+    }
+}
+
+/// 透传调用的审计记录
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PassthroughAuditEntry.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PassthroughAuditEntry {
+    #[ts(type = "number")]
+    pub timestamp_ms: u64,
+    pub method: String,
+    pub path: String,
+    pub has_body: bool,
+    pub allowed: bool,
+    /// 客户端返回的状态码，被拒绝或未收到响应时为 None
+    pub status: Option<u16>,
+    #[ts(type = "number")]
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+/// 自动接受对局的设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoAcceptSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoAcceptSettings {
+    pub enabled: bool,
+    /// 找到对局后等待多久再接受，期间前端可以取消
+    #[ts(type = "number")]
+    pub delay_ms: u64,
+    /// 上一次准备确认时拒绝或没有响应（挂机），本次不自动接受
+    pub skip_after_decline: bool,
+}
+
+impl Default for AutoAcceptSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            delay_ms: 2000,
+            skip_after_decline: true,
+        }
+    }
+}
+
+/// 后台自动化功能的设置（持久化到 automation_settings.json）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutomationSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutomationSettings {
+    pub auto_accept: AutoAcceptSettings,
+    pub pick_ban: AutoPickBanSettings,
+    pub build_source: BuildSourceSettings,
+    pub auto_runes: AutoRuneSettings,
+    pub auto_spells: AutoSpellSettings,
+}
+
+/// 自动选择/禁用英雄的设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoPickBanSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoPickBanSettings {
+    pub pick_enabled: bool,
+    pub ban_enabled: bool,
+    /// 分路（top/jungle/middle/bottom/utility）-> 按优先级排列的英雄 id，
+    /// 没有分路（如匹配模式）或分路未配置时使用 default
+    pub picks: HashMap<String, Vec<i32>>,
+    pub bans: HashMap<String, Vec<i32>>,
+    /// 预选后等待多久再锁定
+    #[ts(type = "number")]
+    pub lock_delay_ms: u64,
+}
+
+impl Default for AutoPickBanSettings {
+    fn default() -> Self {
+        Self {
+            pick_enabled: false,
+            ban_enabled: false,
+            picks: HashMap::new(),
+            bans: HashMap::new(),
+            lock_delay_ms: 3000,
+        }
+    }
+}
+
+/// 自动选择/禁用的一次决策，随 auto-pick-ban-decision 事件发送
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoPickBanDecision.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoPickBanDecision {
+    pub action_id: i32,
+    /// pick 或 ban
+    pub action_type: String,
+    /// 没有可用英雄时为 None
+    pub champion_id: Option<i32>,
+    /// false 为预选，true 为锁定
+    pub locked: bool,
+    pub success: bool,
+    pub reason: String,
+}
+
+/// 出装/符文数据来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/BuildSource.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum BuildSource {
+    /// OP.GG API，按分路和模式区分
+    Opgg,
+    /// ChampR 聚合数据（c.lbj.moe）
+    Champr,
+}
+
+/// 自动化功能获取出装数据时使用的来源
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/BuildSourceSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BuildSourceSettings {
+    pub source: BuildSource,
+    /// OP.GG 区域，如 global、kr
+    pub region: String,
+    /// OP.GG 段位，如 all、emerald
+    pub tier: String,
+    /// ChampR 的数据源名称，如 op.gg
+    pub champr_source: String,
+}
+
+impl Default for BuildSourceSettings {
+    fn default() -> Self {
+        Self {
+            source: BuildSource::Opgg,
+            region: "global".to_string(),
+            tier: "all".to_string(),
+            champr_source: "op.gg".to_string(),
+        }
+    }
+}
+
+/// 固定使用的符文页
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PinnedRunePage.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedRunePage {
+    pub name: String,
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    pub selected_perk_ids: Vec<i32>,
+}
+
+/// 锁定英雄后自动应用符文的设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoRuneSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoRuneSettings {
+    pub enabled: bool,
+    /// 英雄 id -> 固定符文页，所有队列通用，设置后不再从数据源获取
+    pub pinned: HashMap<i32, PinnedRunePage>,
+    /// 队列 id -> 英雄 id -> 固定符文页，优先于 pinned（如只在大乱斗使用的符文页）
+    pub queue_pinned: HashMap<i32, HashMap<i32, PinnedRunePage>>,
+    /// 数据源的符文无效（如版本更新后）时用最常用的有效符文替换
+    pub repair_invalid: bool,
+}
+
+impl Default for AutoRuneSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pinned: HashMap::new(),
+            queue_pinned: HashMap::new(),
+            repair_invalid: true,
+        }
+    }
+}
+
+impl AutoRuneSettings {
+    /// 英雄在该队列下的固定符文页，队列没有单独固定时使用通用的
+    pub fn pinned_page(&self, champion_id: i32, queue_id: Option<i32>) -> Option<&PinnedRunePage> {
+        queue_id
+            .and_then(|queue_id| self.queue_pinned.get(&queue_id))
+            .and_then(|pages| pages.get(&champion_id))
+            .or_else(|| self.pinned.get(&champion_id))
+    }
+}
+
+/// 自动应用符文的结果，随 auto-rune-applied 事件发送
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(
     export,
-    export_to = "../../src/types/generated/LcuAuthInfo.ts",
+    export_to = "../../src/types/generated/AutoRuneResult.ts",
     rename_all = "camelCase"
 )]
-pub struct LcuAuthInfo {
-    pub app_port: u16,
-    pub remoting_auth_token: String,
-    pub riotclient_app_port: u16,
-    pub riotclient_auth_token: String,
+#[serde(rename_all = "camelCase")]
+pub struct AutoRuneResult {
+    pub champion_id: i32,
+    /// pinned / opgg / champr
+    pub source: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// 闪现放在哪个召唤师技能栏位
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/FlashSlot.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum FlashSlot {
+    /// 沿用玩家当前选择中闪现所在的栏位
+    #[default]
+    Keep,
+    D,
+    F,
+}
+
+/// 锁定英雄后自动设置召唤师技能的设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoSpellSettings.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoSpellSettings {
+    pub enabled: bool,
+    pub flash_slot: FlashSlot,
+}
+
+/// 设置召唤师技能的结果，随 auto-spells-applied 事件发送
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/SummonerSpellResult.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpellResult {
+    pub champion_id: i32,
+    /// D 键技能
+    pub spell1_id: Option<i32>,
+    /// F 键技能
+    pub spell2_id: Option<i32>,
+    pub success: bool,
+    pub message: String,
+}
+
+/// 自动接受的进度，随 auto-accept-status 事件发送
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/AutoAcceptPhase.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum AutoAcceptPhase {
+    /// 等待 delay_ms 后接受，期间可调用 cancel_auto_accept
+    Pending,
+    Cancelled,
+    Skipped,
+    Accepted,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AutoAcceptStatus.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAcceptStatus {
+    pub phase: AutoAcceptPhase,
+    #[ts(type = "number")]
+    pub delay_ms: u64,
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, TS)]
@@ -528,6 +1341,25 @@ pub struct LowPriorityData {
     pub reason: String,
 }
 
+/// 准备确认（/lol-matchmaking/v1/ready-check）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/ReadyCheck.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyCheck {
+    /// InProgress / EveryoneReady / StrangerNotReady / PartyNotReady / Invalid
+    pub state: String,
+    /// None / Accepted / Declined
+    pub player_response: String,
+    #[serde(default)]
+    pub timer: f64,
+    #[serde(default)]
+    pub decliner_ids: Vec<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[ts(
     export,
@@ -600,6 +1432,118 @@ pub struct CreateRunePageRequest {
     pub selected_perk_ids: Vec<i32>,
 }
 
+/// 符文页库存（/lol-perks/v1/inventory）
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PerkInventory.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkInventory {
+    /// 可创建的自定义符文页数量
+    pub owned_page_count: i32,
+    #[serde(default)]
+    pub custom_page_count: i32,
+}
+
+/// 符文页备份文件信息
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/RunePageBackupInfo.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RunePageBackupInfo {
+    /// 备份文件名
+    pub file: String,
+    #[ts(type = "number")]
+    pub timestamp_ms: u64,
+    pub page_count: usize,
+}
+
+/// 从备份恢复符文页的结果
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/RuneRestoreResult.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneRestoreResult {
+    /// 已恢复的符文页名称
+    pub restored: Vec<String>,
+    /// 未恢复的符文页及原因
+    pub skipped: Vec<String>,
+}
+
+/// 一套符文选择：主系、副系和 9 个符文（基石、主系 3 个、副系 2 个、属性碎片 3 个）
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/RuneSelection.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneSelection {
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    pub selected_perk_ids: Vec<i32>,
+}
+
+/// 符文选择的问题类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/RuneProblemKind.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum RuneProblemKind {
+    /// 主系不存在
+    UnknownStyle,
+    /// 副系不存在或不能搭配主系
+    InvalidSubStyle,
+    /// 符文数量不是 9 个
+    PerkCount,
+    /// 符文不存在（通常是版本更新后被移除）
+    UnknownPerk,
+    InvalidKeystone,
+    /// 符文不在对应的行中
+    WrongSlot,
+    /// 两个副系符文在同一行
+    DuplicateSecondarySlot,
+    InvalidShard,
+}
+
+/// 符文选择中的一个问题
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/RuneProblem.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneProblem {
+    pub kind: RuneProblemKind,
+    /// 在 selected_perk_ids 中的位置
+    pub index: Option<usize>,
+    pub perk_id: Option<i32>,
+    pub message: String,
+}
+
+/// 符文选择的校验结果
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/RuneValidation.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneValidation {
+    pub valid: bool,
+    pub problems: Vec<RuneProblem>,
+    /// 请求修复且可以修复时为修复后的符文选择
+    pub repaired: Option<RuneSelection>,
+}
+
 /// 装备推荐套装数据结构
 #[derive(Debug, Serialize, Deserialize, Clone, TS)]
 #[ts(
@@ -682,6 +1626,156 @@ pub struct MatchStatistics {
     pub recent_performance: Vec<RecentGame>,
 }
 
+/// 战绩查询条件，筛选条件均按被查询玩家自己的数据判断
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/MatchHistoryQuery.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryQuery {
+    /// 从战绩列表的第几场开始查找（上一页返回的 next_index）
+    pub beg_index: usize,
+    /// 查找到战绩列表的第几场为止（不含），为空时不限制
+    pub end_index: Option<usize>,
+    /// 需要的对局数量，0 表示默认的 20 场
+    pub count: usize,
+    /// 队列 id，为空时不限制
+    pub queue_ids: Vec<i32>,
+    pub champion_id: Option<i32>,
+    /// 对局创建时间范围（毫秒时间戳）
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+    /// true 只看胜场，false 只看败场
+    pub win: Option<bool>,
+}
+
+/// 一页战绩查询结果
+#[derive(Debug, Serialize, Clone, Deserialize, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/MatchHistoryPage.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchHistoryPage {
+    /// 按筛选后的对局统计
+    pub statistics: MatchStatistics,
+    /// 下一页的 beg_index，没有更多对局时为空
+    pub next_index: Option<usize>,
+    /// 本次查找过的对局数量（含被筛选掉的）
+    pub scanned: usize,
+}
+
+/// 按对局时长分段的胜率
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/GameLengthWinRate.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLengthWinRate {
+    pub min_minutes: i32,
+    /// 为空表示不设上限
+    pub max_minutes: Option<i32>,
+    pub games: i32,
+    pub wins: i32,
+    pub win_rate: f32,
+}
+
+/// 一组对局（全部、某个英雄或某个分路）的汇总数据，比率均为百分比
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/AggregateStats.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateStats {
+    pub champion_id: Option<i32>,
+    /// TOP / JUNGLE / MIDDLE / BOTTOM / UTILITY / NONE
+    pub position: Option<String>,
+    pub games: i32,
+    pub wins: i32,
+    pub win_rate: f32,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+    pub kda: f32,
+    pub cs_per_min: f32,
+    pub gold_per_min: f32,
+    pub vision_per_min: f32,
+    /// 对英雄伤害占队伍的比例，没有队友数据时为空
+    pub damage_share: Option<f32>,
+    /// 参团率，没有队友数据时为空
+    pub kill_participation: Option<f32>,
+    /// 平均对局时长（秒）
+    pub avg_game_duration: f32,
+    /// 拿到一血的场次，一血助攻不计入
+    pub first_bloods: i32,
+    pub double_kills: i32,
+    pub triple_kills: i32,
+    pub quadra_kills: i32,
+    pub penta_kills: i32,
+    pub by_game_length: Vec<GameLengthWinRate>,
+}
+
+/// 趋势中的一场对局，rolling_* 为截至该场的最近若干场的平均值
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/TrendPoint.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPoint {
+    #[ts(type = "number")]
+    pub game_id: u64,
+    pub game_creation: i64,
+    pub champion_id: i32,
+    pub win: bool,
+    pub kda: f32,
+    pub cs_per_min: f32,
+    pub gold_per_min: f32,
+    pub rolling_win_rate: f32,
+    pub rolling_kda: f32,
+}
+
+/// 战绩分析结果
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/MatchAnalytics.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAnalytics {
+    pub puuid: String,
+    pub overall: AggregateStats,
+    /// 按场次从多到少排列
+    pub by_champion: Vec<AggregateStats>,
+    pub by_position: Vec<AggregateStats>,
+    /// 按时间从旧到新排列
+    pub trend: Vec<TrendPoint>,
+}
+
+/// 本地战绩数据库概况
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/MatchStoreSummary.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchStoreSummary {
+    #[ts(type = "number")]
+    pub game_count: i64,
+    #[ts(type = "number")]
+    pub player_count: i64,
+}
+
 /// 英雄统计信息
 #[derive(Debug, Serialize, Deserialize, Clone, TS)]
 #[ts(
@@ -722,6 +1816,143 @@ pub struct RecentGame {
     pub performance_rating: String,
 }
 
+/// 选人阶段玩家标签类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export, export_to = "../../src/types/generated/PlayerTagKind.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum PlayerTagKind {
+    /// 最近连败
+    LossStreak,
+    /// 最近的对局中没有玩过当前英雄
+    FirstTimeChampion,
+    /// 最近有重开的对局
+    RecentRemake,
+    /// 最近多次提前投降
+    EarlySurrender,
+    /// 分配的位置不是常玩的位置
+    RoleMismatch,
+    /// 与同队玩家多次一起游戏
+    RepeatedDuo,
+}
+
+/// 标签依据的对局
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PlayerTagEvidence.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerTagEvidence {
+    #[ts(type = "number")]
+    pub game_id: u64,
+    #[ts(type = "number")]
+    pub game_creation: i64,
+    pub champion_id: i32,
+    pub win: bool,
+    #[ts(type = "number")]
+    pub game_duration: i64,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PlayerTag.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerTag {
+    pub kind: PlayerTagKind,
+    pub message: String,
+    pub evidence: Vec<PlayerTagEvidence>,
+}
+
+/// 选人阶段一名队友的分析结果
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/ChampSelectPlayerAnalysis.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectPlayerAnalysis {
+    pub cell_id: i32,
+    pub puuid: String,
+    pub display_name: Option<String>,
+    /// 已选择或预选的英雄
+    pub champion_id: Option<i32>,
+    pub assigned_position: Option<String>,
+    /// 参与分析的最近对局数量
+    pub games_analyzed: i32,
+    pub tags: Vec<PlayerTag>,
+}
+
+/// 开黑组中的玩家
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PremadeMember.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PremadeMember {
+    pub puuid: String,
+    pub display_name: Option<String>,
+    pub champion_id: Option<i32>,
+}
+
+/// 最近多次同队的一组玩家
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PremadeGroup.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PremadeGroup {
+    /// 是否为己方
+    pub ally: bool,
+    pub members: Vec<PremadeMember>,
+    /// 组内至少两人同队的对局，从新到旧
+    #[ts(type = "number[]")]
+    pub game_ids: Vec<u64>,
+    /// 0-1，组内多次同队的两人之间同队场次的平均可信度
+    pub confidence: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/PremadeReport.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct PremadeReport {
+    /// 玩家来源：ChampSelect 或 InProgress
+    pub phase: String,
+    /// 能够查询战绩的玩家数量
+    pub players_checked: i32,
+    pub groups: Vec<PremadeGroup>,
+}
+
+/// 对局中的玩家及其召唤师信息（含段位）和最近战绩，无法查询的玩家（如机器人）只有对局数据
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(
+    export,
+    export_to = "../../src/types/generated/LiveGamePlayer.ts",
+    rename_all = "camelCase"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveGamePlayer {
+    pub player: LiveClientPlayer,
+    /// 是否为己方
+    pub ally: bool,
+    pub puuid: Option<String>,
+    pub summoner: Option<SummonerInfo>,
+    pub recent_matches: Option<MatchStatistics>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS)]
 #[ts(
     export,
@@ -961,8 +2192,10 @@ pub struct Builds {
     export_to = "../../src/types/generated/LcuWebSocketEvent.ts",
     rename_all = "camelCase"
 )]
+#[serde(rename_all = "camelCase")]
 pub struct LcuWebSocketEvent<T> {
     pub event_type: String,
+    pub uri: String,
     pub data: T,
 }
 
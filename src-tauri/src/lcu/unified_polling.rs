@@ -1,31 +1,57 @@
 // 统一轮询管理器 - 优化版本
 use crate::lcu::{
-    auth::service::ensure_valid_auth_info,
-    champ_select::service::get_champ_select_session,
+    auth::{provider::CredentialProvider, service::ensure_valid_endpoint},
+    automation,
+    champ_select::service::{build_champ_select_session, get_champ_select_session},
+    endpoint::LcuEndpoint,
     gameflow::service::get_gameflow_phase,
+    live_client::{
+        poller::{self, LiveClientUpdate},
+        service::{get_live_game_players, LIVE_CLIENT_URL},
+    },
     lobby::service::get_lobby_info,
     matchmaking::service::{get_match_info, get_matchmaking_state},
     summoner::service::get_current_summoner,
-    types::{ChampSelectSession, LcuAuthInfo, MatchInfo, MatchmakingState, SummonerInfo},
+    types::{ChampSelectSession, LcuWebSocketEvent, MatchInfo, MatchmakingState, SummonerInfo},
+    websocket,
 };
+use serde::Serialize;
+use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
+
+/// 进入对局后等待 Live Client Data API 可用的重试间隔和次数（覆盖约 5 分钟的加载）
+const LIVE_CLIENT_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+const LIVE_CLIENT_ATTEMPTS: u32 = 60;
 
 #[derive(Clone)]
 pub struct UnifiedPollingManager {
-    app: AppHandle,
+    events: EventTarget,
     client: reqwest::Client,
     state: Arc<RwLock<PollingState>>,
     is_running: Arc<RwLock<bool>>,
+    // WebSocket 事件订阅：订阅成功后业务轮询退化为兜底
+    ws_running: Arc<RwLock<bool>>,
+    ws_active: Arc<RwLock<bool>>,
+    ws_shutdown: Arc<Notify>,
+    // 指定时只从该来源获取端点判断连接状态，不回退到进程/lockfile 发现
+    credentials: Option<Arc<dyn CredentialProvider>>,
+}
+
+/// 事件发送目标，测试时记录事件而不是发送给前端
+#[derive(Clone)]
+enum EventTarget {
+    App(AppHandle),
+    #[cfg(test)]
+    Recorder(Arc<std::sync::Mutex<Vec<(String, Value)>>>),
 }
 
 #[derive(Default, Clone)]
 struct PollingState {
     // 连接状态
     is_connected: bool,
-    auth_info: Option<LcuAuthInfo>,
 
     // 游戏状态
     current_summoner: Option<SummonerInfo>,
@@ -42,6 +68,10 @@ struct PollingState {
 
 impl UnifiedPollingManager {
     pub fn new(app: AppHandle) -> Self {
+        Self::with_events(EventTarget::App(app))
+    }
+
+    fn with_events(events: EventTarget) -> Self {
         let client = reqwest::Client::builder()
             .danger_accept_invalid_certs(true)
             .timeout(Duration::from_secs(8))
@@ -49,10 +79,26 @@ impl UnifiedPollingManager {
             .expect("Failed to create HTTP client");
 
         Self {
-            app,
+            events,
             client,
             state: Arc::new(RwLock::new(PollingState::default())),
             is_running: Arc::new(RwLock::new(false)),
+            ws_running: Arc::new(RwLock::new(false)),
+            ws_active: Arc::new(RwLock::new(false)),
+            ws_shutdown: Arc::new(Notify::new()),
+            credentials: None,
+        }
+    }
+
+    fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) -> tauri::Result<()> {
+        match &self.events {
+            EventTarget::App(app) => app.emit(event, payload),
+            #[cfg(test)]
+            EventTarget::Recorder(events) => {
+                let payload = serde_json::to_value(payload).unwrap_or(Value::Null);
+                events.lock().unwrap().push((event.to_string(), payload));
+                Ok(())
+            }
         }
     }
 
@@ -76,6 +122,7 @@ impl UnifiedPollingManager {
     pub async fn stop(&self) {
         let mut running = self.is_running.write().await;
         *running = false;
+        self.ws_shutdown.notify_waiters();
         log::info!("[统一轮询] 停止轮询管理器");
     }
 
@@ -97,15 +144,21 @@ impl UnifiedPollingManager {
 
             // 只有在连接时才进行业务轮询
             let is_connected = self.state.read().await.is_connected;
-            if is_connected {
-                self.poll_game_data().await;
-            } else {
+            if !is_connected {
                 // 未连接时减少轮询频率
                 log::debug!("[统一轮询] 未连接，跳过业务轮询");
                 tokio::time::sleep(Duration::from_secs(8)).await;
                 continue;
             }
 
+            // WebSocket 推送正常时只保留连接检查，轮询仅作为兜底
+            if *self.ws_active.read().await {
+                log::debug!("[统一轮询] WebSocket 推送正常，跳过业务轮询");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+            self.poll_game_data().await;
+
             // 动态调整轮询间隔
             let sleep_duration = self.get_adaptive_interval().await;
             tokio::time::sleep(sleep_duration).await;
@@ -114,9 +167,16 @@ impl UnifiedPollingManager {
         log::info!("[统一轮询] 主循环已退出");
     }
 
+    /// 当前可用的 LCU 端点
+    async fn current_endpoint(&self) -> Option<LcuEndpoint> {
+        match &self.credentials {
+            Some(provider) => provider.endpoint(),
+            None => ensure_valid_endpoint().await,
+        }
+    }
+
     async fn check_connection_status(&self) {
-        let auth_info = ensure_valid_auth_info();
-        let is_connected = auth_info.is_some();
+        let is_connected = self.current_endpoint().await.is_some();
 
         let mut state = self.state.write().await;
 
@@ -125,15 +185,19 @@ impl UnifiedPollingManager {
             if is_connected {
                 log::info!("[统一轮询] 检测到连接建立");
                 state.is_connected = true;
-                state.auth_info = auth_info;
 
                 // 连接建立时立即获取召唤师信息
                 drop(state);
                 self.fetch_summoner_info().await;
+
+                // 启动 WebSocket 事件订阅
+                let manager = self.clone();
+                tokio::spawn(async move {
+                    manager.run_websocket_loop().await;
+                });
             } else {
                 log::info!("[统一轮询] 检测到连接断开，清理所有状态");
                 state.is_connected = false;
-                state.auth_info = None;
 
                 // 清理所有游戏状态
                 self.clear_all_state(&mut state).await;
@@ -173,13 +237,13 @@ impl UnifiedPollingManager {
     }
 
     async fn fetch_summoner_info(&self) {
-        match retry(|| get_current_summoner(&self.client), 3, 1500).await {
+        match get_current_summoner(&self.client).await {
             Ok(summoner) => {
                 let mut state = self.state.write().await;
                 if state.current_summoner.as_ref() != Some(&summoner) {
                     log::info!("[统一轮询] 召唤师信息更新: {}", summoner.display_name);
                     state.current_summoner = Some(summoner.clone());
-                    let _ = self.app.emit("summoner-change", &Some(summoner));
+                    let _ = self.emit("summoner-change", &Some(summoner));
                 }
             }
             Err(e) => {
@@ -197,9 +261,14 @@ impl UnifiedPollingManager {
     }
 
     async fn fetch_gameflow_phase(&self) {
-        match retry(|| get_gameflow_phase(&self.client), 2, 500).await {
-            Ok(phase) => {
-                let mut state = self.state.write().await;
+        let phase = get_gameflow_phase(&self.client).await.ok();
+        self.apply_gameflow_phase(phase).await;
+    }
+
+    async fn apply_gameflow_phase(&self, phase: Option<String>) {
+        let mut state = self.state.write().await;
+        match phase {
+            Some(phase) => {
                 if state.gameflow_phase.as_ref() != Some(&phase) {
                     log::info!(
                         "[统一轮询] 游戏阶段变化: {:?} -> {}",
@@ -212,51 +281,62 @@ impl UnifiedPollingManager {
                     let now_finished = phase != "InProgress";
 
                     state.gameflow_phase = Some(phase.clone());
-                    let _ = self.app.emit("gameflow-phase-change", &Some(phase));
+                    let _ = self.emit("gameflow-phase-change", &Some(phase));
 
-                    // 游戏结束时的特殊处理
+                    if !was_in_progress && !now_finished {
+                        self.spawn_live_game_players();
+                        self.spawn_live_client_polling();
+                    }
+
+                    // 游戏结束时的特殊处理（内部会等待数据同步，放到后台执行）
                     if was_in_progress && now_finished {
-                        drop(state);
-                        self.handle_game_finished().await;
+                        let manager = self.clone();
+                        tokio::spawn(async move {
+                            manager.handle_game_finished().await;
+                        });
                     }
                 }
             }
-            Err(_) => {
-                let mut state = self.state.write().await;
+            None => {
                 if state.gameflow_phase.is_some() {
                     log::debug!("[统一轮询] 游戏阶段获取失败，清除状态");
                     state.gameflow_phase = None;
-                    let _ = self.app.emit("gameflow-phase-change", &None::<String>);
+                    let _ = self.emit("gameflow-phase-change", &None::<String>);
                 }
             }
         }
     }
 
     async fn fetch_lobby_info(&self) {
-        match retry(|| get_lobby_info(&self.client), 2, 500).await {
-            Ok(_) => {
-                let mut state = self.state.write().await;
-                if !state.in_lobby {
-                    log::debug!("[统一轮询] 进入大厅");
-                    state.in_lobby = true;
-                    let _ = self.app.emit("lobby-change", true);
-                }
-            }
-            Err(_) => {
-                let mut state = self.state.write().await;
-                if state.in_lobby {
-                    log::debug!("[统一轮询] 离开大厅");
-                    state.in_lobby = false;
-                    let _ = self.app.emit("lobby-change", false);
+        let in_lobby = get_lobby_info(&self.client).await.is_ok();
+        self.apply_lobby_state(in_lobby).await;
+    }
+
+    async fn apply_lobby_state(&self, in_lobby: bool) {
+        let mut state = self.state.write().await;
+        if state.in_lobby != in_lobby {
+            log::debug!(
+                "[统一轮询] {}",
+                if in_lobby {
+                    "进入大厅"
+                } else {
+                    "离开大厅"
                 }
-            }
+            );
+            state.in_lobby = in_lobby;
+            let _ = self.emit("lobby-change", in_lobby);
         }
     }
 
     async fn fetch_matchmaking_state(&self) {
-        match retry(|| get_matchmaking_state(&self.client), 2, 500).await {
-            Ok(matchmaking_state) => {
-                let mut state = self.state.write().await;
+        let matchmaking_state = get_matchmaking_state(&self.client).await.ok();
+        self.apply_matchmaking_state(matchmaking_state).await;
+    }
+
+    async fn apply_matchmaking_state(&self, matchmaking_state: Option<MatchmakingState>) {
+        let mut state = self.state.write().await;
+        match matchmaking_state {
+            Some(matchmaking_state) => {
                 if state.matchmaking_state.as_ref() != Some(&matchmaking_state) {
                     log::info!(
                         "[统一轮询] 匹配状态更新: {:?}",
@@ -265,21 +345,25 @@ impl UnifiedPollingManager {
 
                     // 检查是否找到匹配（在移动值之前）
                     let found_match = matchmaking_state.search_state == "Found";
+                    let was_found = state
+                        .matchmaking_state
+                        .as_ref()
+                        .is_some_and(|s| s.search_state == "Found");
 
                     state.matchmaking_state = Some(matchmaking_state.clone());
-                    let _ = self
-                        .app
-                        .emit("matchmaking-state-changed", matchmaking_state);
+                    let _ = self.emit("matchmaking-state-changed", matchmaking_state);
 
                     // 找到匹配时获取匹配详情
                     if found_match {
                         drop(state);
+                        if !was_found {
+                            self.spawn_auto_accept();
+                        }
                         self.fetch_match_info().await;
                     }
                 }
             }
-            Err(_) => {
-                let mut state = self.state.write().await;
+            None => {
                 if state.matchmaking_state.is_some() {
                     log::debug!("[统一轮询] 匹配状态获取失败，清除状态");
                     state.matchmaking_state = None;
@@ -288,6 +372,107 @@ impl UnifiedPollingManager {
         }
     }
 
+    /// 刚进入准备确认时交给自动接受处理（放到后台，不阻塞轮询）
+    fn spawn_auto_accept(&self) {
+        let settings = automation::settings::get_settings().auto_accept;
+        if !settings.enabled {
+            return;
+        }
+        let manager = self.clone();
+        tokio::spawn(async move {
+            automation::auto_accept::run(&manager.client, settings, |status| {
+                let _ = manager.emit("auto-accept-status", status);
+            })
+            .await;
+        });
+    }
+
+    /// 选人会话变化时交给自动选择/禁用处理
+    fn spawn_auto_pick_ban(&self) {
+        let settings = automation::settings::get_settings().pick_ban;
+        if !settings.pick_enabled && !settings.ban_enabled {
+            return;
+        }
+        let manager = self.clone();
+        tokio::spawn(async move {
+            automation::pick_ban::run(&manager.client, settings, |decision| {
+                let _ = manager.emit("auto-pick-ban-decision", decision);
+            })
+            .await;
+        });
+    }
+
+    /// 选人会话变化时检查是否锁定了新英雄，需要自动应用符文
+    fn spawn_auto_runes(&self) {
+        let settings = automation::settings::get_settings();
+        if !settings.auto_runes.enabled {
+            return;
+        }
+        let manager = self.clone();
+        tokio::spawn(async move {
+            automation::runes::run(&manager.client, settings, |result| {
+                let _ = manager.emit("auto-rune-applied", result);
+            })
+            .await;
+        });
+    }
+
+    /// 选人会话变化时检查是否锁定了新英雄，需要自动设置召唤师技能
+    fn spawn_auto_spells(&self) {
+        let settings = automation::settings::get_settings();
+        if !settings.auto_spells.enabled {
+            return;
+        }
+        let manager = self.clone();
+        tokio::spawn(async move {
+            automation::spells::run(&manager.client, settings, |result| {
+                let _ = manager.emit("auto-spells-applied", result);
+            })
+            .await;
+        });
+    }
+
+    /// 进入对局后查询双方玩家，对局加载完成前 Live Client Data API 不可用，按间隔重试
+    fn spawn_live_game_players(&self) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            for _ in 0..LIVE_CLIENT_ATTEMPTS {
+                if manager.state.read().await.gameflow_phase.as_deref() != Some("InProgress") {
+                    return;
+                }
+                match get_live_game_players(&manager.client, LIVE_CLIENT_URL).await {
+                    Ok(players) if !players.is_empty() => {
+                        log::info!("[统一轮询] 对局玩家查询完成: {} 人", players.len());
+                        let _ = manager.emit("live-game-players", players);
+                        return;
+                    }
+                    Ok(_) => log::debug!("[统一轮询] 对局玩家列表为空，等待加载"),
+                    Err(e) => log::debug!("[统一轮询] Live Client Data API 暂不可用: {}", e),
+                }
+                tokio::time::sleep(LIVE_CLIENT_RETRY_INTERVAL).await;
+            }
+            log::warn!("[统一轮询] 等待 Live Client Data API 超时，未能获取对局玩家");
+        });
+    }
+
+    /// 对局中轮询 Live Client Data API，阶段离开 InProgress 后停止
+    fn spawn_live_client_polling(&self) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let in_progress = || {
+                let manager = manager.clone();
+                async move { manager.state.read().await.gameflow_phase.as_deref() == Some("InProgress") }
+            };
+            poller::run(&manager.client, LIVE_CLIENT_URL, in_progress, |update| {
+                let _ = match update {
+                    LiveClientUpdate::GameData(data) => manager.emit("live-game-data", data),
+                    LiveClientUpdate::Events(events) => manager.emit("live-game-events", events),
+                };
+            })
+            .await;
+        });
+    }
+
     async fn fetch_match_info(&self) {
         match get_match_info(&self.client).await {
             Ok(match_info) => {
@@ -295,7 +480,7 @@ impl UnifiedPollingManager {
                 if state.match_info.as_ref() != Some(&match_info) {
                     log::info!("[统一轮询] 匹配信息更新");
                     state.match_info = Some(match_info.clone());
-                    let _ = self.app.emit("match-info-changed", match_info);
+                    let _ = self.emit("match-info-changed", match_info);
                 }
             }
             Err(e) => {
@@ -306,9 +491,22 @@ impl UnifiedPollingManager {
 
     async fn fetch_champ_select_session(&self) {
         log::debug!("[统一轮询] 开始获取选人阶段会话");
-        match retry(|| get_champ_select_session(&self.client), 2, 500).await {
+        match get_champ_select_session(&self.client).await {
             Ok(session) => {
                 log::debug!("[统一轮询] 成功获取选人阶段会话数据");
+                self.apply_champ_select_session(Some(session)).await;
+            }
+            Err(e) => {
+                log::warn!("[统一轮询] 获取选人阶段会话失败: {}", e);
+                self.apply_champ_select_session(None).await;
+            }
+        }
+    }
+
+    async fn apply_champ_select_session(&self, session: Option<ChampSelectSession>) {
+        automation::session::observe_session(session.as_ref());
+        match session {
+            Some(session) => {
                 let mut state = self.state.write().await;
                 let current_session = state.champ_select_session.clone();
 
@@ -316,24 +514,26 @@ impl UnifiedPollingManager {
                     log::info!("[统一轮询] 选人阶段会话更新，准备发送事件");
                     state.champ_select_session = Some(session.clone());
 
-                    match self.app.emit("champ-select-session-changed", &session) {
+                    match self.emit("champ-select-session-changed", &session) {
                         Ok(_) => log::info!("[统一轮询] champ-select-session-changed 事件发送成功"),
                         Err(e) => log::error!(
                             "[统一轮询] champ-select-session-changed 事件发送失败: {}",
                             e
                         ),
                     }
+                    self.spawn_auto_pick_ban();
+                    self.spawn_auto_runes();
+                    self.spawn_auto_spells();
                 } else {
                     log::info!("[统一轮询] 选人阶段会话无变化，跳过事件发送");
                 }
             }
-            Err(e) => {
-                log::warn!("[统一轮询] 获取选人阶段会话失败: {}", e);
+            None => {
                 let mut state = self.state.write().await;
                 if state.champ_select_session.is_some() {
-                    log::debug!("[统一轮询] 选人阶段会话获取失败，清除状态");
+                    log::debug!("[统一轮询] 选人阶段会话已结束，清除状态");
                     state.champ_select_session = None;
-                    let _ = self.app.emit(
+                    let _ = self.emit(
                         "champ-select-session-changed",
                         Option::<ChampSelectSession>::None,
                     );
@@ -342,6 +542,118 @@ impl UnifiedPollingManager {
         }
     }
 
+    /// WebSocket 事件循环：断开后按指数退避重连，客户端断开或管理器停止时退出
+    async fn run_websocket_loop(&self) {
+        {
+            let mut ws_running = self.ws_running.write().await;
+            if *ws_running {
+                log::debug!("[LCU-WS] 事件循环已在运行，跳过启动");
+                return;
+            }
+            *ws_running = true;
+        }
+
+        let mut retry_delay = Duration::from_secs(1);
+        loop {
+            if !*self.is_running.read().await {
+                break;
+            }
+            if !self.state.read().await.is_connected {
+                break;
+            }
+            // 每次重连都重新获取端点，客户端重启后 token 和端口会变化
+            let Some(endpoint) = self.current_endpoint().await else {
+                break;
+            };
+
+            match self.open_event_stream(&endpoint).await {
+                Ok(mut stream) => {
+                    *self.ws_active.write().await = true;
+                    retry_delay = Duration::from_secs(1);
+                    log::info!("[LCU-WS] 事件订阅已生效，业务轮询转为兜底");
+
+                    // 订阅前可能已错过变化，先全量同步一次
+                    self.poll_game_data().await;
+
+                    loop {
+                        tokio::select! {
+                            event = stream.next_event() => match event {
+                                Some(event) => self.handle_ws_event(event).await,
+                                None => break,
+                            },
+                            _ = self.ws_shutdown.notified() => break,
+                        }
+                    }
+
+                    *self.ws_active.write().await = false;
+                    log::warn!("[LCU-WS] 事件连接已断开，回退到轮询");
+                }
+                Err(e) => {
+                    log::warn!("[LCU-WS] {}，{:?} 后重试", e, retry_delay);
+                }
+            }
+
+            tokio::time::sleep(retry_delay).await;
+            retry_delay = (retry_delay * 2).min(Duration::from_secs(30));
+        }
+
+        *self.ws_active.write().await = false;
+        *self.ws_running.write().await = false;
+        log::info!("[LCU-WS] 事件循环已退出");
+    }
+
+    async fn open_event_stream(
+        &self,
+        endpoint: &LcuEndpoint,
+    ) -> Result<websocket::LcuEventStream, String> {
+        let mut stream = websocket::connect(endpoint).await?;
+        for uri in websocket::SUBSCRIBED_URIS {
+            stream.subscribe(uri).await?;
+        }
+        Ok(stream)
+    }
+
+    /// 将推送事件转换为与轮询相同的状态更新和前端事件
+    async fn handle_ws_event(&self, event: LcuWebSocketEvent<Value>) {
+        log::debug!("[LCU-WS] {} {}", event.event_type, event.uri);
+        let deleted = event.event_type == "Delete" || event.data.is_null();
+
+        match event.uri.as_str() {
+            "/lol-gameflow/v1/gameflow-phase" => {
+                let phase = event.data.as_str().map(String::from);
+                self.apply_gameflow_phase(phase).await;
+            }
+            "/lol-lobby/v2/lobby" => {
+                self.apply_lobby_state(!deleted).await;
+            }
+            "/lol-lobby/v2/lobby/matchmaking/search-state" => {
+                let matchmaking_state = if deleted {
+                    None
+                } else {
+                    serde_json::from_value::<MatchmakingState>(event.data).ok()
+                };
+                self.apply_matchmaking_state(matchmaking_state).await;
+            }
+            "/lol-champ-select/v1/session" => {
+                if deleted {
+                    self.apply_champ_select_session(None).await;
+                    return;
+                }
+                match build_champ_select_session(&self.client, event.data).await {
+                    Ok(session) => self.apply_champ_select_session(Some(session)).await,
+                    Err(e) => log::warn!("[LCU-WS] 解析选人阶段会话失败: {}", e),
+                }
+            }
+            "/lol-summoner/v1/current-summoner" => {
+                // 推送内容不含段位信息，重新获取完整数据
+                if !deleted {
+                    self.fetch_summoner_info().await;
+                }
+            }
+            _ => {}
+        }
+    }
+
     async fn handle_game_finished(&self) {
         log::info!("[统一轮询] 游戏结束，开始后处理");
 
@@ -352,7 +664,7 @@ impl UnifiedPollingManager {
         self.fetch_summoner_info().await;
 
         // 发送游戏结束事件让前端刷新战绩
-        let _ = self.app.emit("game-finished", ());
+        let _ = self.emit("game-finished", ());
         log::info!("[统一轮询] 游戏结束处理完成");
     }
 
@@ -366,9 +678,9 @@ impl UnifiedPollingManager {
         state.champ_select_session = None;
 
         // 发送清理事件
-        let _ = self.app.emit("summoner-change", &None::<SummonerInfo>);
-        let _ = self.app.emit("gameflow-phase-change", &None::<String>);
-        let _ = self.app.emit("lobby-change", false);
+        let _ = self.emit("summoner-change", &None::<SummonerInfo>);
+        let _ = self.emit("gameflow-phase-change", &None::<String>);
+        let _ = self.emit("lobby-change", false);
 
         log::info!("[统一轮询] 所有状态已清理并通知前端");
     }
@@ -385,23 +697,131 @@ impl UnifiedPollingManager {
     }
 }
 
-// 通用重试工具
-async fn retry<F, Fut, T, E>(mut f: F, retries: u32, delay_ms: u64) -> Result<T, E>
-where
-    F: FnMut() -> Fut,
-    Fut: std::future::Future<Output = Result<T, E>>,
-{
-    let mut last_err = None;
-    for _ in 0..retries {
-        match f().await {
-            Ok(val) => return Ok(val),
-            Err(e) => {
-                last_err = Some(e);
-                if retries > 1 {
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcu;
+    use std::sync::Mutex;
+
+    type Events = Arc<std::sync::Mutex<Vec<(String, Value)>>>;
+
+    fn recording_manager() -> (UnifiedPollingManager, Events) {
+        let events: Events = Default::default();
+        let manager = UnifiedPollingManager::with_events(EventTarget::Recorder(events.clone()));
+        (manager, events)
+    }
+
+    /// 取出并清空已记录的事件
+    fn take(events: &Events) -> Vec<(String, Value)> {
+        std::mem::take(&mut *events.lock().unwrap())
+    }
+
+    fn payloads<'a>(events: &'a [(String, Value)], name: &str) -> Vec<&'a Value> {
+        events
+            .iter()
+            .filter(|(event, _)| event == name)
+            .map(|(_, payload)| payload)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn polling_follows_lobby_to_champ_select() {
+        let mock = MockLcu::start().await;
+        let (manager, events) = recording_manager();
+
+        // 连接建立：获取召唤师信息
+        mock.transition("Lobby");
+        manager.check_connection_status().await;
+        let emitted = take(&events);
+        let summoner = payloads(&emitted, "summoner-change");
+        assert_eq!(summoner.len(), 1);
+        assert_eq!(summoner[0]["displayName"], "Nidalee#CN1");
+
+        // 大厅
+        manager.poll_game_data().await;
+        let emitted = take(&events);
+        assert_eq!(
+            payloads(&emitted, "gameflow-phase-change"),
+            [&Value::from("Lobby")]
+        );
+        assert_eq!(payloads(&emitted, "lobby-change"), [&Value::Bool(true)]);
+        assert_eq!(
+            payloads(&emitted, "matchmaking-state-changed")[0]["searchState"],
+            "Invalid"
+        );
+
+        // 匹配中
+        mock.transition("Matchmaking");
+        manager.poll_game_data().await;
+        let emitted = take(&events);
+        assert_eq!(
+            payloads(&emitted, "gameflow-phase-change"),
+            [&Value::from("Matchmaking")]
+        );
+        assert!(payloads(&emitted, "lobby-change").is_empty());
+        assert_eq!(
+            payloads(&emitted, "matchmaking-state-changed")[0]["searchState"],
+            "Searching"
+        );
+
+        // 找到对局，等待确认
+        mock.transition("ReadyCheck");
+        manager.poll_game_data().await;
+        let emitted = take(&events);
+        assert_eq!(
+            payloads(&emitted, "matchmaking-state-changed")[0]["searchState"],
+            "Found"
+        );
+
+        // 进入选人
+        mock.transition("ChampSelect");
+        manager.poll_game_data().await;
+        let emitted = take(&events);
+        assert_eq!(
+            payloads(&emitted, "gameflow-phase-change"),
+            [&Value::from("ChampSelect")]
+        );
+        let sessions = payloads(&emitted, "champ-select-session-changed");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0]["localPlayerCellId"], 0);
+
+        // 会话未变化时不重复发送
+        manager.poll_game_data().await;
+        assert!(payloads(&take(&events), "champ-select-session-changed").is_empty());
+    }
+
+    /// 可以随时断开的凭据来源，模拟客户端退出
+    struct SwitchableProvider(Mutex<Option<LcuEndpoint>>);
+
+    impl CredentialProvider for SwitchableProvider {
+        fn name(&self) -> &str {
+            "switchable"
         }
+
+        fn endpoint(&self) -> Option<LcuEndpoint> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn polling_clears_state_when_client_disconnects() {
+        let mock = MockLcu::start().await;
+        let (mut manager, events) = recording_manager();
+        let credentials = Arc::new(SwitchableProvider(Mutex::new(Some(mock.endpoint.clone()))));
+        manager.credentials = Some(credentials.clone());
+
+        mock.transition("Lobby");
+        manager.check_connection_status().await;
+        manager.poll_game_data().await;
+        take(&events);
+
+        // 客户端退出：不再有可用端点（模拟服务保持运行，不会回退到真实的进程发现）
+        *credentials.0.lock().unwrap() = None;
+        manager.check_connection_status().await;
+
+        let emitted = take(&events);
+        assert_eq!(payloads(&emitted, "summoner-change"), [&Value::Null]);
+        assert_eq!(payloads(&emitted, "gameflow-phase-change"), [&Value::Null]);
+        assert_eq!(payloads(&emitted, "lobby-change"), [&Value::Bool(false)]);
     }
-    Err(last_err.unwrap())
 }
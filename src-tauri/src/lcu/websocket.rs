@@ -0,0 +1,128 @@
+//! LCU WebSocket 事件订阅（WAMP 1.0），用于替代大部分轮询
+use crate::http_client;
+use crate::lcu::endpoint::LcuEndpoint;
+use crate::lcu::types::LcuWebSocketEvent;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
+
+// WAMP 1.0 消息类型
+const WAMP_SUBSCRIBE: u8 = 5;
+const WAMP_EVENT: u8 = 8;
+
+/// 需要订阅的 LCU 资源，推送内容与对应的 GET 接口一致
+pub const SUBSCRIBED_URIS: &[&str] = &[
+    "/lol-gameflow/v1/gameflow-phase",
+    "/lol-lobby/v2/lobby",
+    "/lol-lobby/v2/lobby/matchmaking/search-state",
+    "/lol-champ-select/v1/session",
+    "/lol-summoner/v1/current-summoner",
+];
+
+/// 已建立的 LCU 事件连接
+pub struct LcuEventStream {
+    inner: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+/// 连接端点对应的 WebSocket 地址，LCU 使用自签名证书，需要跳过校验
+pub async fn connect(endpoint: &LcuEndpoint) -> Result<LcuEventStream, String> {
+    let url = endpoint.ws_url();
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| format!("构建 WebSocket 请求失败: {}", e))?;
+
+    let header = HeaderValue::from_str(&endpoint.authorization())
+        .map_err(|e| format!("构建认证头失败: {}", e))?;
+    request.headers_mut().insert("Authorization", header);
+
+    log::info!("[LCU-WS] 正在连接 {}", url);
+    let (inner, _) = tokio_tungstenite::connect_async_tls_with_config(
+        request,
+        None,
+        false,
+        Some(Connector::Rustls(http_client::get_lcu_tls_config())),
+    )
+    .await
+    .map_err(|e| format!("WebSocket 连接失败: {}", e))?;
+
+    log::info!("[LCU-WS] 连接成功");
+    Ok(LcuEventStream { inner })
+}
+
+impl LcuEventStream {
+    /// 订阅某个资源的 OnJsonApiEvent
+    pub async fn subscribe(&mut self, uri: &str) -> Result<(), String> {
+        let topic = event_topic(uri);
+        let message = serde_json::json!([WAMP_SUBSCRIBE, topic]).to_string();
+        self.inner
+            .send(Message::Text(message))
+            .await
+            .map_err(|e| format!("订阅 {} 失败: {}", topic, e))?;
+        log::debug!("[LCU-WS] 已订阅 {}", topic);
+        Ok(())
+    }
+
+    /// 读取下一条事件，连接关闭或出错时返回 None
+    pub async fn next_event(&mut self) -> Option<LcuWebSocketEvent<Value>> {
+        while let Some(message) = self.inner.next().await {
+            match message {
+                Ok(Message::Text(text)) => {
+                    if let Some(event) = parse_event(&text) {
+                        return Some(event);
+                    }
+                }
+                Ok(Message::Close(frame)) => {
+                    log::info!("[LCU-WS] 服务端关闭连接: {:?}", frame);
+                    return None;
+                }
+                // Ping/Pong 由 tungstenite 自动处理
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("[LCU-WS] 读取消息失败: {}", e);
+                    return None;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// "/lol-gameflow/v1/gameflow-phase" -> "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase"
+fn event_topic(uri: &str) -> String {
+    format!("OnJsonApiEvent{}", uri.replace('/', "_"))
+}
+
+/// 解析 [8, topic, {"data", "eventType", "uri"}] 格式的事件消息
+fn parse_event(text: &str) -> Option<LcuWebSocketEvent<Value>> {
+    let message: Value = serde_json::from_str(text).ok()?;
+    let parts = message.as_array()?;
+    if parts.first()?.as_u64()? != WAMP_EVENT as u64 {
+        return None;
+    }
+    match serde_json::from_value::<LcuWebSocketEvent<Value>>(parts.get(2)?.clone()) {
+        Ok(event) => Some(event),
+        Err(e) => {
+            log::debug!("[LCU-WS] 忽略无法解析的事件: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcu;
+
+    #[tokio::test]
+    async fn connect_accepts_self_signed_certificate() {
+        let mock = MockLcu::start().await;
+        // 模拟服务不支持 WebSocket 升级：TLS 握手成功后才会收到 HTTP 错误
+        let err = connect(&mock.endpoint).await.err().unwrap();
+        assert!(err.contains("HTTP error"), "{}", err);
+    }
+}
@@ -20,14 +20,36 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // 认证 / 连接
             lcu::auth::commands::get_auth_info,
+            lcu::auth::commands::get_auth_discovery_config,
+            lcu::auth::commands::save_auth_discovery_config,
+            lcu::auth::commands::get_auth_discovery_report,
             lcu::connection::commands::get_connection_state,
             lcu::connection::commands::force_refresh_connection,
             lcu::connection::commands::check_connection_state_command,
+            lcu::traffic::commands::start_lcu_capture,
+            lcu::traffic::commands::start_lcu_replay,
+            lcu::traffic::commands::stop_lcu_traffic,
+            lcu::traffic::commands::get_lcu_traffic_status,
+            lcu::passthrough::commands::lcu_passthrough,
+            lcu::passthrough::commands::get_passthrough_config,
+            lcu::passthrough::commands::save_passthrough_config,
+            lcu::passthrough::commands::get_passthrough_audit,
 
             // 游戏流程 / 英雄选择 / 匹配
             lcu::gameflow::commands::get_game_version,
             lcu::gameflow::commands::get_live_player_list,
+            lcu::live_client::commands::get_live_game_players,
+            lcu::live_client::commands::get_live_all_game_data,
+            lcu::live_client::commands::get_live_active_player,
+            lcu::live_client::commands::get_live_active_player_abilities,
+            lcu::live_client::commands::get_live_active_player_runes,
+            lcu::live_client::commands::get_live_player_scores,
+            lcu::live_client::commands::get_live_player_items,
+            lcu::live_client::commands::get_live_event_data,
+            lcu::live_client::commands::get_live_game_stats,
             lcu::champ_select::commands::get_champselect_team_players_info,
+            lcu::champ_select::commands::analyze_champ_select_team,
+            lcu::premade::commands::detect_premades,
             lcu::champ_select::commands::get_champ_select_session,
             lcu::champ_select::commands::get_champ_select_session_typed,
             lcu::champ_select::commands::pick_champion,
@@ -37,9 +59,21 @@ pub fn run() {
             lcu::matchmaking::commands::accept_match,
             lcu::matchmaking::commands::decline_match,
 
+            // 自动化
+            lcu::automation::commands::get_automation_settings,
+            lcu::automation::commands::save_automation_settings,
+            lcu::automation::commands::cancel_auto_accept,
+            lcu::automation::commands::pin_current_rune_page,
+            lcu::automation::commands::unpin_rune_page,
+            lcu::automation::commands::apply_summoner_spells,
+
             // 比赛记录
             lcu::matches::commands::get_match_history,
             lcu::matches::commands::get_game_detail,
+            lcu::matches::commands::query_match_history,
+            lcu::matches::commands::get_stored_match_history,
+            lcu::matches::commands::get_match_store_summary,
+            lcu::matches::commands::get_match_analytics,
 
             // 召唤师
             lcu::summoner::commands::get_current_summoner,
@@ -53,6 +87,14 @@ pub fn run() {
             lcu::perks::commands::get_lcu_rune_styles,
             lcu::perks::commands::get_lcu_perks,
             lcu::perks::commands::get_lcu_perk_icon,
+            lcu::perks::commands::backup_rune_pages,
+            lcu::perks::commands::list_rune_page_backups,
+            lcu::perks::commands::restore_rune_pages,
+            lcu::perks::commands::validate_rune_selection,
+
+            // 物品集
+            lcu::item_sets::commands::apply_opgg_item_set,
+            lcu::item_sets::commands::apply_champr_item_set,
 
             // OPGG 相关
             lcu::opgg::commands::get_opgg_champion_build,
@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'vue-sonner'
 import { useActivityLogger } from '@/composables/utils/useActivityLogger'
+import { formatLcuError } from '@/lib'
 
 export function useGameHelper() {
   const activityLogger = useActivityLogger()
@@ -24,7 +25,7 @@ export function useGameHelper() {
       })
     } catch (error) {
       toast.error('设置生涯背景失败', {
-        description: String(error),
+        description: formatLcuError(error),
         duration: 5000
       })
     }
@@ -73,7 +74,7 @@ export function useGameHelper() {
       await invoke('set_summoner_chat_profile', { statusMessage, queue, tier, division })
       toast.success('资料修改成功', { duration: 3000 })
     } catch (error) {
-      toast.error('资料修改失败', { description: String(error), duration: 5000 })
+      toast.error('资料修改失败', { description: formatLcuError(error), duration: 5000 })
     }
   }
 
@@ -91,7 +92,7 @@ export function useGameHelper() {
       toast.success('签名修改成功', { description: '召唤师签名已更新', duration: 3000 })
       activityLogger.logSettings.setCareerBackground('修改签名')
     } catch (error) {
-      toast.error('签名修改失败', { description: String(error), duration: 5000 })
+      toast.error('签名修改失败', { description: formatLcuError(error), duration: 5000 })
     } finally {
       updatingNote.value = false
     }
@@ -119,7 +120,7 @@ export function useGameHelper() {
       toast.success('段位信息修改成功', { description: '聊天段位已更新', duration: 3000 })
       activityLogger.logSettings.setCareerBackground('修改段位')
     } catch (error) {
-      toast.error('段位信息修改失败', { description: String(error), duration: 5000 })
+      toast.error('段位信息修改失败', { description: formatLcuError(error), duration: 5000 })
     } finally {
       updatingRank.value = false
     }
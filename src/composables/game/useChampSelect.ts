@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core'
+import { formatLcuError } from '@/lib'
 
 export function useChampSelect() {
   /**
@@ -13,7 +14,7 @@ export function useChampSelect() {
       return session
     } catch (error) {
       console.error('[🔍 ChampSelect] ❌ 获取选人会话失败:', error)
-      throw new Error(`获取选人会话失败: ${error}`)
+      throw new Error(`获取选人会话失败: ${formatLcuError(error)}`)
     }
   }
 
@@ -36,7 +37,7 @@ export function useChampSelect() {
       console.log(`[⭐ ChampSelect] ✅ ${completed ? '英雄已锁定' : '英雄已Hover'}`)
     } catch (error) {
       console.error('[⭐ ChampSelect] ❌ 选择英雄失败:', error)
-      throw new Error(`选择英雄失败: ${error}`)
+      throw new Error(`选择英雄失败: ${formatLcuError(error)}`)
     }
   }
 
@@ -53,7 +54,7 @@ export function useChampSelect() {
       console.log('[🚫 ChampSelect] ✅ 英雄已被禁用')
     } catch (error) {
       console.error('[🚫 ChampSelect] ❌ 禁用英雄失败:', error)
-      throw new Error(`禁用英雄失败: ${error}`)
+      throw new Error(`禁用英雄失败: ${formatLcuError(error)}`)
     }
   }
 
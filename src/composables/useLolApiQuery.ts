@@ -39,19 +39,7 @@ export function useBuildsByAliasQuery(source: string, champion: string) {
 export function usePlayerListQuery(enabled: MaybeRefOrGetter<boolean>) {
   return useQuery({
     queryKey: ['liveclient-playerlist'],
-    queryFn: async () => {
-      const result = await invoke<string>('get_live_player_list')
-      if (typeof result === 'string') {
-        try {
-          return JSON.parse(result) as LiveClientPlayer[]
-        } catch (error) {
-          console.error('Failed to parse player list JSON:', error)
-          return []
-        }
-      }
-      // 如果后端直接返回了数组，也做兼容
-      return Array.isArray(result) ? result : []
-    },
+    queryFn: () => invoke<LiveClientPlayer[]>('get_live_player_list'),
     enabled: () => toValue(enabled)
   })
 }
@@ -6,6 +6,9 @@ import { getGameTypeById, getMapById } from '@/common'
 // 主题配置模块
 export * from './theme'
 
+// LCU 错误处理
+export * from './lcuError'
+
 // 其他辅助函数
 export const getPlayerProfileIcon = (participantId: number, gameDetail: GameDetail): number => {
   const identity = gameDetail.participants?.find((id) => id.participantId === participantId)
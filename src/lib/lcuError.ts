@@ -0,0 +1,55 @@
+// LCU 命令错误（与后端 LcuError 对应，按 kind 区分类型）
+
+const LCU_ERROR_KINDS: LcuError['kind'][] = [
+  'notConnected',
+  'authExpired',
+  'httpStatus',
+  'timeout',
+  'decode',
+  'transport',
+  'forbidden',
+  'other'
+]
+
+/**
+ * 判断 invoke 抛出的错误是否为后端返回的 LcuError
+ */
+export const isLcuError = (error: unknown): error is LcuError => {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    LCU_ERROR_KINDS.includes((error as { kind?: unknown }).kind as LcuError['kind'])
+  )
+}
+
+/**
+ * 把 invoke 抛出的错误转换为可展示的文本，优先使用 LCU 返回的 message
+ * @param error catch 到的错误（LcuError、字符串或 Error）
+ * @returns 错误描述
+ */
+export const formatLcuError = (error: unknown): string => {
+  if (!isLcuError(error)) {
+    if (error instanceof Error) return error.message
+    return typeof error === 'string' ? error : '未知错误'
+  }
+
+  switch (error.kind) {
+    case 'notConnected':
+    case 'other':
+      return error.message
+    case 'authExpired':
+      return `认证信息已失效 (${error.status})${error.message ? `: ${error.message}` : ''}`
+    case 'httpStatus': {
+      const code = error.errorCode ? ` [${error.errorCode}]` : ''
+      return `服务器返回错误 ${error.status}${code}${error.message ? `: ${error.message}` : ''}`
+    }
+    case 'timeout':
+      return `请求超时: ${error.path}`
+    case 'decode':
+      return `解析响应失败: ${error.message}`
+    case 'transport':
+      return `请求失败: ${error.message}`
+    case 'forbidden':
+      return `请求被拒绝: ${error.reason}`
+  }
+}
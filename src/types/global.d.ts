@@ -184,6 +184,12 @@ type ItemSet = { title: string, champion: string, mode: string, map: string, blo
 type LcuAuthInfo = { appPort: number, remotingAuthToken: string, riotclientAppPort: number, riotclientAuthToken: string, };
 
 
+// --- 从 LcuError.ts 同步 ---
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+type LcuError = { "kind": "notConnected", message: string, } | { "kind": "authExpired", status: number, method: string, path: string, elapsedMs: number, errorCode: string | null, message: string | null, body: string | null, } | { "kind": "httpStatus", status: number, method: string, path: string, elapsedMs: number, errorCode: string | null, message: string | null, body: string | null, } | { "kind": "timeout", path: string, } | { "kind": "decode", path: string, message: string, } | { "kind": "transport", message: string, } | { "kind": "forbidden", method: string, path: string, reason: string, } | { "kind": "other", message: string, };
+
+
 // --- 从 LcuWebSocketEvent.ts 同步 ---
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 